@@ -33,6 +33,13 @@ fn make_log_obj(ty: LogType, args: &[&str], tag: &str) -> LogObject {
         icon: None,
         style: None,
         error: None,
+        group_depth: 0,
+        group_event: None,
+        fields: Vec::new(),
+        repeated: 0,
+        trace_id: None,
+        prefix: None,
+        extensions: Default::default(),
     }
 }
 
@@ -71,7 +78,7 @@ fn log_object_building(bencher: divan::Bencher) {
 
 #[divan::bench]
 fn basic_reporter_format(bencher: divan::Bencher) {
-    let reporter = BasicReporter;
+    let reporter = BasicReporter::new();
     let log_obj = make_log_obj(LogType::Info, &["hello", "world"], "tag");
     let ctx = make_log_ctx();
 
@@ -185,7 +192,7 @@ fn format_tree_bench(bencher: divan::Bencher) {
 fn consola_info_small(bencher: divan::Bencher) {
     let consola = Consola::new(ConsolaOpts {
         level: 3,
-        reporters: vec![Box::new(BasicReporter)],
+        reporters: vec![Box::new(BasicReporter::new())],
         ..ConsolaOpts::default()
     });
 
@@ -196,7 +203,7 @@ fn consola_info_small(bencher: divan::Bencher) {
 fn consola_info_medium(bencher: divan::Bencher) {
     let consola = Consola::new(ConsolaOpts {
         level: 3,
-        reporters: vec![Box::new(BasicReporter)],
+        reporters: vec![Box::new(BasicReporter::new())],
         ..ConsolaOpts::default()
     });
 
@@ -211,7 +218,7 @@ fn consola_info_medium(bencher: divan::Bencher) {
 fn consola_info_large(bencher: divan::Bencher) {
     let consola = Consola::new(ConsolaOpts {
         level: 3,
-        reporters: vec![Box::new(BasicReporter)],
+        reporters: vec![Box::new(BasicReporter::new())],
         ..ConsolaOpts::default()
     });
     let large =
@@ -224,7 +231,7 @@ fn consola_info_large(bencher: divan::Bencher) {
 fn consola_info_multiline(bencher: divan::Bencher) {
     let consola = Consola::new(ConsolaOpts {
         level: 3,
-        reporters: vec![Box::new(BasicReporter)],
+        reporters: vec![Box::new(BasicReporter::new())],
         ..ConsolaOpts::default()
     });
 
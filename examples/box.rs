@@ -34,6 +34,7 @@ fn main() {
                     border_style: "double-single-rounded".into(),
                     ..BoxStyle::default()
                 }),
+                ..BoxOpts::default()
             },
         ),
     );
@@ -51,6 +52,7 @@ fn main() {
                     border_style: "rounded".into(),
                     ..BoxStyle::default()
                 }),
+                ..BoxOpts::default()
             },
         ),
     );
@@ -3,7 +3,7 @@
 // This module is conditionally compiled when feature = "log".
 // See `super::log_impl` declaration in mod.rs.
 
-use crate::constants::LogType;
+use crate::constants::{LogType, passes_level};
 #[cfg(feature = "backtrace")]
 use crate::types::ErrorInfo;
 use crate::types::LogObject;
@@ -19,7 +19,7 @@ impl log::Log for Consola {
             log::Level::Debug => 4,
             log::Level::Trace => 5,
         };
-        level <= self.level()
+        passes_level(level, self.level_for_target(metadata.target()))
     }
 
     fn log(&self, record: &log::Record<'_>) {
@@ -30,7 +30,7 @@ impl log::Log for Consola {
             log::Level::Debug => 4,
             log::Level::Trace => 5,
         };
-        if raw_level > self.level() {
+        if !passes_level(raw_level, self.level_for_target(record.target())) {
             return;
         }
 
@@ -59,12 +59,10 @@ impl log::Log for Consola {
             });
         }
 
-        self._emit(&log_obj);
+        self.emit_entry(log_obj);
     }
 
     fn flush(&self) {
-        use std::io::Write;
-        let _ = std::io::stdout().flush();
-        let _ = std::io::stderr().flush();
+        self.flush_outputs();
     }
 }
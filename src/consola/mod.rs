@@ -1,16 +1,24 @@
 //! The [`Consola`] logger — core struct, log methods, and crate integrations.
 //!
-//! Emission goes through `log` or `tracing` crates. There is no IO.
+//! Reporters format each entry and the configured [`Sink`](crate::Sink)s
+//! write it (stdout and stderr by default); the `log` and `tracing` features
+//! bridge those crates in.
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::Instant;
 
-#[cfg(feature = "tracing")]
-use std::collections::HashMap;
-
-use crate::sync::Mutex;
+use crate::sync::{Condvar, Mutex};
 
-use crate::constants::{LogLevel, LogType, log_levels, log_type_defaults, normalize_log_level};
-use crate::types::{ConsolaOptions, LogContext, LogObject, LogObjectInput, Reporter};
+use crate::constants::{
+    LOG_TYPES, LogLevel, LogType, log_levels, log_type_defaults, normalize_log_level, passes_level,
+};
+use crate::types::{
+    ConsolaOptions, ErrorInfo, FormatOptions, GroupEvent, LogContext, LogObject, LogObjectInput,
+    LogStats, QueueOverflow, RateLimit, Reporter, ThrottleConfig, merge_fields,
+};
+use crate::util::clock::Clock;
+use crate::util::table::TableBuilder;
+use crate::util::tree::{TreeItem, TreeOptions, format_tree};
 
 /// `log` crate integration.
 #[cfg(feature = "log")]
@@ -21,17 +29,131 @@ pub mod tracing_impl;
 
 #[derive(Debug, Clone)]
 struct LastLogInfo {
-    serialized: String,
+    fingerprint: Vec<u8>,
     object: LogObject,
     count: u32,
     time: Option<Instant>,
+    /// Throttle settings the entry was logged under.
+    throttle: ThrottleConfig,
+}
+
+impl LastLogInfo {
+    /// Number of throttled repeats awaiting a `(repeated N times)` summary.
+    fn pending_repeats(&self) -> u32 {
+        // A lone entry is not a repeat, even with a `throttle_min` of 0.
+        if self.count > 1 {
+            self.count.saturating_sub(self.throttle.min_count)
+        } else {
+            0
+        }
+    }
+}
+
+/// A call recorded while logging is paused, replayed in order on resume.
+enum Queued {
+    Log(Box<LogObjectInput>, Vec<String>, LogMode, LogTime),
+    Group(GroupEvent, String),
+}
+
+/// How a log call was made.
+#[derive(Debug, Clone, Copy, Default)]
+struct LogMode {
+    /// Logged through a `_raw` method.
+    raw: bool,
+    /// Logged through a `_once` method: dropped if an identical entry was
+    /// already logged once.
+    once: bool,
 }
 
+/// When a log call was made. Queued calls keep the time of the original
+/// call, so throttling and timestamps on resume match what would have
+/// happened without the pause.
+#[derive(Debug, Clone, Copy)]
+struct LogTime {
+    instant: Option<Instant>,
+    timestamp_ms: i64,
+}
+
+impl LogTime {
+    fn now(clock: &dyn Clock) -> Self {
+        Self {
+            instant: clock.now(),
+            timestamp_ms: clock.now_ms(),
+        }
+    }
+}
+
+/// Distinct entries seen in the current `cardinality_window`.
+#[derive(Default)]
+struct CardinalityGuard {
+    window_start: Option<Instant>,
+    /// Holds at most `cardinality_limit` fingerprints.
+    seen: HashSet<Vec<u8>>,
+    suppressed: u64,
+}
+
+/// Which of the two sinks an entry is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stream {
+    Out,
+    Err,
+}
+
+/// Consecutive write failures of a sink, for `write_failure_limit`.
+#[derive(Default, Clone, Copy)]
+struct SinkHealth {
+    failures: u32,
+    latched: bool,
+}
+
+/// Callback registered with [`Consola::on_record`].
+type RecordHook = std::sync::Arc<dyn Fn(&LogObject) + Send + Sync>;
+
+/// Identifies a hook registered with [`Consola::on_record`], for
+/// [`Consola::remove_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookHandle(u64);
+
 #[derive(Default)]
 struct ConsolaState {
     paused: bool,
-    queue: Vec<(LogObjectInput, Vec<String>, bool)>,
+    silenced: bool,
+    closed: bool,
+    /// Thread running `shutdown`, set before `closed` while the pause queue
+    /// is replayed.
+    closing: Option<std::thread::ThreadId>,
+    /// Set once `shutdown` has replayed, flushed and returned.
+    shut_down: bool,
+    queue: VecDeque<Queued>,
+    /// Number of `Queued::Log` calls in `queue`; only these count towards
+    /// `pause_queue_capacity`.
+    queued_logs: usize,
+    /// Thread that called `pause_logs`, which must not block on a full queue.
+    paused_by: Option<std::thread::ThreadId>,
+    /// Calls discarded because the pause queue was full.
+    queue_dropped: u64,
+    /// Entries folded into a throttle summary since creation.
+    throttle_suppressed: u64,
+    /// Token bucket of `rate_limit`: thousandths of an entry left and when
+    /// it was last refilled.
+    rate_bucket: Option<(u64, Instant)>,
+    /// Entries dropped by `rate_limit` since creation.
+    rate_limited: u64,
+    group_labels: Vec<String>,
     last_log: Option<LastLogInfo>,
+    /// Error of the last emitted entry and when it was logged, for
+    /// `error_chain_window`.
+    last_error: Option<(ErrorInfo, Option<Instant>)>,
+    /// Fingerprints of entries logged through the `_once` methods.
+    once_seen: HashSet<Vec<u8>>,
+    cardinality: CardinalityGuard,
+    /// Write health of `out_sink` and `err_sink`.
+    out_health: SinkHealth,
+    err_health: SinkHealth,
+    stats: HashMap<LogType, LogStats>,
+    /// Hooks from `on_record`, in registration order.
+    hooks: Vec<(HookHandle, RecordHook)>,
+    next_hook_id: u64,
     #[cfg(feature = "tracing")]
     span_id_counter: u64,
     #[cfg(feature = "tracing")]
@@ -46,10 +168,45 @@ struct ConsolaState {
     span_follows_from: HashMap<u64, Vec<u64>>,
 }
 
+impl ConsolaState {
+    /// Empty the pause queue, returning the calls it held.
+    fn take_queue(&mut self) -> VecDeque<Queued> {
+        self.queued_logs = 0;
+        std::mem::take(&mut self.queue)
+    }
+}
+
 /// The main logger struct. Thread-safe; all methods take `&self`.
+///
+/// Share one instance across threads through an `Arc<Consola>` or a
+/// `static`; wrapping it in a `Mutex` is unnecessary. Internal locks are held
+/// only to update throttle and group state, not while reporters format or
+/// sinks write, so concurrent calls do not serialize on output. Entries from
+/// one thread are written in the order that thread logged them.
+///
+/// # Return values
+///
+/// The logging methods return `true` when the entry was accepted: written,
+/// queued while [paused](Self::pause_logs), or folded into a throttle
+/// summary. They return `false` when it was discarded: filtered by level,
+/// [silenced](Self::silence), logged after [`shutdown`](Self::shutdown),
+/// a repeat of a `_once` entry, over `cardinality_limit` or `rate_limit`,
+/// or dropped from a full pause queue. Discarded entries are counted in
+/// [`stats`](Self::stats).
 pub struct Consola {
     options: Mutex<ConsolaOptions>,
     state: Mutex<ConsolaState>,
+    /// Notified when the pause queue gains room or logging stops being
+    /// paused, for [`QueueOverflow::Block`].
+    queue_space: Condvar,
+    /// Notified when `shutdown` completes, for concurrent callers.
+    shutdown_done: Condvar,
+}
+
+impl Drop for Consola {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
 }
 
 impl std::fmt::Debug for Consola {
@@ -62,24 +219,116 @@ impl std::fmt::Debug for Consola {
 
 impl Consola {
     /// Create a new `Consola` instance with the given options.
-    pub fn new(options: ConsolaOptions) -> Self {
+    pub fn new(mut options: ConsolaOptions) -> Self {
+        let format_options = &mut options.format_options;
+        format_options.time_origin_ms = format_options
+            .time_origin_ms
+            .or_else(|| Some(options.clock.now_ms()));
         Self {
             options: Mutex::new(options),
             state: Mutex::new(ConsolaState::default()),
+            queue_space: Condvar::new(),
+            shutdown_done: Condvar::new(),
         }
     }
 
+    /// Returns the logger name, if one was set.
+    pub fn name(&self) -> Option<String> {
+        self.options.lock().name.clone()
+    }
+
     /// Returns the current log level.
     pub fn level(&self) -> LogLevel {
         self.options.lock().level
     }
 
     /// Set the log level. Filters out messages below this level.
+    ///
+    /// Levels are clamped to `0..=5`, except [`log_levels::SILENT`], which is
+    /// kept as-is so that nothing is logged at all.
     pub fn set_level(&self, level: LogLevel) {
-        let normalized = normalize_log_level(Some(level), log_levels::INFO);
+        let normalized = if level == log_levels::SILENT {
+            level
+        } else {
+            normalize_log_level(Some(level), log_levels::INFO)
+        };
         self.options.lock().level = normalized;
     }
 
+    /// Whether entries of type `ty` pass the current level and the instance
+    /// is not [silenced](Self::silence), so callers can skip building
+    /// messages that would be filtered out.
+    pub fn is_enabled(&self, ty: LogType) -> bool {
+        passes_level(ty.level(), self.level()) && !self.is_silenced()
+    }
+
+    /// Returns a copy of the formatting options passed to reporters.
+    pub fn format_options(&self) -> FormatOptions {
+        self.options.lock().format_options.clone()
+    }
+
+    /// Replace the formatting options passed to reporters, e.g. to turn off
+    /// dates or force compact output on an instance from
+    /// [`create_consola`](crate::create_consola).
+    pub fn set_format_options(&self, mut format_options: FormatOptions) {
+        let mut opts = self.options.lock();
+        format_options.time_origin_ms = format_options
+            .time_origin_ms
+            .or(opts.format_options.time_origin_ms);
+        opts.format_options = format_options;
+    }
+
+    /// Set the column width reporters wrap, truncate and align to (`None`
+    /// for no limit).
+    pub fn set_columns(&self, columns: Option<u16>) {
+        self.options.lock().format_options.columns = columns;
+    }
+
+    /// Set whether reporters emit ANSI colors.
+    pub fn set_colors(&self, colors: bool) {
+        self.options.lock().format_options.colors = colors;
+    }
+
+    /// Replace the per-target level overrides used by the `log` crate bridge.
+    ///
+    /// See [`TargetLevels`](crate::types::TargetLevels) for the matching rules.
+    pub fn set_target_levels(&self, target_levels: crate::types::TargetLevels) {
+        self.options.lock().target_levels = target_levels;
+    }
+
+    /// Returns the current repeat throttling settings.
+    pub fn throttle(&self) -> ThrottleConfig {
+        let opts = self.options.lock();
+        ThrottleConfig {
+            window_ms: opts.throttle,
+            min_count: opts.throttle_min,
+        }
+    }
+
+    /// Replace the repeat throttling settings, e.g. with
+    /// [`ThrottleConfig::disabled`](crate::types::ThrottleConfig::disabled).
+    /// A pending throttle summary is emitted first.
+    pub fn set_throttle(&self, throttle: ThrottleConfig) {
+        self.flush();
+        let mut opts = self.options.lock();
+        opts.throttle = throttle.window_ms;
+        opts.throttle_min = throttle.min_count;
+    }
+
+    /// Replace the redactors applied to each entry before it is throttled
+    /// and emitted. See [`ConsolaOptions::redactors`].
+    pub fn set_redactors(&self, redactors: Vec<std::sync::Arc<dyn crate::util::Redactor>>) {
+        self.options.lock().redactors = redactors;
+    }
+
+    /// Level applied to a bridged record from `target`: its per-target
+    /// override when one matches, the instance level otherwise.
+    #[cfg(feature = "log")]
+    fn level_for_target(&self, target: &str) -> LogLevel {
+        let opts = self.options.lock();
+        opts.target_levels.level_for(target).unwrap_or(opts.level)
+    }
+
     /// Add a reporter to the list of active reporters.
     pub fn add_reporter(&self, reporter: Box<dyn Reporter>) {
         self.options.lock().reporters.push(reporter);
@@ -108,6 +357,47 @@ impl Consola {
         self.options.lock().reporters = reporters;
     }
 
+    /// Route both sinks to a [`ThreadLocalSink`](crate::util::ThreadLocalSink),
+    /// so output stays out of `cargo test` and each test reads its own
+    /// through [`captured_output`](crate::util::captured_output).
+    ///
+    /// Call it on [`CONSOLA`](crate::CONSOLA) at the start of a test to
+    /// quiet the global logger; other instances are unaffected.
+    #[cfg(feature = "testing")]
+    pub fn capture_output(&self) {
+        let sink: std::sync::Arc<dyn crate::Sink> =
+            std::sync::Arc::new(crate::util::ThreadLocalSink);
+        let mut opts = self.options.lock();
+        opts.out_sink = sink.clone();
+        opts.err_sink = sink;
+    }
+
+    /// Call `hook` with every entry this instance emits, after the reporters
+    /// have written it.
+    ///
+    /// Hooks run in registration order and see each entry once, including
+    /// group events and throttle summaries, but not entries that were
+    /// filtered, suppressed, queued or dropped because their sink stopped
+    /// after repeated write failures. They are not inherited by
+    /// [`create`](Self::create)d instances.
+    pub fn on_record(&self, hook: impl Fn(&LogObject) + Send + Sync + 'static) -> HookHandle {
+        let mut state = self.state.lock();
+        let handle = HookHandle(state.next_hook_id);
+        state.next_hook_id += 1;
+        state.hooks.push((handle, std::sync::Arc::new(hook)));
+        handle
+    }
+
+    /// Unregister a hook added with [`on_record`](Self::on_record).
+    ///
+    /// Returns `false` if the hook was already removed.
+    pub fn remove_hook(&self, handle: HookHandle) -> bool {
+        let mut state = self.state.lock();
+        let len = state.hooks.len();
+        state.hooks.retain(|(h, _)| *h != handle);
+        state.hooks.len() != len
+    }
+
     /// Create a new `Consola` instance by merging the current options with the given overrides.
     pub fn create(&self, options_overrides: ConsolaOptions) -> Self {
         let current = self.options.lock().clone();
@@ -133,8 +423,22 @@ impl Consola {
         if options_overrides.defaults.additional.is_some() {
             merged_defaults.additional = options_overrides.defaults.additional;
         }
+        merge_fields(
+            &mut merged_defaults.fields,
+            &options_overrides.defaults.fields,
+        );
+        if options_overrides.defaults.trace_id.is_some() {
+            merged_defaults.trace_id = options_overrides.defaults.trace_id;
+        }
+        if options_overrides.defaults.prefix.is_some() {
+            merged_defaults.prefix = options_overrides.defaults.prefix;
+        }
+        merged_defaults
+            .extensions
+            .extend(&options_overrides.defaults.extensions);
 
         let merged = ConsolaOptions {
+            name: options_overrides.name.or(current.name),
             level: options_overrides.level,
             reporters: if options_overrides.reporters.is_empty() {
                 current.reporters
@@ -144,7 +448,32 @@ impl Consola {
             defaults: merged_defaults,
             throttle: options_overrides.throttle,
             throttle_min: options_overrides.throttle_min,
+            throttle_by_type: if options_overrides.throttle_by_type.is_empty() {
+                current.throttle_by_type
+            } else {
+                options_overrides.throttle_by_type
+            },
+            error_chain_window: options_overrides.error_chain_window,
+            cardinality_limit: options_overrides.cardinality_limit,
+            cardinality_window: options_overrides.cardinality_window,
+            rate_limit: options_overrides.rate_limit,
             format_options: options_overrides.format_options,
+            out_sink: options_overrides.out_sink,
+            err_sink: options_overrides.err_sink,
+            stderr_level: options_overrides.stderr_level,
+            stdout_only: options_overrides.stdout_only,
+            flush_level: options_overrides.flush_level,
+            write_failure_limit: options_overrides.write_failure_limit,
+            pause_queue_capacity: options_overrides.pause_queue_capacity,
+            pause_queue_overflow: options_overrides.pause_queue_overflow,
+            clock: options_overrides.clock,
+            target_levels: options_overrides.target_levels,
+            redactors: if options_overrides.redactors.is_empty() {
+                current.redactors
+            } else {
+                options_overrides.redactors
+            },
+            fingerprint_prefix: options_overrides.fingerprint_prefix,
         };
 
         Self::new(merged)
@@ -169,6 +498,14 @@ impl Consola {
         if let Some(additional) = defaults.additional {
             merged.additional = Some(additional);
         }
+        merge_fields(&mut merged.fields, &defaults.fields);
+        if let Some(trace_id) = defaults.trace_id {
+            merged.trace_id = Some(trace_id);
+        }
+        if let Some(prefix) = defaults.prefix {
+            merged.prefix = Some(prefix);
+        }
+        merged.extensions.extend(&defaults.extensions);
 
         let opts = ConsolaOptions {
             defaults: merged,
@@ -177,6 +514,15 @@ impl Consola {
         self.create(opts)
     }
 
+    /// Create a new `Consola` instance with the given logger name.
+    pub fn with_name(&self, name: &str) -> Self {
+        let current = self.options.lock().clone();
+        Self::new(ConsolaOptions {
+            name: Some(name.to_string()),
+            ..current
+        })
+    }
+
     /// Create a new `Consola` instance with the given tag added to the defaults.
     pub fn with_tag(&self, tag: &str) -> Self {
         self.with_defaults(LogObjectInput {
@@ -185,49 +531,391 @@ impl Consola {
         })
     }
 
+    /// Create a new `Consola` instance whose entries show `prefix` between
+    /// their type and message.
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        self.with_defaults(LogObjectInput {
+            prefix: Some(prefix.to_string()),
+            ..LogObjectInput::default()
+        })
+    }
+
+    /// Create a new `Consola` instance whose entries carry `id` as their trace id.
+    ///
+    /// Entries that set their own [`LogObjectInput::trace_id`] keep it.
+    pub fn with_trace_id(&self, id: &str) -> Self {
+        self.with_defaults(LogObjectInput {
+            trace_id: Some(id.to_string()),
+            ..LogObjectInput::default()
+        })
+    }
+
     /// Pause all logging. Logs are queued and will be flushed on [`resume_logs`].
     pub fn pause_logs(&self) {
-        self.state.lock().paused = true;
+        let mut state = self.state.lock();
+        state.paused = true;
+        state.paused_by = Some(std::thread::current().id());
     }
 
     /// Resume logging and flush any queued log messages.
+    ///
+    /// Queued entries are replayed in the order they were logged, keeping
+    /// their original timestamps. Throttling compares those original times,
+    /// so identical entries logged further apart than `throttle` are still
+    /// reported separately instead of collapsing into one repeat group.
     pub fn resume_logs(&self) {
+        let queue = {
+            let mut state = self.state.lock();
+            state.paused = false;
+            state.paused_by = None;
+            state.take_queue()
+        };
+        self.queue_space.notify_all();
+        self.replay(queue);
+    }
+
+    /// Replay calls queued while paused, in order.
+    fn replay(&self, queue: VecDeque<Queued>) {
+        for queued in queue {
+            match queued {
+                Queued::Log(defaults, args, mode, time) => {
+                    self._log_at(&defaults, &args, mode, time);
+                }
+                Queued::Group(event, label) => self._group(event, label),
+            }
+        }
+    }
+
+    /// Number of calls queued while paused and not yet replayed.
+    pub fn queued_len(&self) -> usize {
+        self.state.lock().queue.len()
+    }
+
+    /// Discard the log calls queued while paused without emitting them,
+    /// returning how many were discarded. Logging stays paused.
+    ///
+    /// Discarded log calls are counted as dropped in [`stats`](Self::stats).
+    /// Queued [`group`](Self::group) and [`group_end`](Self::group_end) calls
+    /// are not emitted either, but still update the nesting depth, as while
+    /// [`silence`](Self::silence)d.
+    pub fn discard_queued(&self) -> usize {
         let mut state = self.state.lock();
-        state.paused = false;
-        let queue = std::mem::take(&mut state.queue);
-        drop(state);
+        let queue = state.take_queue();
+        self.queue_space.notify_all();
+        let mut discarded = 0;
+        for queued in queue {
+            match queued {
+                Queued::Log(defaults, ..) => {
+                    let ty = defaults.r#type.unwrap_or(LogType::Log);
+                    state.stats.entry(ty).or_default().dropped += 1;
+                    discarded += 1;
+                }
+                Queued::Group(GroupEvent::Start, label) => state.group_labels.push(label),
+                Queued::Group(GroupEvent::End, _) => {
+                    state.group_labels.pop();
+                }
+            }
+        }
+        discarded
+    }
+
+    /// Number of calls discarded because the pause queue was full (see
+    /// [`ConsolaOptions::pause_queue_capacity`]).
+    pub fn queue_dropped_count(&self) -> u64 {
+        self.state.lock().queue_dropped
+    }
+
+    /// Number of entries throttling has folded into `(repeated N times)`
+    /// summaries since the logger was created.
+    ///
+    /// Unlike the per-type `suppressed` counts of [`stats`](Self::stats), it
+    /// excludes repeats of `_once` calls and is not zeroed by
+    /// [`reset_session`](Self::reset_session).
+    pub fn suppressed_count(&self) -> u64 {
+        self.state.lock().throttle_suppressed
+    }
+
+    /// Number of entries dropped by [`ConsolaOptions::rate_limit`] since the
+    /// logger was created. They also count as dropped in [`stats`](Self::stats).
+    pub fn rate_limited_count(&self) -> u64 {
+        self.state.lock().rate_limited
+    }
+
+    /// Queue `queued` while paused, applying `pause_queue_capacity`.
+    ///
+    /// Only log calls count towards the capacity: group events are always
+    /// queued, so replaying the queue keeps the nesting intact.
+    ///
+    /// Returns `None` when logging is no longer paused (it may have been
+    /// resumed while blocked on a full queue); the caller then handles the
+    /// call directly. Otherwise returns whether the call was kept: `false`
+    /// for a log call dropped because the queue was full or logging shut
+    /// down while it waited.
+    fn enqueue(&self, queued: Queued) -> Option<bool> {
+        let (capacity, overflow) = {
+            let opts = self.options.lock();
+            (opts.pause_queue_capacity, opts.pause_queue_overflow)
+        };
+        let is_log = matches!(queued, Queued::Log(..));
+        let mut state = self.state.lock();
+        // The thread that paused logging would wait for itself to resume it,
+        // so its calls are dropped like `DropNewest` instead.
+        let blocks = overflow == QueueOverflow::Block
+            && state.paused_by != Some(std::thread::current().id());
+        if capacity > 0 && is_log && blocks {
+            while state.paused && !state.closed && state.queued_logs >= capacity {
+                state = self.queue_space.wait(state);
+            }
+        }
+        if state.closed {
+            if let Queued::Log(defaults, ..) = &queued {
+                let ty = defaults.r#type.unwrap_or(LogType::Log);
+                state.stats.entry(ty).or_default().dropped += 1;
+            }
+            return Some(!is_log);
+        }
+        if !state.paused {
+            return None;
+        }
+        let (dropped, kept) = if capacity > 0 && is_log && state.queued_logs >= capacity {
+            if overflow == QueueOverflow::DropOldest {
+                let oldest = state
+                    .queue
+                    .iter()
+                    .position(|q| matches!(q, Queued::Log(..)))
+                    .expect("queue holds a log call");
+                state.queue.push_back(queued);
+                (state.queue.remove(oldest), true)
+            } else {
+                (Some(queued), false)
+            }
+        } else {
+            state.queued_logs += usize::from(is_log);
+            state.queue.push_back(queued);
+            (None, true)
+        };
+        if let Some(Queued::Log(defaults, ..)) = dropped {
+            state.queue_dropped += 1;
+            let ty = defaults.r#type.unwrap_or(LogType::Log);
+            state.stats.entry(ty).or_default().dropped += 1;
+        }
+        Some(kept)
+    }
+
+    /// Discard every log until [`unsilence`](Self::unsilence), without
+    /// changing the level.
+    ///
+    /// Unlike [`pause_logs`](Self::pause_logs), nothing is queued: entries
+    /// logged while silenced are counted as filtered and never emitted. A
+    /// pending throttle summary is written first so it is not lost. Groups
+    /// opened or closed meanwhile still update the nesting depth.
+    pub fn silence(&self) {
+        self.flush();
+        self.state.lock().silenced = true;
+    }
+
+    /// Resume emitting logs after [`silence`](Self::silence).
+    pub fn unsilence(&self) {
+        self.state.lock().silenced = false;
+    }
+
+    /// Returns `true` between [`silence`](Self::silence) and
+    /// [`unsilence`](Self::unsilence).
+    pub fn is_silenced(&self) -> bool {
+        self.state.lock().silenced
+    }
+
+    /// Open a group: `label` is logged as a normal line and every following
+    /// log is nested one level deeper until the matching [`group_end`](Self::group_end).
+    ///
+    /// Text reporters indent nested output; [`JsonReporter`](crate::reporters::JsonReporter)
+    /// emits `group_start` / `group_end` events instead.
+    pub fn group(&self, label: impl Into<String>) {
+        self._group(GroupEvent::Start, label.into());
+    }
+
+    /// Close the innermost open group. Does nothing when no group is open.
+    pub fn group_end(&self) {
+        self._group(GroupEvent::End, String::new());
+    }
+
+    /// Returns the number of currently open groups.
+    pub fn group_depth(&self) -> usize {
+        self.state.lock().group_labels.len()
+    }
+
+    fn _group(&self, event: GroupEvent, label: String) {
+        let (label, depth) = {
+            let mut state = self.state.lock();
+            if state.closed {
+                return;
+            }
+            if state.paused {
+                drop(state);
+                if self.enqueue(Queued::Group(event, label.clone())).is_some() {
+                    return;
+                }
+                state = self.state.lock();
+            }
+            match event {
+                GroupEvent::Start => {
+                    let depth = state.group_labels.len();
+                    state.group_labels.push(label.clone());
+                    (label, depth)
+                }
+                GroupEvent::End => match state.group_labels.pop() {
+                    Some(label) => (label, state.group_labels.len()),
+                    None => return,
+                },
+            }
+        };
+        if self.is_silenced() {
+            return;
+        }
 
-        for (defaults, args, is_raw) in queue {
-            self._log_fn(&defaults, &args, is_raw);
+        let (level, tag, timestamp_ms) = {
+            let opts = self.options.lock();
+            (
+                opts.level,
+                opts.defaults.tag.clone().unwrap_or_default(),
+                opts.clock.now_ms(),
+            )
+        };
+        if !passes_level(LogType::Log.level(), level) {
+            return;
+        }
+
+        // Keep a pending throttle summary ahead of the group boundary.
+        self.flush();
+        let mut log_obj = LogObject::new(LogType::Log);
+        log_obj.timestamp_ms = timestamp_ms;
+        log_obj.tag = tag;
+        log_obj.args = vec![label];
+        log_obj.group_depth = depth;
+        log_obj.group_event = Some(event);
+        self.emit_entry(log_obj);
+    }
+
+    /// Apply the configured redactors to `log_obj`, in order.
+    fn redact(&self, log_obj: &mut LogObject) {
+        let redactors = self.options.lock().redactors.clone();
+        for redactor in &redactors {
+            redactor.redact(log_obj);
+        }
+    }
+
+    /// Whether an entry of type `ty` may be emitted: after shutdown it is
+    /// counted as dropped, while silenced as filtered.
+    fn admit(state: &mut ConsolaState, ty: LogType) -> bool {
+        if state.closed {
+            state.stats.entry(ty).or_default().dropped += 1;
+            return false;
+        }
+        if state.silenced {
+            state.stats.entry(ty).or_default().filtered += 1;
+            return false;
         }
+        true
+    }
+
+    /// Redact and emit an entry built outside [`_log_at`](Self::_log_at),
+    /// such as a group boundary or a record from the `log` and `tracing`
+    /// bridges.
+    ///
+    /// Like direct log calls, the entry is discarded after
+    /// [`shutdown`](Self::shutdown) or while [silenced](Self::silence).
+    fn emit_entry(&self, mut log_obj: LogObject) {
+        if !Self::admit(&mut self.state.lock(), log_obj.r#type) {
+            return;
+        }
+        self.redact(&mut log_obj);
+        self._emit(&log_obj);
     }
 
     fn _log_fn(&self, input_defaults: &LogObjectInput, args: &[String], is_raw: bool) -> bool {
+        let mode = LogMode {
+            raw: is_raw,
+            ..LogMode::default()
+        };
+        self._log_mode(input_defaults, args, mode)
+    }
+
+    fn _log_mode(&self, input_defaults: &LogObjectInput, args: &[String], mode: LogMode) -> bool {
+        let time = LogTime::now(self.options.lock().clock.as_ref());
+        self._log_at(input_defaults, args, mode, time)
+    }
+
+    fn _log_at(
+        &self,
+        input_defaults: &LogObjectInput,
+        args: &[String],
+        mode: LogMode,
+        time: LogTime,
+    ) -> bool {
         // Read config once
-        let (level, throttle, throttle_min) = {
+        let (
+            level,
+            throttle,
+            error_chain_window,
+            cardinality,
+            mut fields,
+            trace_id,
+            prefix,
+            mut extensions,
+            fingerprint_prefix,
+            rate_limit,
+        ) = {
             let opts = self.options.lock();
-            (opts.level, opts.throttle, opts.throttle_min)
+            let ty = input_defaults.r#type.unwrap_or(LogType::Log);
+            let throttle =
+                opts.throttle_by_type
+                    .get(ty.as_str())
+                    .copied()
+                    .unwrap_or(ThrottleConfig {
+                        window_ms: opts.throttle,
+                        min_count: opts.throttle_min,
+                    });
+            (
+                opts.level,
+                throttle,
+                opts.error_chain_window,
+                (opts.cardinality_limit, opts.cardinality_window),
+                opts.defaults.fields.clone(),
+                opts.defaults.trace_id.clone(),
+                opts.defaults.prefix.clone(),
+                opts.defaults.extensions.clone(),
+                opts.fingerprint_prefix,
+                opts.rate_limit,
+            )
         };
 
         let msg_level = input_defaults.level.unwrap_or(log_levels::INFO);
-        if msg_level > level {
+        if !passes_level(msg_level, level) {
+            let ty = input_defaults.r#type.unwrap_or(LogType::Log);
+            self.state.lock().stats.entry(ty).or_default().filtered += 1;
             return false;
         }
 
-        // Check paused state
+        // Check closed / paused state
         {
             let mut state = self.state.lock();
+            if !Self::admit(&mut state, input_defaults.r#type.unwrap_or(LogType::Log)) {
+                return false;
+            }
             if state.paused {
-                state
-                    .queue
-                    .push((input_defaults.clone(), args.to_vec(), is_raw));
-                return true;
+                drop(state);
+                let queued =
+                    Queued::Log(Box::new(input_defaults.clone()), args.to_vec(), mode, time);
+                if let Some(kept) = self.enqueue(queued) {
+                    return kept;
+                }
             }
         }
 
         // Build LogObject
         let log_type = input_defaults.r#type.unwrap_or(LogType::Log);
         let mut log_obj = LogObject::new(log_type);
+        log_obj.timestamp_ms = time.timestamp_ms;
         log_obj.level = normalize_log_level(input_defaults.level, log_type.level());
         log_obj.tag = input_defaults.tag.clone().unwrap_or_default();
         log_obj.message = input_defaults.message.clone();
@@ -236,6 +924,13 @@ impl Consola {
         log_obj.icon = input_defaults.icon.clone();
         log_obj.style = input_defaults.style.clone();
         log_obj.error = input_defaults.error.clone();
+        merge_fields(&mut fields, &input_defaults.fields);
+        log_obj.fields = fields;
+        log_obj.trace_id = input_defaults.trace_id.clone().or(trace_id);
+        log_obj.prefix = input_defaults.prefix.clone().or(prefix);
+        extensions.extend(&input_defaults.extensions);
+        log_obj.extensions = extensions;
+        log_obj.group_depth = self.state.lock().group_labels.len();
 
         // Auto-capture backtrace for error-level logs when backtrace feature is enabled
         // and no explicit error info was provided (e.g. via log crate integration).
@@ -265,15 +960,37 @@ impl Consola {
             log_obj.args.push("\n".to_string() + &lines.join("\n"));
         }
 
-        // Throttle / Dedup
-        let serialized = format!("{:?}:{}:{:?}", log_obj.r#type, log_obj.tag, log_obj.args);
+        // Redact before fingerprinting so masked-equal messages coalesce
+        self.redact(&mut log_obj);
+
+        // Throttle / Dedup. Without throttling, `_once` or the cardinality
+        // guard nothing compares fingerprints, so skip hashing the entry.
+        let mut fingerprint = Vec::new();
+        if !throttle.is_disabled() || mode.once || cardinality.0 > 0 {
+            fingerprint = log_obj.fingerprint(mode.raw);
+            if fingerprint_prefix && let Some(prefix) = &log_obj.prefix {
+                fingerprint.push(0x0a);
+                fingerprint.extend_from_slice(&(prefix.len() as u32).to_be_bytes());
+                fingerprint.extend_from_slice(prefix.as_bytes());
+            }
+        }
+
+        if mode.once {
+            let mut state = self.state.lock();
+            if !state.once_seen.insert(fingerprint.clone()) {
+                state.stats.entry(log_obj.r#type).or_default().suppressed += 1;
+                return false;
+            }
+        }
 
-        let is_repeat = {
+        let is_repeat = if throttle.is_disabled() {
+            None
+        } else {
             let state = self.state.lock();
             state.last_log.as_ref().and_then(|last| {
-                last.time.and_then(|t| {
-                    if (t.elapsed().as_millis() as u64) < throttle && last.serialized == serialized
-                    {
+                last.time.zip(time.instant).and_then(|(t, now)| {
+                    let elapsed = now.saturating_duration_since(t).as_millis() as u64;
+                    if elapsed < throttle.window_ms && last.fingerprint == fingerprint {
                         Some(last.count)
                     } else {
                         None
@@ -286,48 +1003,43 @@ impl Consola {
             let mut state = self.state.lock();
             if let Some(last) = &mut state.last_log {
                 last.count = count.saturating_add(1);
-                last.serialized = serialized.clone();
-                if last.count > throttle_min {
+                last.fingerprint = fingerprint.clone();
+                if last.count > throttle.min_count {
+                    let ty = log_obj.r#type;
                     last.object = log_obj;
+                    state.stats.entry(ty).or_default().suppressed += 1;
+                    state.throttle_suppressed += 1;
                     return true;
                 }
             }
         }
 
         // Emit repeated count from previous log
+        self.emit_repeated();
+
+        if cardinality.0 > 0 && !self.admit_distinct(&log_obj, &fingerprint, cardinality, time) {
+            return false;
+        }
+
+        if let Some(limit) = rate_limit
+            && !self.admit_rate(&log_obj, limit, time)
         {
-            let mut state = self.state.lock();
-            if let Some(last) = state.last_log.clone() {
-                let repeated = (last.count as i64)
-                    .saturating_sub(throttle_min as i64)
-                    .max(0) as u32;
-                if repeated > 0 {
-                    let mut repeat_args = last.object.args.clone();
-                    if repeated > 1 {
-                        repeat_args.push(format!("(repeated {} times)", repeated));
-                    }
-                    let mut repeat_obj = last.object;
-                    repeat_obj.args = repeat_args;
-                    if let Some(l) = &mut state.last_log {
-                        l.count = 1;
-                    }
-                    drop(state);
-                    self._emit(&repeat_obj);
-                }
-            }
+            return false;
+        }
+
+        if error_chain_window > 0 {
+            self.abbreviate_repeated_error(&mut log_obj, error_chain_window, time.instant);
         }
 
         // Save as last log
         {
             let mut state = self.state.lock();
             state.last_log = Some(LastLogInfo {
-                serialized,
+                fingerprint,
                 object: log_obj.clone(),
                 count: 1,
-                #[cfg(not(target_arch = "wasm32"))]
-                time: Some(Instant::now()),
-                #[cfg(target_arch = "wasm32")]
-                time: None,
+                time: time.instant,
+                throttle,
             });
         }
 
@@ -336,52 +1048,434 @@ impl Consola {
         true
     }
 
+    /// Take a token for `log_obj` from the `rate_limit` bucket, returning
+    /// `false` and counting the entry as dropped when the bucket is empty.
+    fn admit_rate(&self, log_obj: &LogObject, limit: RateLimit, time: LogTime) -> bool {
+        // Without a monotonic clock the bucket never refills, so the cap is off.
+        let Some(now) = time.instant else {
+            return true;
+        };
+        let capacity = u64::from(limit.burst.max(1)) * 1000;
+        let mut state = self.state.lock();
+        let (tokens, refilled) = state.rate_bucket.unwrap_or((capacity, now));
+        // Refill in whole milliseconds, carrying the remainder to the next call.
+        let elapsed = now.saturating_duration_since(refilled).as_millis() as u64;
+        let refilled = refilled + std::time::Duration::from_millis(elapsed);
+        let tokens = tokens
+            .saturating_add(elapsed.saturating_mul(u64::from(limit.per_second)))
+            .min(capacity);
+        if tokens < 1000 {
+            state.rate_bucket = Some((tokens, refilled));
+            state.rate_limited += 1;
+            state.stats.entry(log_obj.r#type).or_default().dropped += 1;
+            return false;
+        }
+        state.rate_bucket = Some((tokens - 1000, refilled));
+        true
+    }
+
+    /// Apply `cardinality_limit` to `log_obj`, returning `false` when it is
+    /// suppressed. Emits the guard's warning and end-of-window summary.
+    fn admit_distinct(
+        &self,
+        log_obj: &LogObject,
+        fingerprint: &[u8],
+        (limit, window): (u32, u64),
+        time: LogTime,
+    ) -> bool {
+        // Without a monotonic clock windows never end, so the guard is off.
+        let Some(now) = time.instant else {
+            return true;
+        };
+        let mut notices = Vec::new();
+        let mut state = self.state.lock();
+        let guard = &mut state.cardinality;
+        if guard
+            .window_start
+            .is_none_or(|start| now.saturating_duration_since(start).as_millis() as u64 >= window)
+        {
+            if guard.suppressed > 0 {
+                notices.push(format!(
+                    "consola: suppressed {} distinct messages over the cardinality limit",
+                    guard.suppressed
+                ));
+            }
+            guard.window_start = Some(now);
+            guard.seen.clear();
+            guard.suppressed = 0;
+        }
+        let admitted = guard.seen.contains(fingerprint) || guard.seen.len() < limit as usize;
+        if admitted {
+            guard.seen.insert(fingerprint.to_vec());
+        } else {
+            if guard.suppressed == 0 {
+                notices.push(format!(
+                    "consola: more than {} distinct messages within {}ms, suppressing new ones",
+                    limit, window
+                ));
+            }
+            guard.suppressed += 1;
+            state.stats.entry(log_obj.r#type).or_default().suppressed += 1;
+        }
+        drop(state);
+
+        for notice in notices {
+            let mut notice_obj = LogObject::new(LogType::Warn);
+            notice_obj.timestamp_ms = time.timestamp_ms;
+            notice_obj.args = vec![notice];
+            self._emit(&notice_obj);
+        }
+        admitted
+    }
+
+    /// Abbreviate `log_obj`'s error if it repeats the previously emitted
+    /// error within `window` ms, and remember it for the next entry.
+    fn abbreviate_repeated_error(
+        &self,
+        log_obj: &mut LogObject,
+        window: u64,
+        now: Option<Instant>,
+    ) {
+        let Some(err) = log_obj.error.take() else {
+            return;
+        };
+        let mut state = self.state.lock();
+        let repeated = state.last_error.as_ref().is_some_and(|(last, t)| {
+            *last == err
+                && t.zip(now).is_some_and(|(t, now)| {
+                    (now.saturating_duration_since(t).as_millis() as u64) < window
+                })
+        });
+        log_obj.error = Some(if repeated {
+            err.abbreviated()
+        } else {
+            err.clone()
+        });
+        state.last_error = Some((err, now));
+    }
+
+    /// Emit the "(repeated N times)" summary for throttled duplicates of the
+    /// last log, if any are pending, and reset the repeat counter.
+    fn emit_repeated(&self) {
+        let mut state = self.state.lock();
+        if let Some(last) = state.last_log.clone() {
+            let repeated = last.pending_repeats();
+            if repeated > 0 {
+                let mut repeat_args = last.object.args.clone();
+                if repeated > 1 {
+                    repeat_args.push(format!("(repeated {} times)", repeated));
+                }
+                let mut repeat_obj = last.object;
+                repeat_obj.args = repeat_args;
+                repeat_obj.repeated = repeated;
+                if let Some(l) = &mut state.last_log {
+                    l.count = 1;
+                }
+                drop(state);
+                self._emit(&repeat_obj);
+            }
+        }
+    }
+
+    /// Emit any pending throttle summary for suppressed duplicate logs.
+    ///
+    /// Calling this repeatedly is harmless: once the summary is written there
+    /// is nothing left to flush.
+    pub fn flush(&self) {
+        self.emit_repeated();
+    }
+
+    /// Emit the pending throttle summary if its `throttle` window has ended,
+    /// returning whether one was written.
+    ///
+    /// A summary is normally written when the next entry arrives, so after a
+    /// burst of duplicates followed by silence it waits indefinitely. Call
+    /// `poll` periodically (e.g. from an existing timer or event loop tick)
+    /// to write it once the window has passed instead. Unlike
+    /// [`flush`](Self::flush), a window still in progress is left alone.
+    pub fn poll(&self) -> bool {
+        let clock = self.options.lock().clock.clone();
+        let expired = {
+            let state = self.state.lock();
+            state.last_log.as_ref().is_some_and(|last| {
+                last.pending_repeats() > 0
+                    && last.time.zip(clock.now()).is_some_and(|(t, now)| {
+                        now.saturating_duration_since(t).as_millis() as u64
+                            >= last.throttle.window_ms
+                    })
+            })
+        };
+        if expired {
+            self.emit_repeated();
+        }
+        expired
+    }
+
+    /// Flush pending output and stop accepting logs.
+    ///
+    /// Calls queued while paused are replayed, a pending throttle summary is
+    /// written, and every reporter (through [`Reporter::flush`]) and then
+    /// both sinks are flushed, so every entry has reached its sink when
+    /// `shutdown` returns; an
+    /// [`AsyncSink`](crate::AsyncSink) waits for its worker to write its
+    /// backlog.
+    ///
+    /// Later log calls are counted as dropped and return without emitting.
+    /// Only the first call does any work, so `shutdown` followed by the
+    /// implicit shutdown in [`Drop`] never emits twice. A call from another
+    /// thread while the first is still running waits for it to finish; a
+    /// nested call from the shutting-down thread (e.g. from a reporter)
+    /// returns at once.
+    ///
+    /// Dropping the instance is best-effort: an instance in a static or a
+    /// leaked `Arc` is never dropped, and exiting the process skips
+    /// destructors. Call `shutdown` where output must be complete, e.g.
+    /// before a test inspects its sink.
+    pub fn shutdown(&self) {
+        let current = std::thread::current().id();
+        let queue = {
+            let mut state = self.state.lock();
+            while !state.shut_down {
+                match state.closing {
+                    None => break,
+                    Some(id) if id == current => return,
+                    Some(_) => state = self.shutdown_done.wait(state),
+                }
+            }
+            if state.shut_down {
+                return;
+            }
+            state.closing = Some(current);
+            state.paused = false;
+            state.paused_by = None;
+            state.take_queue()
+        };
+        self.queue_space.notify_all();
+        self.replay(queue);
+        self.state.lock().closed = true;
+        self.flush();
+        self.flush_outputs();
+        self.state.lock().shut_down = true;
+        self.shutdown_done.notify_all();
+    }
+
+    /// Flush every reporter (through [`Reporter::flush`]) and then both
+    /// sinks, ignoring errors.
+    pub(crate) fn flush_outputs(&self) {
+        let (out_sink, err_sink, reporters) = {
+            let opts = self.options.lock();
+            (
+                opts.out_sink.clone(),
+                opts.err_sink.clone(),
+                opts.reporters.clone(),
+            )
+        };
+        for reporter in &reporters {
+            let _ = reporter.flush();
+        }
+        let _ = out_sink.flush();
+        let _ = err_sink.flush();
+    }
+
+    /// Returns `true` once [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shut_down(&self) -> bool {
+        self.state.lock().closed
+    }
+
+    /// Write an already redacted entry through every reporter.
     fn _emit(&self, log_obj: &LogObject) {
-        let opts = self.options.lock();
+        // Format and write from a snapshot so concurrent callers do not
+        // serialize on the options lock while reporters and sinks run.
         let ctx = LogContext {
-            options: std::sync::Arc::new(opts.clone()),
+            options: std::sync::Arc::new(self.options.lock().clone()),
+        };
+        let opts = &ctx.options;
+        let stream = Self::stream_for(opts, log_obj.level);
+        let (out_latched, err_latched) = {
+            let state = self.state.lock();
+            (state.out_health.latched, state.err_health.latched)
+        };
+        let latched = match stream {
+            Stream::Out => out_latched,
+            Stream::Err => err_latched,
         };
 
-        for reporter in &opts.reporters {
+        // An entry for a latched sink is dropped without being formatted.
+        let mut failed = latched;
+        let mut write_result = None;
+        for reporter in opts.reporters.iter().filter(|_| !latched) {
             match reporter.format(log_obj, &ctx) {
                 Ok(formatted) => {
                     if !formatted.is_empty() {
-                        let _ = Self::write_line(&formatted, log_obj.level);
+                        let result = Self::write_line(opts, stream, &formatted, log_obj.level);
+                        if write_result.as_ref().is_none_or(Result::is_ok) {
+                            write_result = Some(result);
+                        }
                     }
                 }
+                Err(crate::error::ConsolaError::SinkLatched) => failed = true,
                 Err(e) => {
-                    use std::io::Write;
-                    let _ = writeln!(std::io::stderr(), "[consola] reporter error: {}", e);
+                    failed = true;
+                    if !err_latched {
+                        let _ = opts
+                            .err_sink
+                            .write_line(&format!("[consola] reporter error: {}", e));
+                    }
                 }
             }
         }
+
+        // Entries dropped by a latched sink were never emitted.
+        let hooks: Vec<RecordHook> = if latched {
+            Vec::new()
+        } else {
+            let state = self.state.lock();
+            state.hooks.iter().map(|(_, hook)| hook.clone()).collect()
+        };
+        for hook in &hooks {
+            hook(log_obj);
+        }
+
+        let mut state = self.state.lock();
+        let stats = state.stats.entry(log_obj.r#type).or_default();
+        if failed {
+            stats.dropped += 1;
+        } else {
+            stats.emitted += 1;
+        }
+        let Some(result) = write_result else {
+            return;
+        };
+        if opts.write_failure_limit == 0 {
+            return;
+        }
+        let health = match stream {
+            Stream::Out => &mut state.out_health,
+            Stream::Err => &mut state.err_health,
+        };
+        let err = match result {
+            Ok(()) => {
+                health.failures = 0;
+                return;
+            }
+            Err(err) => err,
+        };
+        health.failures += 1;
+        if health.latched || health.failures < opts.write_failure_limit {
+            return;
+        }
+        health.latched = true;
+        let (name, other, other_latched) = match stream {
+            Stream::Out => ("output", &opts.err_sink, state.err_health.latched),
+            Stream::Err => ("error", &opts.out_sink, state.out_health.latched),
+        };
+        drop(state);
+        if !other_latched {
+            let _ = other.write_line(&format!(
+                "[consola] stopped writing to the {} sink after {} failed writes: {}",
+                name, opts.write_failure_limit, err
+            ));
+        }
+    }
+
+    /// Returns the per-type counters collected by this instance, in [`LOG_TYPES`] order
+    /// followed by custom types sorted by name.
+    ///
+    /// Types that have not been logged (or filtered) yet are omitted.
+    pub fn stats(&self) -> Vec<(LogType, LogStats)> {
+        let state = self.state.lock();
+        let mut stats: Vec<(LogType, LogStats)> = LOG_TYPES
+            .iter()
+            .filter_map(|ty| state.stats.get(ty).map(|s| (*ty, *s)))
+            .collect();
+        let mut custom: Vec<(LogType, LogStats)> = state
+            .stats
+            .iter()
+            .filter(|(ty, _)| matches!(ty, LogType::Custom(_)))
+            .map(|(ty, s)| (*ty, *s))
+            .collect();
+        custom.sort_by_key(|(ty, _)| ty.as_str());
+        stats.extend(custom);
+        stats
     }
 
-    /// Write a line to stdout or stderr based on log level.
-    /// Errors are silently ignored (e.g. in WASM environments where stdout may not exist).
-    fn write_line(message: &str, level: LogLevel) -> std::io::Result<()> {
-        use std::io::Write;
-        if level < 2 {
-            let mut stderr = std::io::stderr().lock();
-            writeln!(stderr, "{message}")
+    /// Render [`stats`](Self::stats) as an aligned, human-readable table.
+    pub fn stats_table(&self) -> String {
+        let mut table =
+            TableBuilder::new().headers(["type", "emitted", "suppressed", "filtered", "dropped"]);
+        for (ty, s) in self.stats() {
+            table = table.row([
+                ty.as_str().to_string(),
+                s.emitted.to_string(),
+                s.suppressed.to_string(),
+                s.filtered.to_string(),
+                s.dropped.to_string(),
+            ]);
+        }
+        (1..=4).fold(table, |t, col| t.align(col, "right")).build()
+    }
+
+    /// The sink an entry at `level` is written to, based on `stderr_level`
+    /// and `stdout_only`.
+    fn stream_for(opts: &ConsolaOptions, level: LogLevel) -> Stream {
+        if opts.writes_to_err_sink(level) {
+            Stream::Err
         } else {
-            let mut stdout = std::io::stdout().lock();
-            writeln!(stdout, "{message}")
+            Stream::Out
         }
     }
+
+    /// Zero the per-type [`stats`](Self::stats) and the cardinality guard's
+    /// window, e.g. to collect metrics per phase of a long-running process.
+    ///
+    /// With `reset_throttle`, any pending throttle summary is emitted first
+    /// and the throttle and `error_chain_window` history is then forgotten,
+    /// so the next entry is never treated as a repeat. The `_once` cache is
+    /// kept; see [`clear_once_cache`](Self::clear_once_cache).
+    pub fn reset_session(&self, reset_throttle: bool) {
+        if reset_throttle {
+            self.flush();
+        }
+        let mut state = self.state.lock();
+        state.stats.clear();
+        state.cardinality = CardinalityGuard::default();
+        if reset_throttle {
+            state.last_log = None;
+            state.last_error = None;
+        }
+    }
+
+    /// Write a line to `stream`'s sink, flushing it when `level` is at or
+    /// above `flush_level` in severity.
+    fn write_line(
+        opts: &ConsolaOptions,
+        stream: Stream,
+        message: &str,
+        level: LogLevel,
+    ) -> std::io::Result<()> {
+        let sink = match stream {
+            Stream::Err => &opts.err_sink,
+            Stream::Out => &opts.out_sink,
+        };
+        sink.write_line(message)?;
+        if passes_level(level, opts.flush_level) {
+            sink.flush()?;
+        }
+        Ok(())
+    }
 }
 
 macro_rules! consola_methods {
     ($($method:ident, $raw_method:ident, $Type:ident;)*) => {
         impl Consola {
             $(
-                #[doc = concat!("Log a message at `", stringify!($Type), "` level.\n\nReturns `true` if the message was logged, `false` if filtered by log level.")]
+                #[doc = concat!("Log a message at `", stringify!($Type), "` level.\n\nReturns `true` if the entry was accepted, `false` if it was filtered or dropped; see [`Consola`](Consola#return-values).")]
                 pub fn $method(&self, msg: &str) -> bool {
                     let defaults = log_type_defaults(LogType::$Type);
                     self._log_fn(&defaults, &[msg.to_string()], false)
                 }
 
-                #[doc = concat!("Log a message at `", stringify!($Type), "` level (raw variant).\n\nReturns `true` if the message was logged, `false` if filtered by log level.")]
+                #[doc = concat!("Log a message at `", stringify!($Type), "` level (raw variant).\n\nReturns `true` if the entry was accepted, `false` if it was filtered or dropped; see [`Consola`](Consola#return-values).")]
                 pub fn $raw_method(&self, msg: &str) -> bool {
                     let defaults = log_type_defaults(LogType::$Type);
                     self._log_fn(&defaults, &[msg.to_string()], true)
@@ -434,10 +1528,97 @@ impl Consola {
             icon: input.icon.clone(),
             style: input.style.clone(),
             error: input.error.clone(),
+            fields: input.fields.clone(),
+            trace_id: input.trace_id.clone(),
+            prefix: input.prefix.clone(),
+            extensions: input.extensions.clone(),
         };
         self._log_fn(&defaults, &input.args, false)
     }
 
+    /// Log `message` of type `ty` with a tag and structured fields.
+    ///
+    /// Fields are merged over the instance's default fields; when a key
+    /// appears more than once, the last value wins.
+    ///
+    /// Returns `true` if the entry was accepted, `false` if it was filtered
+    /// or dropped; see [`Consola`](Consola#return-values).
+    pub fn log_with_fields(
+        &self,
+        ty: LogType,
+        tag: &str,
+        message: &str,
+        fields: &[(&str, &dyn std::fmt::Display)],
+    ) -> bool {
+        let input = fields.iter().fold(
+            LogObjectInput::new().type_(ty).tag(tag).arg(message),
+            |input, (key, value)| input.field(*key, value),
+        );
+        self.log_obj(&input)
+    }
+
+    /// Log `lines` as a box titled `title` (no title when empty), at `log`
+    /// level.
+    ///
+    /// The fancy reporter draws a bordered box; other text reporters list
+    /// the lines under the title.
+    ///
+    /// Returns `true` if the entry was accepted, `false` if it was filtered
+    /// or dropped; see [`Consola`](Consola#return-values).
+    pub fn box_message(&self, title: &str, lines: &[String]) -> bool {
+        let mut input = LogObjectInput::new()
+            .type_(LogType::Box)
+            .arg(lines.join("\n"));
+        if !title.is_empty() {
+            input = input.title(title);
+        }
+        input.level = Some(log_levels::LOG);
+        self.log_obj(&input)
+    }
+
+    /// Render `items` with [`format_tree`] and log the tree at `log` level.
+    ///
+    /// Returns `true` if the entry was accepted, `false` if it was filtered
+    /// or dropped; see [`Consola`](Consola#return-values).
+    pub fn tree(&self, items: &[TreeItem], options: &TreeOptions) -> bool {
+        self.log(format_tree(items, options).trim_end_matches('\n'))
+    }
+
+    /// Log `msg` of type `ty` only the first time this entry is seen.
+    ///
+    /// Unlike throttling, which only coalesces repeats within a time window,
+    /// later calls producing the same entry (see [`LogObject::fingerprint`])
+    /// are dropped for the lifetime of the instance, or until
+    /// [`clear_once_cache`](Self::clear_once_cache). Useful for deprecation
+    /// warnings and similar one-off notices.
+    ///
+    /// Returns `true` if the entry was accepted, `false` if it was filtered,
+    /// dropped or already logged once; see [`Consola`](Consola#return-values).
+    pub fn log_once(&self, ty: LogType, msg: &str) -> bool {
+        let defaults = log_type_defaults(ty);
+        let mode = LogMode {
+            once: true,
+            ..LogMode::default()
+        };
+        self._log_mode(&defaults, &[msg.to_string()], mode)
+    }
+
+    /// Log `msg` at `info` level only once; see [`log_once`](Self::log_once).
+    pub fn info_once(&self, msg: &str) -> bool {
+        self.log_once(LogType::Info, msg)
+    }
+
+    /// Log `msg` at `warn` level only once; see [`log_once`](Self::log_once).
+    pub fn warn_once(&self, msg: &str) -> bool {
+        self.log_once(LogType::Warn, msg)
+    }
+
+    /// Forget which entries were logged through the `_once` methods, so each
+    /// is logged again on its next call.
+    pub fn clear_once_cache(&self) {
+        self.state.lock().once_seen.clear();
+    }
+
     /// Log with a structured `LogObjectInput` (raw alias).
     pub fn log_obj_raw(&self, input: &LogObjectInput) -> bool {
         self.log_obj(input)
@@ -3,10 +3,10 @@
 // This module is conditionally compiled when feature = "tracing".
 // See `super::tracing_impl` declaration in mod.rs.
 
-use crate::constants::LogType;
+use crate::constants::{LogType, passes_level};
 #[cfg(feature = "backtrace")]
 use crate::types::ErrorInfo;
-use crate::types::LogObject;
+use crate::types::{LogObject, set_field};
 
 use super::Consola;
 
@@ -65,13 +65,13 @@ impl tracing::Subscriber for Consola {
             tracing::Level::DEBUG => 4,
             tracing::Level::TRACE => 5,
         };
-        level <= self.level()
+        passes_level(level, self.level())
     }
 
     fn max_level_hint(&self) -> Option<tracing::metadata::LevelFilter> {
         let raw = self.level();
         // Negative levels (e.g. SILENT = i32::MIN) mean no events pass;
-        // see `enabled()` which compares `level <= raw`.
+        // see `enabled()` which uses `passes_level`.
         if raw < 0 {
             return Some(tracing::metadata::LevelFilter::OFF);
         }
@@ -132,7 +132,7 @@ impl tracing::Subscriber for Consola {
             tracing::Level::DEBUG => 4,
             tracing::Level::TRACE => 5,
         };
-        if raw_level > self.level() {
+        if !passes_level(raw_level, self.level()) {
             return;
         }
 
@@ -145,27 +145,35 @@ impl tracing::Subscriber for Consola {
         let message = visitor.message.unwrap_or_default();
         let base_tag = event.metadata().target().to_string();
 
-        // Collect current span context (name + recorded fields) without
-        // holding the lock across the remaining work.
-        let (tag, span_field_args) = {
+        // Collect the active span context (names + recorded fields, outermost
+        // first) without holding the lock across the remaining work.
+        let (tag, span_fields) = {
             let state = self.state.lock();
-            let top = state.span_stack.last().copied();
-            if let Some(top) = top {
-                let span_name = state.span_metas.get(&top).map(|m| m.name().to_string());
-                let span_fields = state.span_fields.get(&top).cloned().unwrap_or_default();
-                let tag = match span_name {
-                    Some(name) => format!("{}::{}", name, base_tag),
-                    None => base_tag,
-                };
-                let args: Vec<String> = span_fields
-                    .into_iter()
-                    .filter(|(k, _)| k != "message")
-                    .map(|(k, v)| format!("{}={}", k, v))
-                    .collect();
-                (tag, args)
-            } else {
-                (base_tag, Vec::new())
+            let mut names = Vec::new();
+            let mut span_fields = Vec::new();
+            let mut seen = Vec::with_capacity(state.span_stack.len());
+            for id in &state.span_stack {
+                // A span entered more than once only contributes its context once.
+                if seen.contains(id) {
+                    continue;
+                }
+                seen.push(*id);
+                if let Some(meta) = state.span_metas.get(id) {
+                    names.push(meta.name());
+                }
+                // Inner spans override same-named fields of outer spans.
+                if let Some(fields) = state.span_fields.get(id) {
+                    for (k, v) in fields.iter().filter(|(k, _)| k != "message") {
+                        set_field(&mut span_fields, k.clone(), v.clone());
+                    }
+                }
             }
+            let tag = if names.is_empty() {
+                base_tag
+            } else {
+                format!("{}::{}", names.join(":"), base_tag)
+            };
+            (tag, span_fields)
         };
 
         let mut log_obj = LogObject::new(LogType::Log);
@@ -178,14 +186,8 @@ impl tracing::Subscriber for Consola {
             _ => LogType::Trace,
         };
         log_obj.tag = tag;
-        log_obj.args = if span_field_args.is_empty() {
-            vec![message]
-        } else {
-            let mut args = Vec::with_capacity(span_field_args.len() + 1);
-            args.push(message);
-            args.extend(span_field_args);
-            args
-        };
+        log_obj.args = vec![message];
+        log_obj.fields = span_fields;
 
         #[cfg(feature = "backtrace")]
         if raw_level == 0 {
@@ -198,7 +200,7 @@ impl tracing::Subscriber for Consola {
             });
         }
 
-        self._emit(&log_obj);
+        self.emit_entry(log_obj);
     }
 
     fn enter(&self, span: &tracing::span::Id) {
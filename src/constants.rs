@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::sync::Mutex;
 use crate::types::LogObjectInput;
 
 /// Numeric log level. Higher values mean more verbosity.
@@ -68,6 +72,8 @@ pub enum LogType {
     Trace,
     /// Verbose message.
     Verbose,
+    /// Application-specific type registered with [`define_type`].
+    Custom(&'static str),
 }
 
 impl LogType {
@@ -89,6 +95,7 @@ impl LogType {
             LogType::Debug => "debug",
             LogType::Trace => "trace",
             LogType::Verbose => "verbose",
+            LogType::Custom(name) => name,
         }
     }
 
@@ -118,7 +125,7 @@ impl std::str::FromStr for LogType {
             "debug" => LogType::Debug,
             "trace" => LogType::Trace,
             "verbose" => LogType::Verbose,
-            _ => return Err(()),
+            _ => return custom_type(s).ok_or(()),
         })
     }
 }
@@ -159,9 +166,244 @@ pub fn log_type_level(ty: LogType) -> LogLevel {
         LogType::Debug => log_levels::DEBUG,
         LogType::Trace => log_levels::TRACE,
         LogType::Verbose => log_levels::VERBOSE,
+        LogType::Custom(name) => custom_type_def(name).map_or(log_levels::LOG, |def| def.level),
+    }
+}
+
+/// Level and fancy-reporter theme of a type registered with [`define_type`]
+/// or [`define_type_with`].
+///
+/// Build one with [`CustomTypeDef::new`] and the chaining setters; the
+/// default has level [`log_levels::LOG`] and no theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomTypeDef {
+    /// Default level of entries of this type.
+    pub level: LogLevel,
+    /// Icon shown by `FancyReporter` in place of the type name.
+    pub icon: Option<&'static str>,
+    /// Icon used instead of `icon` when the terminal lacks Unicode support.
+    /// Falls back to `icon` when unset.
+    pub ascii_icon: Option<&'static str>,
+    /// Color name (as accepted by [`get_color`](crate::util::get_color)) or
+    /// `#rrggbb` hex color for the icon/badge. Hex colors are downgraded to
+    /// the terminal's [`ColorDepth`](crate::util::ColorDepth).
+    pub color: Option<&'static str>,
+}
+
+impl Default for CustomTypeDef {
+    fn default() -> Self {
+        Self::new(log_levels::LOG)
+    }
+}
+
+impl CustomTypeDef {
+    /// Create a definition at `level` with no icon or color.
+    pub const fn new(level: LogLevel) -> Self {
+        Self {
+            level,
+            icon: None,
+            ascii_icon: None,
+            color: None,
+        }
+    }
+
+    /// Set the icon, returning the builder for chaining.
+    pub const fn icon(mut self, icon: &'static str) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set the ASCII fallback icon, returning the builder for chaining.
+    pub const fn ascii_icon(mut self, icon: &'static str) -> Self {
+        self.ascii_icon = Some(icon);
+        self
+    }
+
+    /// Set the color, returning the builder for chaining.
+    pub const fn color(mut self, color: &'static str) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+static CUSTOM_TYPES: LazyLock<Mutex<HashMap<&'static str, CustomTypeDef>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn intern(s: &str) -> &'static str {
+    Box::leak(s.to_owned().into_boxed_str())
+}
+
+/// Register an application-specific log type, such as `deploy`, in one call.
+///
+/// Sets the type's default level along with the icon and color used by
+/// `FancyReporter`, and makes the name parseable via [`LogType::from_str`]
+/// and [`parse_log_level`]. Defining an existing custom type again replaces
+/// its level and theme. Registration is process-wide and meant to happen once
+/// at startup: the name, icon, and color strings are kept for the rest of the
+/// program.
+///
+/// Returns [`ConsolaError::BuiltinType`](crate::error::ConsolaError::BuiltinType)
+/// when `name` is one of the built-in types.
+///
+/// [`LogType::from_str`]: std::str::FromStr::from_str
+pub fn define_type(
+    name: &str,
+    level: LogLevel,
+    icon: Option<&str>,
+    color: Option<&str>,
+) -> Result<LogType, crate::error::ConsolaError> {
+    define_type_with(
+        name,
+        CustomTypeDef {
+            level,
+            icon: icon.map(intern),
+            ascii_icon: None,
+            color: color.map(intern),
+        },
+    )
+}
+
+/// Register an application-specific log type from a full [`CustomTypeDef`],
+/// including an ASCII fallback icon.
+///
+/// Behaves like [`define_type`] otherwise:
+///
+/// ```
+/// use consola::constants::{CustomTypeDef, define_type_with, log_levels};
+///
+/// let deploy = define_type_with(
+///     "deploy",
+///     CustomTypeDef::new(log_levels::INFO).icon("🚀").ascii_icon("^").color("magenta"),
+/// )
+/// .unwrap();
+/// assert_eq!(deploy.as_str(), "deploy");
+/// ```
+pub fn define_type_with(
+    name: &str,
+    def: CustomTypeDef,
+) -> Result<LogType, crate::error::ConsolaError> {
+    if LOG_TYPES.iter().any(|ty| ty.as_str() == name) {
+        return Err(crate::error::ConsolaError::BuiltinType(name.to_string()));
+    }
+    let mut types = CUSTOM_TYPES.lock();
+    let key = match types.get_key_value(name) {
+        Some((key, _)) => *key,
+        None => intern(name),
+    };
+    types.insert(key, def);
+    Ok(LogType::Custom(key))
+}
+
+/// Look up a type registered with [`define_type`] by name.
+pub fn custom_type(name: &str) -> Option<LogType> {
+    CUSTOM_TYPES
+        .lock()
+        .get_key_value(name)
+        .map(|(key, _)| LogType::Custom(key))
+}
+
+/// Level and theme of a type registered with [`define_type`].
+pub fn custom_type_def(name: &str) -> Option<CustomTypeDef> {
+    CUSTOM_TYPES.lock().get(name).copied()
+}
+
+/// Every type registered with [`define_type`] and its definition, sorted by name.
+pub fn custom_types() -> Vec<(&'static str, CustomTypeDef)> {
+    let mut types: Vec<_> = CUSTOM_TYPES
+        .lock()
+        .iter()
+        .map(|(name, def)| (*name, *def))
+        .collect();
+    types.sort_by_key(|(name, _)| *name);
+    types
+}
+
+/// Unregister every custom type, leaving only the built-in [`LOG_TYPES`].
+///
+/// Lets tests that call [`define_type`] start from a clean registry. The
+/// registry is process-wide, so tests running in parallel in the same binary
+/// see the reset too; see [`scoped_custom_types`] to undo only a test's own
+/// registrations. Requires the `testing` Cargo feature.
+#[cfg(feature = "testing")]
+pub fn reset_custom_types() {
+    CUSTOM_TYPES.lock().clear();
+}
+
+/// Snapshot the custom type registry, restoring it when the returned guard
+/// is dropped. Requires the `testing` Cargo feature.
+///
+/// Types defined or redefined while the guard is alive are forgotten once it
+/// goes out of scope.
+#[cfg(feature = "testing")]
+pub fn scoped_custom_types() -> CustomTypesGuard {
+    CustomTypesGuard {
+        saved: CUSTOM_TYPES.lock().clone(),
+    }
+}
+
+/// Restores the custom type registry on drop; see [`scoped_custom_types`].
+#[cfg(feature = "testing")]
+#[derive(Debug)]
+#[must_use = "the registry is restored as soon as the guard is dropped"]
+pub struct CustomTypesGuard {
+    saved: HashMap<&'static str, CustomTypeDef>,
+}
+
+#[cfg(feature = "testing")]
+impl Drop for CustomTypesGuard {
+    fn drop(&mut self) {
+        *CUSTOM_TYPES.lock() = std::mem::take(&mut self.saved);
     }
 }
 
+/// All known types: the built-in [`LOG_TYPES`] followed by the
+/// [custom types](custom_types), sorted by name.
+pub fn all_log_types() -> Vec<LogType> {
+    LOG_TYPES
+        .iter()
+        .copied()
+        .chain(
+            custom_types()
+                .into_iter()
+                .map(|(name, _)| LogType::Custom(name)),
+        )
+        .collect()
+}
+
+/// Names of every type whose default level is `level`, in [`all_log_types`] order.
+///
+/// For example level `3` yields `info`, `success`, `fail`, `ready`, `start`
+/// and `box`, plus any custom types defined at that level.
+pub fn type_names_for_level(level: LogLevel) -> Vec<&'static str> {
+    all_log_types()
+        .into_iter()
+        .filter(|ty| ty.level() == level)
+        .map(LogType::as_str)
+        .collect()
+}
+
+/// The canonical type for `level`, as a bridge or custom reporter would name it.
+///
+/// The built-in levels map to `error`, `warn`, `log`, `info`, `debug`,
+/// `trace` and `verbose`; other levels map to the first type in
+/// [`all_log_types`] order at that level, if any.
+pub fn primary_type_for_level(level: LogLevel) -> Option<LogType> {
+    const PRIMARY: &[LogType] = &[
+        LogType::Error,
+        LogType::Warn,
+        LogType::Log,
+        LogType::Info,
+        LogType::Debug,
+        LogType::Trace,
+        LogType::Verbose,
+    ];
+    PRIMARY
+        .iter()
+        .copied()
+        .find(|ty| ty.level() == level)
+        .or_else(|| all_log_types().into_iter().find(|ty| ty.level() == level))
+}
+
 /// The per-type default partial input (as in JS `LogTypes`).
 #[inline]
 pub fn log_type_defaults(ty: LogType) -> LogObjectInput {
@@ -173,8 +415,46 @@ pub fn log_type_defaults(ty: LogType) -> LogObjectInput {
     }
 }
 
+/// Whether a message at `msg_level` is shown when the instance is configured at `level`.
+///
+/// Lower numbers are more severe, so a message passes when `msg_level <= level`.
+/// The one exception is [`log_levels::SILENT`]: configured as the level it
+/// suppresses everything, including custom levels at or below `SILENT` itself.
+/// Any other negative configuration is compared numerically, so a message
+/// logged at `-100` is shown at level `0` (it is more severe than `FATAL`).
+pub fn passes_level(msg_level: LogLevel, level: LogLevel) -> bool {
+    level != log_levels::SILENT && msg_level <= level
+}
+
 /// Normalize an optional level / type to a concrete numeric level.
 pub fn normalize_log_level(input: Option<LogLevel>, default_level: LogLevel) -> LogLevel {
     let level = input.unwrap_or(default_level);
     level.clamp(0, 5)
 }
+
+/// Parse a log level from a numeric string (`"4"`) or a log type name (`"debug"`).
+///
+/// Names are case-insensitive, and common aliases are accepted: `warning`
+/// (warn), `err` (error), `dbg` (debug), `all` (verbose), and `off` / `none`
+/// ([`log_levels::SILENT`]).
+///
+/// Returns `None` when the input is neither.
+pub fn parse_log_level(input: &str) -> Option<LogLevel> {
+    let input = input.trim();
+    if let Ok(n) = input.parse::<LogLevel>() {
+        return Some(n);
+    }
+    if let Ok(ty) = input.parse::<LogType>() {
+        return Some(ty.level());
+    }
+    let lower = input.to_ascii_lowercase();
+    let name = match lower.as_str() {
+        "off" | "none" => return Some(log_levels::SILENT),
+        "warning" => "warn",
+        "err" => "error",
+        "dbg" => "debug",
+        "all" => "verbose",
+        name => name,
+    };
+    name.parse::<LogType>().ok().map(LogType::level)
+}
@@ -32,9 +32,27 @@ pub enum ConsolaError {
     #[error("reporter error: {0}")]
     Reporter(String),
 
+    /// A custom log type tried to reuse the name of a built-in type.
+    #[error("`{0}` is a built-in log type and cannot be redefined")]
+    BuiltinType(String),
+
+    /// A level directive (e.g. `mycrate=debug`) named an unknown level.
+    #[error("invalid level in directive: {0}")]
+    InvalidDirective(String),
+
     /// A value lookup failed unexpectedly.
     #[error("lookup failed: {0}")]
     Lookup(String),
+
+    /// Encoded log records could not be decoded.
+    #[error("invalid protobuf record: {0}")]
+    Decode(String),
+
+    /// A reporter writing to its own sink has stopped after
+    /// `write_failure_limit` consecutive failed writes. `Consola` counts the
+    /// entry as dropped without reporting the error again.
+    #[error("sink stopped after repeated write failures")]
+    SinkLatched,
 }
 
 impl From<String> for ConsolaError {
@@ -122,6 +140,14 @@ mod tests {
         assert!(debug.contains("oops"));
     }
 
+    #[test]
+    fn test_sink_latched_display() {
+        assert_eq!(
+            ConsolaError::SinkLatched.to_string(),
+            "sink stopped after repeated write failures"
+        );
+    }
+
     #[test]
     fn test_lookup_display() {
         let err = ConsolaError::Lookup("key not found".into());
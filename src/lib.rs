@@ -9,9 +9,20 @@
 //!   - `tracing`: implement `tracing::Subscriber` (receive from `tracing` crate)
 //!   - `browser`: browser console styling via `web-sys` (runtime detection)
 //!   - `parking_lot`: use `parking_lot::Mutex` (default: std::sync::Mutex)
+//!   - `testing`: opt-in per-thread output capture through
+//!     [`Consola::capture_output`] (see [`ThreadLocalSink`](util::sink::ThreadLocalSink)),
+//!     and custom log types can be reset between tests
+//!   - `proto`: length-delimited protobuf output via `reporters::ProtoReporter`
+//!   - `syslog`: syslog output via `reporters::SyslogReporter`
 //!   - `prompt`: interactive prompts via demand
 //!   - `prompt-inquire`: interactive prompts via inquire
 //!   - `prompt-dialoguer`: interactive prompts via dialoguer
+//!
+//! The crate requires `std`: it uses `std::sync` locks and statics,
+//! `std::time::Instant` for throttle windows, `std::io` for sinks, `std::env`
+//! for configuration and `std::error::Error` for error chains. Constrained
+//! targets can supply their own [`Clock`] (returning `None` from
+//! `now()` disables time windows) and [`Sink`].
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
 
@@ -47,42 +58,46 @@ use std::sync::LazyLock;
 use reporters::{BasicReporter, FancyReporter};
 /// Create a new Consola instance with the given reporters and options.
 ///
-/// By default uses `FancyReporter`. Pass `Reporters::Basic` to use the basic reporter.
+/// By default uses [`FancyReporter::adaptive`] (or the reporter named by `CONSOLA_FORMAT`).
+/// When `level` is `None`, `CONSOLA_LEVEL` is consulted before falling back to `INFO`.
+/// Format options are read from the environment via [`ConsolaOptions::from_env`].
 pub fn create_consola(
     level: Option<LogLevel>,
     reporters: Vec<Box<dyn types::Reporter>>,
 ) -> Consola {
-    let level = level.unwrap_or(constants::log_levels::INFO);
-    let reporters = if reporters.is_empty() {
-        vec![Box::new(FancyReporter::new()) as Box<dyn types::Reporter>]
-    } else {
+    let env = ConsolaOptions::from_env();
+    let reporters = if !reporters.is_empty() {
         reporters
+    } else if !env.reporters.is_empty() {
+        env.reporters.clone()
+    } else {
+        vec![Box::new(FancyReporter::adaptive()) as Box<dyn types::Reporter>]
     };
 
     Consola::new(ConsolaOptions {
-        level,
+        level: level.unwrap_or(env.level),
         reporters,
-        ..ConsolaOptions::default()
+        ..env
     })
 }
 
 /// Create a Consola instance with only `BasicReporter`.
 pub fn create_basic_consola(level: Option<LogLevel>) -> Consola {
-    let level = level.unwrap_or(constants::log_levels::INFO);
+    let env = ConsolaOptions::from_env();
     Consola::new(ConsolaOptions {
-        level,
-        reporters: vec![Box::new(BasicReporter) as Box<dyn types::Reporter>],
-        ..ConsolaOptions::default()
+        level: level.unwrap_or(env.level),
+        reporters: vec![Box::new(BasicReporter::new()) as Box<dyn types::Reporter>],
+        ..env
     })
 }
 
 /// Create a Consola instance with only `FancyReporter`.
 pub fn create_fancy_consola(level: Option<LogLevel>) -> Consola {
-    let level = level.unwrap_or(constants::log_levels::INFO);
+    let env = ConsolaOptions::from_env();
     Consola::new(ConsolaOptions {
-        level,
-        reporters: vec![Box::new(FancyReporter::new()) as Box<dyn types::Reporter>],
-        ..ConsolaOptions::default()
+        level: level.unwrap_or(env.level),
+        reporters: vec![Box::new(FancyReporter::adaptive()) as Box<dyn types::Reporter>],
+        ..env
     })
 }
 
@@ -91,19 +106,28 @@ pub fn create_core_consola(
     level: Option<LogLevel>,
     reporters: Vec<Box<dyn types::Reporter>>,
 ) -> Consola {
-    let level = level.unwrap_or(constants::log_levels::INFO);
+    let env = ConsolaOptions::from_env();
     Consola::new(ConsolaOptions {
-        level,
+        level: level.unwrap_or(env.level),
         reporters,
-        ..ConsolaOptions::default()
+        ..env
     })
 }
 
 /// A default, lazily-initialized [`Consola`] instance for convenience use.
 pub static CONSOLA: LazyLock<Consola> = LazyLock::new(|| create_consola(None, vec![]));
 
-pub use consola::Consola;
-pub use constants::{LogLevel, LogType, log_levels};
+/// Whether entries of type `ty` would be logged by [`CONSOLA`] at its
+/// current level and silence state.
+///
+/// Reflects [`Consola::set_level`] and [`Consola::silence`] calls on the
+/// global instance; its initial level comes from `CONSOLA_LEVEL`.
+pub fn is_log_type_enabled(ty: LogType) -> bool {
+    CONSOLA.is_enabled(ty)
+}
+
+pub use consola::{Consola, HookHandle};
+pub use constants::{LogLevel, LogType, define_type, define_type_with, log_levels};
 pub use types::{ConsolaOptions as ConsolaOpts, FormatOptions, LogObject, LogObjectInput};
-pub use types::{ConsolaOptions, LogContext, Reporter};
+pub use types::{ConsolaOptions, GroupEvent, LogContext, Reporter};
 pub use util::*;
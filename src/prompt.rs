@@ -1,7 +1,8 @@
 //! Interactive prompts for user input.
 //!
-//! Provides [`text()`], [`confirm()`], [`select()`], and [`multiselect()`] functions,
-//! plus the sentinel constant [`K_CANCEL`] returned when the user aborts.
+//! Provides [`text()`], [`confirm()`], [`select()`], [`multiselect()`], [`password()`],
+//! [`number()`], and [`autocomplete()`] functions, plus the sentinel constant
+//! [`K_CANCEL`] returned when the user aborts.
 //!
 //! Backend selection (priority):
 //! - `prompt` → demand (default)
@@ -12,16 +13,23 @@
 pub const K_CANCEL: &str = "Symbol(cancel)";
 
 pub use crate::types::{
-    ConfirmPromptOptions, MultiSelectOptions, PromptCommonOptions, PromptOptions, SelectOption,
-    SelectPromptOptions, TextPromptOptions,
+    AutocompletePromptOptions, ConfirmPromptOptions, MultiSelectOptions, NumberPromptOptions,
+    PasswordPromptOptions, PromptCommonOptions, PromptOptions, SelectOption, SelectPromptOptions,
+    TextPromptOptions,
 };
 
 /// Prompt the user for free-form text input.
+///
+/// `opts.initial` pre-fills the editable input, `opts.default` is returned
+/// when the user submits an empty answer, and `opts.placeholder` is only a
+/// visual hint that is never returned.
 pub fn text(message: &str, opts: &TextPromptOptions) -> Result<String, crate::error::ConsolaError> {
     backend::text(message, opts)
 }
 
 /// Prompt the user for a yes/no confirmation.
+///
+/// Submitting without choosing (e.g. pressing Enter) yields `opts.initial`.
 pub fn confirm(
     message: &str,
     opts: &ConfirmPromptOptions,
@@ -45,6 +53,72 @@ pub fn multiselect(
     backend::multiselect(message, opts)
 }
 
+/// Prompt the user for a password. Input is masked and never echoed.
+pub fn password(
+    message: &str,
+    opts: &PasswordPromptOptions,
+) -> Result<String, crate::error::ConsolaError> {
+    backend::password(message, opts)
+}
+
+/// Prompt the user for a number, asking again until the input parses and
+/// lies within `opts.min`..=`opts.max`.
+pub fn number(
+    message: &str,
+    opts: &NumberPromptOptions,
+) -> Result<f64, crate::error::ConsolaError> {
+    backend::number(message, opts)
+}
+
+/// Prompt the user for text, suggesting matching entries from `opts.candidates`.
+pub fn autocomplete(
+    message: &str,
+    opts: &AutocompletePromptOptions,
+) -> Result<String, crate::error::ConsolaError> {
+    backend::autocomplete(message, opts)
+}
+
+/// Check `value` against the bounds in `opts`.
+#[allow(dead_code)]
+fn check_number(value: f64, opts: &NumberPromptOptions) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err("Please enter a finite number".into());
+    }
+    if let Some(min) = opts.min
+        && value < min
+    {
+        return Err(format!("Please enter a number >= {}", min));
+    }
+    if let Some(max) = opts.max
+        && value > max
+    {
+        return Err(format!("Please enter a number <= {}", max));
+    }
+    Ok(())
+}
+
+/// Parse and validate numeric prompt input.
+#[allow(dead_code)]
+fn parse_number(input: &str, opts: &NumberPromptOptions) -> Result<f64, String> {
+    let value = input
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a number", input.trim()))?;
+    check_number(value, opts)?;
+    Ok(value)
+}
+
+/// Candidates containing `input` (case-insensitive), in their original order.
+#[allow(dead_code)]
+fn matching_candidates(input: &str, candidates: &[String]) -> Vec<String> {
+    let needle = input.to_lowercase();
+    candidates
+        .iter()
+        .filter(|c| c.to_lowercase().contains(&needle))
+        .cloned()
+        .collect()
+}
+
 /// Mock backend used in `#[cfg(test)]` — always active in tests, regardless of features.
 /// Returns deterministic values without real TTY access.
 #[cfg(test)]
@@ -55,7 +129,11 @@ mod backend {
         _message: &str,
         opts: &TextPromptOptions,
     ) -> Result<String, crate::error::ConsolaError> {
-        Ok(opts.default.clone().unwrap_or_else(|| "mock-text".into()))
+        Ok(opts
+            .initial
+            .clone()
+            .or_else(|| opts.default.clone())
+            .unwrap_or_else(|| "mock-text".into()))
     }
 
     pub(super) fn confirm(
@@ -82,6 +160,33 @@ mod backend {
     ) -> Result<Vec<String>, crate::error::ConsolaError> {
         Ok(opts.options.iter().map(|o| o.value.clone()).collect())
     }
+
+    pub(super) fn password(
+        _message: &str,
+        _opts: &PasswordPromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        Ok("mock-password".into())
+    }
+
+    pub(super) fn number(
+        _message: &str,
+        opts: &NumberPromptOptions,
+    ) -> Result<f64, crate::error::ConsolaError> {
+        let value = opts.default.or(opts.min).unwrap_or(0.0);
+        check_number(value, opts).map_err(crate::error::ConsolaError::Prompt)?;
+        Ok(value)
+    }
+
+    pub(super) fn autocomplete(
+        _message: &str,
+        opts: &AutocompletePromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        Ok(opts
+            .default
+            .clone()
+            .or_else(|| opts.candidates.first().cloned())
+            .unwrap_or_else(|| "mock-text".into()))
+    }
 }
 
 /// Demand backend (highest priority).
@@ -98,10 +203,15 @@ mod backend {
         if let Some(placeholder) = &opts.placeholder {
             input = input.placeholder(placeholder);
         }
-        if let Some(default) = &opts.default {
-            input = input.default_value(default);
+        if let Some(initial) = &opts.initial {
+            input = input.default_value(initial);
+        }
+        let answer = input.run().map_err(map_err_demand)?;
+        // demand has no separate "value on empty submit", so apply it here.
+        match &opts.default {
+            Some(default) if answer.is_empty() => Ok(default.clone()),
+            _ => Ok(answer),
         }
-        input.run().map_err(map_err_demand)
     }
 
     pub(super) fn confirm(
@@ -158,6 +268,48 @@ mod backend {
         ms.options(items).run().map_err(map_err_demand)
     }
 
+    pub(super) fn password(
+        message: &str,
+        opts: &PasswordPromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        let mut input = Input::new(message).password(true);
+        if let Some(placeholder) = &opts.placeholder {
+            input = input.placeholder(placeholder);
+        }
+        input.run().map_err(map_err_demand)
+    }
+
+    pub(super) fn number(
+        message: &str,
+        opts: &NumberPromptOptions,
+    ) -> Result<f64, crate::error::ConsolaError> {
+        let bounds = opts.clone();
+        let mut input =
+            Input::new(message).validator(move |s: &str| parse_number(s, &bounds).map(|_| ()));
+        if let Some(default) = opts.default {
+            input = input.default_value(default.to_string());
+        }
+        let raw = input.run().map_err(map_err_demand)?;
+        parse_number(&raw, opts).map_err(crate::error::ConsolaError::Prompt)
+    }
+
+    pub(super) fn autocomplete(
+        message: &str,
+        opts: &AutocompletePromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        let candidates = opts.candidates.clone();
+        let mut input = Input::new(message).autocomplete_fn(move |s: &str| {
+            Ok::<_, Box<dyn std::error::Error>>(matching_candidates(s, &candidates))
+        });
+        if let Some(placeholder) = &opts.placeholder {
+            input = input.placeholder(placeholder);
+        }
+        if let Some(default) = &opts.default {
+            input = input.default_value(default);
+        }
+        input.run().map_err(map_err_demand)
+    }
+
     fn map_err_demand(e: std::io::Error) -> crate::error::ConsolaError {
         if e.kind() == std::io::ErrorKind::Interrupted {
             crate::error::ConsolaError::PromptCancelled
@@ -183,6 +335,9 @@ mod backend {
         if let Some(default) = &opts.default {
             input = input.with_default(default);
         }
+        if let Some(initial) = &opts.initial {
+            input = input.with_initial_value(initial);
+        }
         input
             .prompt()
             .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
@@ -235,6 +390,58 @@ mod backend {
             })
             .collect()
     }
+
+    pub(super) fn password(
+        message: &str,
+        _opts: &PasswordPromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        inquire::Password::new(message)
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
+
+    pub(super) fn number(
+        message: &str,
+        opts: &NumberPromptOptions,
+    ) -> Result<f64, crate::error::ConsolaError> {
+        let bounds = opts.clone();
+        let mut input = inquire::CustomType::<f64>::new(message).with_validator(
+            move |v: &f64| -> Result<inquire::validator::Validation, inquire::CustomUserError> {
+                Ok(match check_number(*v, &bounds) {
+                    Ok(()) => inquire::validator::Validation::Valid,
+                    Err(msg) => inquire::validator::Validation::Invalid(msg.into()),
+                })
+            },
+        );
+        if let Some(default) = opts.default {
+            input = input.with_default(default);
+        }
+        input
+            .prompt()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
+
+    pub(super) fn autocomplete(
+        message: &str,
+        opts: &AutocompletePromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        let candidates = opts.candidates.clone();
+        let mut input = inquire::Text::new(message).with_autocomplete(
+            move |s: &str| -> Result<Vec<String>, inquire::CustomUserError> {
+                Ok(matching_candidates(s, &candidates))
+            },
+        );
+        if let Some(placeholder) = &opts.placeholder {
+            input = input.with_placeholder(placeholder);
+        }
+        if let Some(default) = &opts.default {
+            input = input.with_default(default);
+        }
+        input
+            .prompt()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
 }
 
 /// Dialoguer backend (lowest priority).
@@ -252,7 +459,10 @@ mod backend {
     ) -> Result<String, crate::error::ConsolaError> {
         let mut input = dialoguer::Input::<String>::new().with_prompt(message);
         if let Some(default) = &opts.default {
-            input = input.with_initial_text(default);
+            input = input.default(default.clone());
+        }
+        if let Some(initial) = &opts.initial {
+            input = input.with_initial_text(initial);
         }
         input
             .allow_empty(true)
@@ -301,6 +511,58 @@ mod backend {
             .map(|&i| opts.options[i].value.clone())
             .collect())
     }
+
+    pub(super) fn password(
+        message: &str,
+        _opts: &PasswordPromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        dialoguer::Password::new()
+            .with_prompt(message)
+            .allow_empty_password(true)
+            .interact()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
+
+    pub(super) fn number(
+        message: &str,
+        opts: &NumberPromptOptions,
+    ) -> Result<f64, crate::error::ConsolaError> {
+        let mut input = dialoguer::Input::<f64>::new()
+            .with_prompt(message)
+            .validate_with(|v: &f64| check_number(*v, opts));
+        if let Some(default) = opts.default {
+            input = input.default(default);
+        }
+        input
+            .interact_text()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
+
+    /// Completes to the first candidate matching the typed text.
+    struct CandidateCompletion<'a>(&'a [String]);
+
+    impl dialoguer::Completion for CandidateCompletion<'_> {
+        fn get(&self, input: &str) -> Option<String> {
+            matching_candidates(input, self.0).into_iter().next()
+        }
+    }
+
+    pub(super) fn autocomplete(
+        message: &str,
+        opts: &AutocompletePromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        let completion = CandidateCompletion(&opts.candidates);
+        let mut input = dialoguer::Input::<String>::new()
+            .with_prompt(message)
+            .completion_with(&completion);
+        if let Some(default) = &opts.default {
+            input = input.default(default.clone());
+        }
+        input
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|e| crate::error::ConsolaError::Prompt(e.to_string()))
+    }
 }
 
 /// Stub backend when no prompt feature is enabled and we are not in tests.
@@ -342,4 +604,185 @@ mod backend {
     ) -> Result<Vec<String>, crate::error::ConsolaError> {
         Err(crate::error::ConsolaError::NoPromptBackend)
     }
+
+    pub(super) fn password(
+        _message: &str,
+        _opts: &PasswordPromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        Err(crate::error::ConsolaError::NoPromptBackend)
+    }
+
+    pub(super) fn number(
+        _message: &str,
+        _opts: &NumberPromptOptions,
+    ) -> Result<f64, crate::error::ConsolaError> {
+        Err(crate::error::ConsolaError::NoPromptBackend)
+    }
+
+    pub(super) fn autocomplete(
+        _message: &str,
+        _opts: &AutocompletePromptOptions,
+    ) -> Result<String, crate::error::ConsolaError> {
+        Err(crate::error::ConsolaError::NoPromptBackend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn common() -> PromptCommonOptions {
+        PromptCommonOptions { cancel: None }
+    }
+
+    fn number_opts(
+        default: Option<f64>,
+        min: Option<f64>,
+        max: Option<f64>,
+    ) -> NumberPromptOptions {
+        NumberPromptOptions {
+            common: common(),
+            default,
+            min,
+            max,
+        }
+    }
+
+    fn text_opts(
+        default: Option<&str>,
+        placeholder: Option<&str>,
+        initial: Option<&str>,
+    ) -> TextPromptOptions {
+        TextPromptOptions {
+            common: common(),
+            r#type: None,
+            default: default.map(Into::into),
+            placeholder: placeholder.map(Into::into),
+            initial: initial.map(Into::into),
+        }
+    }
+
+    fn confirm_opts(initial: Option<bool>) -> ConfirmPromptOptions {
+        ConfirmPromptOptions {
+            common: common(),
+            r#type: "confirm".into(),
+            initial,
+        }
+    }
+
+    #[test]
+    fn test_confirm_accepts_default() {
+        assert!(confirm("Proceed?", &confirm_opts(Some(true))).unwrap());
+        assert!(!confirm("Proceed?", &confirm_opts(Some(false))).unwrap());
+    }
+
+    #[test]
+    fn test_text_empty_submission_yields_default() {
+        let opts = text_opts(Some("guest"), None, None);
+        assert_eq!(text("Name", &opts).unwrap(), "guest");
+    }
+
+    #[test]
+    fn test_text_placeholder_is_not_a_default() {
+        let opts = text_opts(None, Some("your name"), None);
+        assert_ne!(text("Name", &opts).unwrap(), "your name");
+    }
+
+    #[test]
+    fn test_text_initial_prefills_answer() {
+        let opts = text_opts(Some("guest"), None, Some("admin"));
+        assert_eq!(text("Name", &opts).unwrap(), "admin");
+    }
+
+    #[test]
+    fn test_password_returns_mock_value() {
+        let opts = PasswordPromptOptions {
+            common: common(),
+            placeholder: None,
+        };
+        assert_eq!(password("Password", &opts).unwrap(), "mock-password");
+    }
+
+    #[test]
+    fn test_number_returns_default() {
+        let opts = number_opts(Some(42.0), None, None);
+        assert_eq!(number("Count", &opts).unwrap(), 42.0);
+    }
+
+    #[test]
+    fn test_number_falls_back_to_min() {
+        let opts = number_opts(None, Some(3.0), Some(10.0));
+        assert_eq!(number("Count", &opts).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_number_default_out_of_bounds_errors() {
+        let opts = number_opts(Some(20.0), None, Some(10.0));
+        assert!(matches!(
+            number("Count", &opts),
+            Err(crate::error::ConsolaError::Prompt(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_number_valid() {
+        let opts = number_opts(None, Some(0.0), Some(100.0));
+        assert_eq!(parse_number(" 12.5 ", &opts), Ok(12.5));
+    }
+
+    #[test]
+    fn test_parse_number_rejects_garbage() {
+        let opts = number_opts(None, None, None);
+        assert_eq!(
+            parse_number("abc", &opts),
+            Err("'abc' is not a number".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_number_rejects_out_of_range() {
+        let opts = number_opts(None, Some(1.0), Some(5.0));
+        assert!(parse_number("0", &opts).is_err());
+        assert!(parse_number("6", &opts).is_err());
+        assert_eq!(parse_number("5", &opts), Ok(5.0));
+    }
+
+    #[test]
+    fn test_parse_number_rejects_non_finite() {
+        let opts = number_opts(None, None, None);
+        assert!(parse_number("NaN", &opts).is_err());
+        assert!(parse_number("inf", &opts).is_err());
+    }
+
+    #[test]
+    fn test_matching_candidates_case_insensitive() {
+        let candidates = vec!["Apple".to_string(), "banana".into(), "Pineapple".into()];
+        assert_eq!(
+            matching_candidates("APP", &candidates),
+            vec!["Apple".to_string(), "Pineapple".into()]
+        );
+        assert!(matching_candidates("kiwi", &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_autocomplete_prefers_default() {
+        let opts = AutocompletePromptOptions {
+            common: common(),
+            candidates: vec!["red".into(), "green".into()],
+            default: Some("blue".into()),
+            placeholder: None,
+        };
+        assert_eq!(autocomplete("Color", &opts).unwrap(), "blue");
+    }
+
+    #[test]
+    fn test_autocomplete_falls_back_to_first_candidate() {
+        let opts = AutocompletePromptOptions {
+            common: common(),
+            candidates: vec!["red".into(), "green".into()],
+            default: None,
+            placeholder: None,
+        };
+        assert_eq!(autocomplete("Color", &opts).unwrap(), "red");
+    }
 }
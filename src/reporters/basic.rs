@@ -1,6 +1,18 @@
 //! BasicReporter — pure formatter — no I/O. Returns Result<String, String> for the Consola to emit.
 
-use crate::types::{ErrorInfo, FormatOptions, LogContext, LogObject, Reporter};
+use std::borrow::Cow;
+
+use crate::reporters::fancy::type_color_name;
+use crate::reporters::segment::{SegmentKind, build_basic_segments};
+use crate::types::{
+    ErrorInfo, ErrorLayout, FormatOptions, GroupEvent, LogContext, LogObject, Reporter, TimeMode,
+    TimePrecision,
+};
+use crate::util::color::paint_always;
+use crate::util::string::{
+    coalesce_styles, indent_lines, mark_continuation_lines, prefix_first_line, string_width,
+    truncate_lines, wrap_prefixed,
+};
 
 fn bracket(x: &str) -> String {
     if x.is_empty() {
@@ -10,9 +22,65 @@ fn bracket(x: &str) -> String {
     }
 }
 
+/// Local hour, minute and second of `ms` milliseconds since the Unix epoch,
+/// using whichever time crate is enabled (UTC when none is).
+#[allow(unreachable_code)]
+fn local_hms(ms: i64) -> (u8, u8, u8) {
+    #[cfg(feature = "jiff")]
+    if let Ok(ts) = jiff::Timestamp::from_millisecond(ms) {
+        let civil = ts.to_zoned(jiff::tz::TimeZone::system()).datetime();
+        return (
+            civil.hour() as u8,
+            civil.minute() as u8,
+            civil.second() as u8,
+        );
+    }
+
+    #[cfg(all(feature = "chrono", not(feature = "jiff")))]
+    if let Some(utc) = chrono::DateTime::from_timestamp_millis(ms) {
+        use chrono::Timelike;
+        let local = utc.with_timezone(&chrono::Local);
+        return (
+            local.hour() as u8,
+            local.minute() as u8,
+            local.second() as u8,
+        );
+    }
+
+    #[cfg(all(feature = "time", not(any(feature = "jiff", feature = "chrono"))))]
+    if let Ok(utc) = time::OffsetDateTime::from_unix_timestamp_nanos(ms as i128 * 1_000_000) {
+        let offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+        let local = utc.to_offset(offset);
+        return (local.hour(), local.minute(), local.second());
+    }
+
+    // Fallback: UTC (unreachable when a crate feature is active)
+    let secs = ms.div_euclid(1000);
+    let day = secs.rem_euclid(86_400);
+    ((day / 3600) as u8, (day / 60 % 60) as u8, (day % 60) as u8)
+}
+
+/// Formats an elapsed time of `ms` milliseconds as `+1.250s`, or as
+/// `+2m05.000s` from a minute on. Negative durations show as `+0.000s`.
+fn format_elapsed(ms: i64, precision: TimePrecision) -> String {
+    let ms = ms.max(0);
+    let (mins, secs) = (ms / 60_000, ms / 1000 % 60);
+    let fraction = precision.fraction(ms, 3);
+    if mins > 0 {
+        format!("+{}m{:02}{}s", mins, secs, fraction)
+    } else {
+        format!("+{}{}s", secs, fraction)
+    }
+}
+
 /// Formats log messages as plain text.
+///
+/// With [`FormatOptions::colors`] set, the type badge is colored by type and
+/// level (red for errors, green for success, ...) like the fancy reporter's.
 #[derive(Debug, Clone)]
-pub struct BasicReporter;
+pub struct BasicReporter {
+    compact: bool,
+}
 
 impl Default for BasicReporter {
     fn default() -> Self {
@@ -23,11 +91,26 @@ impl Default for BasicReporter {
 impl BasicReporter {
     /// Creates a new `BasicReporter`.
     pub fn new() -> Self {
-        Self
+        Self { compact: false }
+    }
+
+    /// Preset for CI logs: one line per entry.
+    ///
+    /// Basic output never contains icons or cursor movement, and is only
+    /// colored when [`FormatOptions::colors`] is set.
+    pub fn for_ci() -> Self {
+        Self::new().compact(true)
+    }
+
+    /// Force compact output regardless of [`FormatOptions::compact`],
+    /// returning the builder for chaining.
+    pub fn compact(mut self, enabled: bool) -> Self {
+        self.compact = enabled;
+        self
     }
 
     /// Formats an error with its source chain (recursive).
-    pub fn format_error(err: &ErrorInfo, _opts: &FormatOptions, level: usize) -> String {
+    pub fn format_error(err: &ErrorInfo, opts: &FormatOptions, level: usize) -> String {
         let caused_prefix = if level > 0 {
             format!("{}[cause]: ", "  ".repeat(level))
         } else {
@@ -36,29 +119,48 @@ impl BasicReporter {
 
         let mut result = format!("{}{}", caused_prefix, err.message);
 
-        if let Some(stack) = &err.stack
-            && !stack.is_empty()
-        {
-            // Blank line before stack
-            result.push('\n');
-            // Indent each line of the stack
-            for line in stack.lines() {
-                let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                result.push_str(&format!("\n{}{}", "  ".repeat(level + 2), trimmed));
-            }
+        if matches!(
+            opts.error_layout,
+            ErrorLayout::StackThenChain | ErrorLayout::StackOnly
+        ) {
+            Self::push_stack(&mut result, err, level);
         }
 
-        if let Some(cause) = &err.cause {
+        if let Some(cause) = &err.cause
+            && opts.error_layout != ErrorLayout::StackOnly
+        {
             result.push_str("\n\n");
-            result.push_str(&Self::format_error(cause, _opts, level + 1));
+            result.push_str(&Self::format_error(cause, opts, level + 1));
+        }
+
+        if level == 0 && opts.error_layout == ErrorLayout::ChainThenStack {
+            let chain = std::iter::successors(Some(err), |e| e.cause.as_deref());
+            for (depth, err) in chain.enumerate() {
+                Self::push_stack(&mut result, err, depth);
+            }
         }
 
         result
     }
 
+    /// Append the stack of `err`, if any, after a blank line with each line
+    /// indented for an error `level` causes deep.
+    fn push_stack(result: &mut String, err: &ErrorInfo, level: usize) {
+        let Some(stack) = err.stack.as_deref().filter(|s| !s.is_empty()) else {
+            return;
+        };
+        // Blank line before stack
+        result.push('\n');
+        // Indent each line of the stack
+        for line in stack.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            result.push_str(&format!("\n{}{}", "  ".repeat(level + 2), trimmed));
+        }
+    }
+
     /// Joins the log message arguments into a single space-separated string.
     /// Detects errors in args and formats them with source chains.
     pub fn format_args(&self, args: &[String], _opts: &FormatOptions) -> String {
@@ -69,6 +171,30 @@ impl BasicReporter {
         parts.join(" ")
     }
 
+    /// Formats the entry's args and appends its fields as `key=value` pairs
+    /// to the end of the first line.
+    pub fn format_message(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
+        let message = self.format_args(&log_obj.args, opts);
+        if log_obj.fields.is_empty() {
+            return message;
+        }
+        let pairs: Vec<String> = log_obj
+            .fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        let (first, rest) = match message.split_once('\n') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (message.as_str(), None),
+        };
+        let mut line = self.filter_and_join(&[first.trim_end().to_string(), pairs.join(" ")]);
+        if let Some(rest) = rest {
+            line.push('\n');
+            line.push_str(rest);
+        }
+        line
+    }
+
     /// Formats the current time as 12-hour local time (`h:mm:ss AM/PM`).
     #[allow(unreachable_code)]
     pub fn format_date(&self, opts: &FormatOptions) -> String {
@@ -77,109 +203,128 @@ impl BasicReporter {
 
     /// Like `format_date` but accepts an explicit timestamp (milliseconds since epoch).
     /// Used internally so tests can inject a specific time.
-    #[allow(unreachable_code)]
-    pub(crate) fn format_date_at(&self, opts: &FormatOptions, _now_ms: i64) -> String {
-        if opts.date {
-            #[cfg(feature = "jiff")]
-            {
-                if let Ok(ts) = jiff::Timestamp::from_millisecond(_now_ms) {
-                    let zoned = ts.to_zoned(jiff::tz::TimeZone::system());
-                    let civil = zoned.datetime();
-                    let h = civil.hour();
-                    let hour12 = match h {
-                        0 => 12,
-                        1..=12 => h,
-                        _ => h - 12,
-                    };
-                    let ampm = if h < 12 { "AM" } else { "PM" };
-                    return format!(
-                        "{}:{:02}:{:02} {}",
-                        hour12,
-                        civil.minute(),
-                        civil.second(),
-                        ampm
-                    );
-                }
-            }
-
-            #[cfg(all(feature = "chrono", not(feature = "jiff")))]
-            {
-                use chrono::Timelike;
-                let local = chrono::Local::now();
-                let h = local.hour12();
-                let hour12 = match h.1 {
-                    0 => 12,
-                    n => n,
-                };
-                let ampm = if h.0 { "PM" } else { "AM" };
-                return format!(
-                    "{}:{:02}:{:02} {}",
-                    hour12,
-                    local.minute(),
-                    local.second(),
-                    ampm
-                );
-            }
-
-            #[cfg(all(feature = "time", not(any(feature = "jiff", feature = "chrono"))))]
-            {
-                let offset =
-                    time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-                let now = time::OffsetDateTime::now_utc().to_offset(offset);
-                let h = now.hour();
-                let hour12 = match h {
-                    0 => 12,
-                    1..=12 => h,
-                    _ => h - 12,
-                };
-                let ampm = if h < 12 { "AM" } else { "PM" };
-                return format!(
-                    "{}:{:02}:{:02} {}",
-                    hour12,
-                    now.minute(),
-                    now.second(),
-                    ampm
-                );
-            }
+    pub(crate) fn format_date_at(&self, opts: &FormatOptions, now_ms: i64) -> String {
+        if !opts.date {
+            return String::new();
+        }
+        let (h, m, s) = local_hms(now_ms);
+        let hour12 = match h {
+            0 => 12,
+            1..=12 => h,
+            _ => h - 12,
+        };
+        let ampm = if h < 12 { "AM" } else { "PM" };
+        let fraction = opts.time_precision.fraction(now_ms, 0);
+        format!("{}:{:02}:{:02}{} {}", hour12, m, s, fraction, ampm)
+    }
 
-            // Fallback: UTC-based 12-hour (unreachable when a crate feature is active)
-            {
-                let total_secs = (_now_ms / 1000) as u64;
-                let hours = (total_secs / 3600) % 24;
-                let mins = (total_secs / 60) % 60;
-                let secs = total_secs % 60;
-                let hour12 = match hours {
-                    0 => 12,
-                    1..=12 => hours,
-                    _ => hours - 12,
-                };
-                let ampm = if hours < 12 { "AM" } else { "PM" };
-                format!("{}:{:02}:{:02} {}", hour12, mins, secs, ampm)
-            }
-        } else {
-            String::new()
+    /// Timestamp prefix of `log_obj` in the style of `opts.time_mode`, or an
+    /// empty string when timestamps are off.
+    pub(crate) fn format_entry_time(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
+        match (opts.time_mode, opts.time_origin_ms) {
+            _ if !opts.date => String::new(),
+            (TimeMode::None, _) => String::new(),
+            (TimeMode::Relative, Some(origin)) => format_elapsed(
+                log_obj.timestamp_ms.saturating_sub(origin),
+                opts.time_precision,
+            ),
+            _ => self.format_date_at(opts, log_obj.timestamp_ms),
         }
     }
 
+    /// Formats `ms` (milliseconds since epoch) as 24-hour local time (`HH:MM:SS`).
+    pub(crate) fn format_clock_at(&self, ms: i64) -> String {
+        let (h, m, s) = local_hms(ms);
+        format!("{:02}:{:02}:{:02}", h, m, s)
+    }
+
     /// Filters out empty parts and joins the remainder with spaces.
     pub fn filter_and_join(&self, parts: &[String]) -> String {
+        self.filter_and_join_with(parts, " ")
+    }
+
+    /// Filters out empty parts and joins the remainder with `separator`.
+    pub(crate) fn filter_and_join_with(&self, parts: &[String], separator: &str) -> String {
         parts
             .iter()
             .filter(|p| !p.is_empty())
             .fold(String::new(), |mut acc, p| {
                 if !acc.is_empty() {
-                    acc.push(' ');
+                    acc.push_str(separator);
                 }
                 acc.push_str(p);
                 acc
             })
     }
 
+    /// Appends the log object's error chain to `message` as `message: cause: cause`
+    /// when `opts.inline_error_chain` is set; otherwise returns `message` unchanged.
+    pub fn with_inline_error(
+        &self,
+        message: String,
+        log_obj: &LogObject,
+        opts: &FormatOptions,
+    ) -> String {
+        let chain = match &log_obj.error {
+            Some(err) if opts.inline_error_chain => err.inline_chain(opts.error_level),
+            _ => return message,
+        };
+        match (message.is_empty(), chain.is_empty()) {
+            (_, true) => message,
+            (true, false) => chain,
+            (false, false) => format!("{}: {}", message, chain),
+        }
+    }
+
     /// Formats a `LogObject` into a plain-text string based on the given format options.
     pub fn format_log_obj(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
-        let message = self.format_args(&log_obj.args, opts);
+        self.format_named(log_obj, opts, None)
+    }
+
+    /// Like [`format_log_obj`](Self::format_log_obj), with ANSI colors on or
+    /// off as `colors` says regardless of [`FormatOptions::colors`]. Colors
+    /// use the 16 basic ones when [`FormatOptions::color_depth`] is
+    /// [`ColorDepth::None`](crate::util::color::ColorDepth::None).
+    pub fn render(&self, log_obj: &LogObject, opts: &FormatOptions, colors: bool) -> String {
+        // A detected depth of `None` would still render plain text, so
+        // asking for colors falls back to the 16 basic ones.
+        let color_depth = if colors && opts.color_depth == crate::util::color::ColorDepth::None {
+            crate::util::color::ColorDepth::Ansi16
+        } else {
+            opts.color_depth
+        };
+        if opts.colors == colors && opts.color_depth == color_depth {
+            return self.format_log_obj(log_obj, opts);
+        }
+        let opts = FormatOptions {
+            colors,
+            color_depth,
+            ..opts.clone()
+        };
+        self.format_log_obj(log_obj, &opts)
+    }
+
+    /// Like [`format_log_obj`](Self::format_log_obj), opening the line with
+    /// the logger `name` so that aligned and wrapped lines account for it.
+    fn format_named(
+        &self,
+        log_obj: &LogObject,
+        opts: &FormatOptions,
+        name: Option<&str>,
+    ) -> String {
+        let opts = if self.compact && !opts.compact {
+            Cow::Owned(FormatOptions {
+                compact: true,
+                ..opts.clone()
+            })
+        } else {
+            Cow::Borrowed(opts)
+        };
+        let effective = opts.effective();
+        let opts = &*effective;
 
         if log_obj.r#type == crate::constants::LogType::Box {
+            let message = self.format_message(log_obj, opts);
             let mut lines: Vec<String> = Vec::new();
             lines.push(String::new());
             let tag = bracket(&log_obj.tag);
@@ -193,21 +338,89 @@ impl BasicReporter {
                 lines.push(format!(" > {}", line));
             }
             lines.push(String::new());
-            return lines.join("\n");
+            let text = lines.join("\n");
+            return match name {
+                Some(name) => prefix_first_line(&text, &format!("{} ", name)),
+                None => text,
+            };
         }
 
-        let base = self.filter_and_join(&[
-            bracket(log_obj.r#type.as_str()),
-            bracket(&log_obj.tag),
-            message,
-        ]);
+        let mut prefix: Vec<String> = name.map(str::to_string).into_iter().collect();
+        let mut line = Vec::new();
+        let mut additional = None;
+        let mut fields = None;
+        let mut stack = None;
+        for segment in build_basic_segments(log_obj, opts) {
+            match segment.kind {
+                SegmentKind::TypeBadge if opts.colors => prefix.push(paint_always(
+                    type_color_name(log_obj.r#type, log_obj.level),
+                    &bracket(&opts.type_case.apply(&segment.text)),
+                    opts.color_depth,
+                )),
+                SegmentKind::TypeBadge => {
+                    prefix.push(bracket(&opts.type_case.apply(&segment.text)))
+                }
+                SegmentKind::Tag => prefix.push(bracket(&segment.text)),
+                SegmentKind::Prefix if opts.colors => prefix.push(paint_always(
+                    "cyan",
+                    &format!("{}:", segment.text),
+                    opts.color_depth,
+                )),
+                SegmentKind::Prefix => prefix.push(format!("{}:", segment.text)),
+                SegmentKind::TraceId if opts.colors => prefix.push(paint_always(
+                    "magenta",
+                    &format!("#{}", segment.text),
+                    opts.color_depth,
+                )),
+                SegmentKind::TraceId => prefix.push(format!("#{}", segment.text)),
+                SegmentKind::Additional => additional = Some(segment.text),
+                SegmentKind::Fields => fields = Some(segment.text),
+                SegmentKind::Stack => stack = Some(segment.text),
+                _ => line.push(segment.text),
+            }
+        }
+        let separator = opts.separator.as_str();
+        let prefix = self.filter_and_join_with(&prefix, separator);
+        let columns = opts.columns.unwrap_or(0) as usize;
+        let wrap = opts.wrap && columns > 0 && opts.truncate.is_none();
+        let mut message = self.filter_and_join_with(&line, separator);
+        if let Some(additional) = additional {
+            // When wrapping, `wrap_prefixed` pads the lines itself.
+            let additional = if opts.align_multiline && !wrap && !prefix.is_empty() {
+                let width = string_width(&prefix) + string_width(separator);
+                indent_lines(&additional, &" ".repeat(width))
+            } else {
+                additional
+            };
+            message.push('\n');
+            message.push_str(&additional);
+        }
+
+        let base = if wrap {
+            let indent = opts.wrap_indent || opts.align_multiline;
+            wrap_prefixed(&prefix, &message, separator, columns, indent).join("\n")
+        } else {
+            self.filter_and_join_with(&[prefix, message], separator)
+        };
+        let base = match fields {
+            Some(fields) => format!("{}\n{}", base, fields),
+            None => base,
+        };
+        let base = match opts.truncate {
+            Some(max) => truncate_lines(&base, max),
+            None => base,
+        };
 
         // Append error info if present
-        if let Some(err) = &log_obj.error {
-            let error_text = Self::format_error(err, opts, 0);
-            format!("{}\n{}", base, error_text)
+        let text = match stack {
+            Some(stack) => format!("{}\n{}", base, stack),
+            None => base,
+        };
+
+        if opts.mark_continuations {
+            mark_continuation_lines(&text, &format!("{} | ", log_obj.r#type.as_str()))
         } else {
-            base
+            text
         }
     }
 }
@@ -218,12 +431,20 @@ impl Reporter for BasicReporter {
         log_obj: &LogObject,
         ctx: &LogContext,
     ) -> Result<String, crate::error::ConsolaError> {
+        if log_obj.group_event == Some(GroupEvent::End) {
+            return Ok(String::new());
+        }
         let opts = &ctx.options.format_options;
-        Ok(self.format_log_obj(log_obj, opts))
+        let mut formatted = self.format_named(log_obj, opts, ctx.options.name.as_deref());
+        if opts.coalesce_styles {
+            formatted = coalesce_styles(&formatted);
+        }
+        let indent = " ".repeat(opts.group_indent * log_obj.group_depth);
+        Ok(indent_lines(&formatted, &indent))
     }
 
     fn clone_box(&self) -> Box<dyn Reporter> {
-        Box::new(Self)
+        Box::new(self.clone())
     }
 }
 
@@ -231,7 +452,8 @@ impl Reporter for BasicReporter {
 mod tests {
     use super::*;
     use crate::constants::LogType;
-    use crate::types::ConsolaOptions;
+    use crate::types::{ConsolaOptions, TypeCase};
+    use crate::util::color::ColorDepth;
     use std::sync::Arc;
 
     fn make_ctx() -> LogContext {
@@ -254,20 +476,175 @@ mod tests {
             icon: None,
             style: None,
             error: None,
+            group_depth: 0,
+            group_event: None,
+            fields: Vec::new(),
+            repeated: 0,
+            trace_id: None,
+            prefix: None,
+            extensions: Default::default(),
         }
     }
 
+    #[test]
+    fn test_coalesce_styles_reduces_resets() {
+        let format = |coalesce_styles| {
+            let ctx = LogContext {
+                options: Arc::new(ConsolaOptions {
+                    format_options: FormatOptions {
+                        colors: true,
+                        color_depth: ColorDepth::Ansi16,
+                        date: false,
+                        coalesce_styles,
+                        ..FormatOptions::default()
+                    },
+                    ..ConsolaOptions::default()
+                }),
+            };
+            let obj = make_log_obj(LogType::Info, &["\x1b[36mready\x1b[0m\x1b[36m!\x1b[0m"], "");
+            BasicReporter::new().format(&obj, &ctx).unwrap()
+        };
+        let (plain, merged) = (format(false), format(true));
+        assert_eq!(merged, "\x1b[36m[info]\x1b[0m \x1b[36mready!\x1b[0m");
+        assert!(merged.matches("\x1b[0m").count() < plain.matches("\x1b[0m").count());
+        assert_eq!(
+            crate::util::strip_ansi(&merged),
+            crate::util::strip_ansi(&plain)
+        );
+    }
+
+    #[test]
+    fn test_badge_colored_by_type_snapshot() {
+        let opts = FormatOptions {
+            colors: true,
+            color_depth: ColorDepth::Ansi16,
+            date: false,
+            ..FormatOptions::default()
+        };
+        let format =
+            |ty| BasicReporter::new().format_log_obj(&make_log_obj(ty, &["done"], "ci"), &opts);
+        assert_eq!(
+            format(LogType::Success),
+            "\x1b[32m[success]\x1b[0m [ci] done"
+        );
+        assert_eq!(format(LogType::Error), "\x1b[31m[error]\x1b[0m [ci] done");
+        assert_eq!(format(LogType::Warn), "\x1b[33m[warn]\x1b[0m [ci] done");
+        assert_eq!(format(LogType::Info), "\x1b[36m[info]\x1b[0m [ci] done");
+        assert_eq!(format(LogType::Log), "\x1b[90m[log]\x1b[0m [ci] done");
+    }
+
+    #[test]
+    fn test_badge_plain_without_colors() {
+        let obj = make_log_obj(LogType::Success, &["done"], "");
+        let out = BasicReporter::new().format_log_obj(&obj, &FormatOptions::default());
+        assert_eq!(out, "[success] done");
+    }
+
+    #[test]
+    fn test_render_overrides_colors_option() {
+        let obj = make_log_obj(LogType::Success, &["done"], "");
+        for colors in [false, true] {
+            let opts = FormatOptions {
+                colors,
+                color_depth: ColorDepth::Ansi16,
+                ..FormatOptions::default()
+            };
+            assert_eq!(
+                BasicReporter::new().render(&obj, &opts, true),
+                "\x1b[32m[success]\x1b[0m done"
+            );
+            assert_eq!(
+                BasicReporter::new().render(&obj, &opts, false),
+                "[success] done"
+            );
+            assert_eq!(opts.colors, colors);
+        }
+    }
+
+    #[test]
+    fn test_render_colors_without_detected_color_depth() {
+        let obj = make_log_obj(LogType::Success, &["done"], "");
+        let opts = FormatOptions {
+            color_depth: ColorDepth::None,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            BasicReporter::new().render(&obj, &opts, true),
+            "\x1b[32m[success]\x1b[0m done"
+        );
+        assert_eq!(
+            BasicReporter::new().render(&obj, &opts, false),
+            "[success] done"
+        );
+    }
+
+    #[test]
+    fn test_format_compact_snapshot() {
+        let mut obj = make_log_obj(
+            LogType::Error,
+            &["request  failed", "\nretrying\nlater"],
+            "http",
+        );
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            stack: Some("at fetch (src/http.rs:10:5)".into()),
+            cause: Some(Box::new(ErrorInfo {
+                message: "connection reset".into(),
+                ..ErrorInfo::default()
+            })),
+            ..ErrorInfo::default()
+        });
+        let format = |compact: bool| {
+            BasicReporter::new().format_log_obj(
+                &obj,
+                &FormatOptions {
+                    compact,
+                    ..FormatOptions::default()
+                },
+            )
+        };
+        assert_eq!(
+            format(false),
+            "[error] [http] request  failed\nretrying\nlater\ntimeout\n\n    at fetch (src/http.rs:10:5)\n\n  [cause]: connection reset"
+        );
+        assert_eq!(
+            format(true),
+            "[error] [http] request failed retrying later: timeout: connection reset"
+        );
+    }
+
     #[test]
     fn test_new_and_default() {
         let r = BasicReporter::new();
-        let d = BasicReporter;
-        assert_eq!(format!("{:?}", r), "BasicReporter");
-        assert_eq!(format!("{:?}", d), "BasicReporter");
+        let d = BasicReporter::default();
+        assert_eq!(format!("{:?}", r), "BasicReporter { compact: false }");
+        assert_eq!(format!("{:?}", d), "BasicReporter { compact: false }");
+    }
+
+    #[test]
+    fn test_for_ci_writes_one_line_per_entry() {
+        let mut obj = make_log_obj(LogType::Error, &["request failed", "\nretrying"], "http");
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            stack: Some("at fetch (src/http.rs:10:5)".into()),
+            ..Default::default()
+        });
+        let ctx = make_ctx();
+        assert!(
+            BasicReporter::new()
+                .format(&obj, &ctx)
+                .unwrap()
+                .contains('\n')
+        );
+        assert_eq!(
+            BasicReporter::for_ci().format(&obj, &ctx).unwrap(),
+            "[error] [http] request failed retrying: timeout"
+        );
     }
 
     #[test]
     fn test_format_plain() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &["hello", "world"], "");
         let result = r.format(&obj, &ctx).unwrap();
@@ -276,7 +653,7 @@ mod tests {
 
     #[test]
     fn test_format_with_tag() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &["hello"], "mytag");
         let result = r.format(&obj, &ctx).unwrap();
@@ -285,7 +662,7 @@ mod tests {
 
     #[test]
     fn test_format_box_no_title() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Box, &["hello"], "");
         let result = r.format(&obj, &ctx).unwrap();
@@ -294,7 +671,7 @@ mod tests {
 
     #[test]
     fn test_format_box_with_tag() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Box, &["hello"], "mytag");
         let result = r.format(&obj, &ctx).unwrap();
@@ -303,7 +680,7 @@ mod tests {
 
     #[test]
     fn test_format_box_with_title() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let mut obj = make_log_obj(LogType::Box, &["hello"], "");
         obj.title = Some("MyTitle".into());
@@ -313,7 +690,7 @@ mod tests {
 
     #[test]
     fn test_format_with_error() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let mut obj = make_log_obj(LogType::Error, &["an error occurred"], "");
         obj.error = Some(ErrorInfo {
@@ -336,7 +713,7 @@ mod tests {
 
     #[test]
     fn test_format_various_types() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let types = [
             LogType::Silent,
@@ -362,16 +739,28 @@ mod tests {
 
     #[test]
     fn test_format_empty_args() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &[], "");
         let result = r.format(&obj, &ctx).unwrap();
         assert_eq!(result, "[info]");
     }
 
+    #[test]
+    fn test_format_fields_on_first_line() {
+        let ctx = make_ctx();
+        let mut obj = make_log_obj(LogType::Info, &["started", "\ndetails"], "");
+        obj.fields = vec![
+            ("port".into(), "8080".into()),
+            ("tls".into(), "true".into()),
+        ];
+        let result = BasicReporter::new().format(&obj, &ctx).unwrap();
+        assert_eq!(result, "[info] started port=8080 tls=true\ndetails");
+    }
+
     #[test]
     fn test_clone_box() {
-        let r: Box<dyn Reporter> = Box::new(BasicReporter);
+        let r: Box<dyn Reporter> = Box::new(BasicReporter::new());
         let cloned = r.clone_box();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &["test"], "");
@@ -383,15 +772,132 @@ mod tests {
 
     #[test]
     fn test_format_is_ok_on_all_platforms() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &["x"], "");
         assert!(r.format(&obj, &ctx).is_ok());
     }
 
+    #[test]
+    fn test_format_clock_at_is_24_hour() {
+        let clock = BasicReporter::new().format_clock_at(0);
+        let parts: Vec<u32> = clock.split(':').map(|p| p.parse().unwrap()).collect();
+        assert_eq!(clock.len(), 8);
+        assert!(parts[0] < 24 && parts[1] < 60 && parts[2] < 60);
+    }
+
+    #[test]
+    fn test_custom_separator_in_line_and_wrapping() {
+        let mut obj = make_log_obj(LogType::Warn, &["disk almost full on volume"], "db");
+        obj.fields = vec![("pct".into(), "95".into())];
+        let opts = FormatOptions {
+            separator: " | ".into(),
+            date: false,
+            columns: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            BasicReporter::new().format_log_obj(&obj, &opts),
+            "[warn] | [db] | disk almost full on volume | pct=95"
+        );
+
+        // The continuation indent covers the separator after the prefix.
+        let wrapped = FormatOptions {
+            wrap: true,
+            wrap_indent: true,
+            columns: Some(30),
+            ..opts
+        };
+        assert_eq!(
+            BasicReporter::new().format_log_obj(&obj, &wrapped),
+            "[warn] | [db] | disk almost\n                full on volume\n                | pct=95"
+        );
+    }
+
+    #[test]
+    fn test_format_entry_time_modes() {
+        let mut obj = make_log_obj(LogType::Info, &["x"], "");
+        obj.timestamp_ms = 61_250;
+        let relative = |origin| FormatOptions {
+            time_mode: TimeMode::Relative,
+            time_origin_ms: origin,
+            ..Default::default()
+        };
+        assert_eq!(
+            BasicReporter::new().format_entry_time(&obj, &relative(Some(60_000))),
+            "+1.250s"
+        );
+        assert_eq!(
+            BasicReporter::new().format_entry_time(&obj, &relative(Some(0))),
+            "+1m01.250s"
+        );
+        assert_eq!(
+            BasicReporter::new().format_entry_time(&obj, &relative(Some(70_000))),
+            "+0.000s"
+        );
+        assert_eq!(
+            BasicReporter::new().format_entry_time(&obj, &relative(None)),
+            BasicReporter::new().format_date_at(&FormatOptions::default(), 61_250)
+        );
+        let none = FormatOptions {
+            time_mode: TimeMode::None,
+            ..Default::default()
+        };
+        assert_eq!(BasicReporter::new().format_entry_time(&obj, &none), "");
+    }
+
+    #[test]
+    fn test_time_precision_sets_fractional_digits() {
+        let mut obj = make_log_obj(LogType::Info, &["x"], "");
+        obj.timestamp_ms = 61_250;
+        let digits = |time_mode, time_precision| {
+            let opts = FormatOptions {
+                time_mode,
+                time_origin_ms: Some(0),
+                time_precision,
+                ..Default::default()
+            };
+            let time = BasicReporter::new().format_entry_time(&obj, &opts);
+            let time = time.trim_end_matches(" AM").trim_end_matches(" PM");
+            let time = time.trim_end_matches('s');
+            time.split_once('.')
+                .map_or(0, |(_, fraction)| fraction.len())
+        };
+        for (precision, absolute, relative) in [
+            (TimePrecision::AsIs, 0, 3),
+            (TimePrecision::Seconds, 0, 0),
+            (TimePrecision::Millis, 3, 3),
+            (TimePrecision::Micros, 6, 6),
+            (TimePrecision::Nanos, 9, 9),
+        ] {
+            assert_eq!(
+                digits(TimeMode::Absolute, precision),
+                absolute,
+                "{:?}",
+                precision
+            );
+            assert_eq!(
+                digits(TimeMode::Relative, precision),
+                relative,
+                "{:?}",
+                precision
+            );
+        }
+        let micros = FormatOptions {
+            time_mode: TimeMode::Relative,
+            time_origin_ms: Some(0),
+            time_precision: TimePrecision::Micros,
+            ..Default::default()
+        };
+        assert_eq!(
+            BasicReporter::new().format_entry_time(&obj, &micros),
+            "+1m01.250000s"
+        );
+    }
+
     #[test]
     fn test_format_date_at_midnight() {
-        let r = BasicReporter;
+        let r = BasicReporter::new();
         let opts = FormatOptions {
             date: true,
             ..Default::default()
@@ -405,4 +911,149 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_format_wraps_with_indent() {
+        let r = BasicReporter::new();
+        let opts = FormatOptions {
+            columns: Some(20),
+            wrap: true,
+            wrap_indent: true,
+            ..Default::default()
+        };
+        let obj = make_log_obj(LogType::Info, &["one two three four five six"], "");
+        assert_eq!(
+            r.format_log_obj(&obj, &opts),
+            "[info] one two three\n       four five six"
+        );
+    }
+
+    #[test]
+    fn test_format_wraps_long_word() {
+        let r = BasicReporter::new();
+        let opts = FormatOptions {
+            columns: Some(8),
+            wrap: true,
+            ..Default::default()
+        };
+        let obj = make_log_obj(LogType::Log, &["abcdefghijkl"], "");
+        assert_eq!(r.format_log_obj(&obj, &opts), "[log]\nabcdefgh\nijkl");
+    }
+
+    fn three_deep_error() -> ErrorInfo {
+        ErrorInfo {
+            message: "a".into(),
+            stack: Some("at main (src/main.rs:1:1)".into()),
+            backtrace: None,
+            cause: Some(Box::new(ErrorInfo {
+                message: "b".into(),
+                cause: Some(Box::new(ErrorInfo {
+                    message: "c".into(),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            })),
+        }
+    }
+
+    #[test]
+    fn test_format_inline_error_chain() {
+        let r = BasicReporter::new();
+        let opts = FormatOptions {
+            inline_error_chain: true,
+            ..Default::default()
+        };
+        let mut obj = make_log_obj(LogType::Error, &["failed"], "");
+        obj.error = Some(three_deep_error());
+        assert_eq!(r.format_log_obj(&obj, &opts), "[error] failed: a: b: c");
+
+        let capped = FormatOptions {
+            error_level: 1,
+            ..opts
+        };
+        assert_eq!(r.format_log_obj(&obj, &capped), "[error] failed: a: b");
+    }
+
+    #[test]
+    fn test_format_error_chain_multiline_by_default() {
+        let r = BasicReporter::new();
+        let mut obj = make_log_obj(LogType::Error, &["failed"], "");
+        obj.error = Some(three_deep_error());
+        let result = r.format_log_obj(&obj, &FormatOptions::default());
+        assert!(result.starts_with("[error] failed\na\n"));
+        assert!(result.contains("[cause]: b"));
+        assert!(result.contains("[cause]: c"));
+    }
+
+    #[test]
+    fn test_error_layout_orders_stack_and_chain() {
+        let err = ErrorInfo {
+            message: "a".into(),
+            stack: Some("at main (src/main.rs:1:1)".into()),
+            backtrace: None,
+            cause: Some(Box::new(ErrorInfo {
+                message: "b".into(),
+                stack: Some("at io (src/io.rs:2:2)".into()),
+                ..Default::default()
+            })),
+        };
+        let render = |layout| {
+            let opts = FormatOptions {
+                error_layout: layout,
+                ..Default::default()
+            };
+            BasicReporter::format_error(&err, &opts, 0)
+        };
+
+        assert_eq!(
+            render(ErrorLayout::StackThenChain),
+            "a\n\n    at main (src/main.rs:1:1)\n\n  [cause]: b\n\n      at io (src/io.rs:2:2)"
+        );
+        assert_eq!(
+            render(ErrorLayout::ChainThenStack),
+            "a\n\n  [cause]: b\n\n    at main (src/main.rs:1:1)\n\n      at io (src/io.rs:2:2)"
+        );
+        assert_eq!(
+            render(ErrorLayout::StackOnly),
+            "a\n\n    at main (src/main.rs:1:1)"
+        );
+        assert_eq!(render(ErrorLayout::ChainOnly), "a\n\n  [cause]: b");
+    }
+
+    #[test]
+    fn test_format_mark_continuations() {
+        let r = BasicReporter::new();
+        let opts = FormatOptions {
+            mark_continuations: true,
+            ..Default::default()
+        };
+        let mut obj = make_log_obj(LogType::Error, &["failed"], "");
+        obj.error = Some(three_deep_error());
+        let result = r.format_log_obj(&obj, &opts);
+        let lines: Vec<&str> = result.lines().filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines[0], "[error] failed");
+        assert!(lines.len() > 3);
+        for line in &lines[1..] {
+            assert!(line.starts_with("error | "), "unmarked line: {:?}", line);
+        }
+        assert!(lines.contains(&"error |   [cause]: b"));
+    }
+
+    #[test]
+    fn test_type_case_applies_to_badge() {
+        let r = BasicReporter::new();
+        let obj = make_log_obj(LogType::Success, &["done"], "build");
+        for (case, expected) in [
+            (TypeCase::AsIs, "[success] [build] done"),
+            (TypeCase::Lower, "[success] [build] done"),
+            (TypeCase::Upper, "[SUCCESS] [build] done"),
+            (TypeCase::Title, "[Success] [build] done"),
+        ] {
+            let opts = FormatOptions {
+                type_case: case,
+                ..Default::default()
+            };
+            assert_eq!(r.format_log_obj(&obj, &opts), expected, "{:?}", case);
+        }
+    }
 }
@@ -6,7 +6,7 @@
 //! Otherwise, falls back to plain text formatting.
 
 use crate::constants::{LogLevel, LogType};
-use crate::types::{FormatOptions, LogContext, LogObject, Reporter};
+use crate::types::{FormatOptions, LogContext, LogObject, Reporter, TypeCase};
 
 /// Runtime browser detection: returns `true` when the current environment is
 /// a browser with a DOM `window` object. This works on `wasm32` targets
@@ -114,8 +114,9 @@ impl BrowserReporter {
     /// Emit a styled badge + message to the browser console.
     #[cfg(all(target_arch = "wasm32", feature = "browser"))]
     fn emit_browser_styled(&self, log_obj: &LogObject) {
-        let badge_text = self.badge_text(log_obj);
-        let msg = log_obj.args.join(" ");
+        let badge_text = self.badge_text(log_obj, TypeCase::AsIs);
+        let msg = crate::reporters::basic::BasicReporter::new()
+            .format_message(log_obj, &FormatOptions::default());
 
         if badge_text.is_empty() {
             Self::console_fn_plain(log_obj.level, &msg);
@@ -132,11 +133,11 @@ impl BrowserReporter {
     }
 
     /// Build the badge text (tag:type) matching consola-js.
-    fn badge_text(&self, log_obj: &LogObject) -> String {
+    fn badge_text(&self, log_obj: &LogObject, case: TypeCase) -> String {
         let type_str = if log_obj.r#type == LogType::Log {
             String::new()
         } else {
-            log_obj.r#type.as_str().to_string()
+            case.apply(log_obj.r#type.as_str())
         };
         let tag = log_obj.tag.clone();
 
@@ -147,14 +148,15 @@ impl BrowserReporter {
             .join(":")
     }
 
-    fn fmt_log(&self, log_obj: &LogObject) -> (String, String) {
-        let badge = self.badge_text(log_obj);
-        let msg = log_obj.args.join(" ");
+    fn fmt_log(&self, log_obj: &LogObject, case: TypeCase) -> (String, String) {
+        let badge = self.badge_text(log_obj, case);
+        let msg = crate::reporters::basic::BasicReporter::new()
+            .format_message(log_obj, &FormatOptions::default());
         (badge, msg)
     }
 
-    fn format_log_obj(&self, log_obj: &LogObject, _opts: &FormatOptions) -> String {
-        let (badge, msg) = self.fmt_log(log_obj);
+    fn format_log_obj(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
+        let (badge, msg) = self.fmt_log(log_obj, opts.type_case);
         if badge.is_empty() {
             msg
         } else {
@@ -212,6 +214,13 @@ mod tests {
             icon: None,
             style: None,
             error: None,
+            group_depth: 0,
+            group_event: None,
+            fields: Vec::new(),
+            repeated: 0,
+            trace_id: None,
+            prefix: None,
+            extensions: Default::default(),
         }
     }
 
@@ -333,16 +342,16 @@ mod tests {
     fn test_badge_text() {
         let r = BrowserReporter::new();
         let obj = make_log_obj(LogType::Info, &["msg"], "tag", 3);
-        assert_eq!(r.badge_text(&obj), "tag:info");
+        assert_eq!(r.badge_text(&obj, TypeCase::AsIs), "tag:info");
 
         let obj = make_log_obj(LogType::Info, &["msg"], "", 3);
-        assert_eq!(r.badge_text(&obj), "info");
+        assert_eq!(r.badge_text(&obj, TypeCase::AsIs), "info");
 
         let obj = make_log_obj(LogType::Log, &["msg"], "", 2);
-        assert_eq!(r.badge_text(&obj), "");
+        assert_eq!(r.badge_text(&obj, TypeCase::AsIs), "");
 
         let obj = make_log_obj(LogType::Log, &["msg"], "tag", 2);
-        assert_eq!(r.badge_text(&obj), "tag");
+        assert_eq!(r.badge_text(&obj, TypeCase::AsIs), "tag");
     }
 
     #[test]
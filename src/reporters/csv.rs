@@ -0,0 +1,208 @@
+//! CsvReporter — renders log entries as CSV rows under a fixed header.
+
+use std::sync::{Arc, Weak};
+
+use crate::error::ConsolaError;
+use crate::reporters::json::{json_object, json_string};
+use crate::reporters::logfmt::rfc3339_utc;
+use crate::sync::Mutex;
+use crate::types::{GroupEvent, LogContext, LogObject, Reporter};
+use crate::util::sink::Sink;
+
+/// Column names, in order.
+const HEADER: &str = "time,level,type,tag,message,repeat,fields";
+
+/// Quote `value` per RFC 4180 if it contains a comma, quote, or line break.
+fn csv_value(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats log entries as CSV for spreadsheets and quick analysis.
+///
+/// The first entry written to each sink is preceded by the header row
+/// `time,level,type,tag,message,repeat,fields`, so with `stderr_level`
+/// splitting entries across `out_sink` and `err_sink` both get one. Clones
+/// share the record of which sinks already have it, so the header appears
+/// once per sink however many loggers the reporter is installed in. `time`
+/// is RFC 3339 UTC, `message` holds the args with any error chain appended
+/// as `: cause`, `repeat` is the number of throttled duplicates the row
+/// stands for, and `fields` is the entry's fields as one JSON object so the
+/// schema stays fixed. Group ends produce no output.
+#[derive(Debug, Clone, Default)]
+pub struct CsvReporter {
+    /// Sinks that already received the header, held weakly so a dropped
+    /// sink is forgotten rather than matched by a new one at its address.
+    headed_sinks: Arc<Mutex<Vec<Weak<dyn Sink>>>>,
+}
+
+impl CsvReporter {
+    /// Creates a new `CsvReporter` that writes the header before its first row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render a log entry as a CSV row, without the header.
+    pub fn format_row(&self, log_obj: &LogObject) -> String {
+        let mut args: &[String] = &log_obj.args;
+        if log_obj.repeated > 1 {
+            // Drop the "(repeated N times)" suffix in favor of the repeat column.
+            args = &args[..args.len().saturating_sub(1)];
+        }
+        let mut message = args.join(" ");
+        if let Some(chain) = log_obj
+            .error
+            .as_ref()
+            .map(|err| err.inline_chain(0))
+            .filter(|chain| !chain.is_empty())
+        {
+            if !message.is_empty() {
+                message.push_str(": ");
+            }
+            message.push_str(&chain);
+        }
+        let fields: Vec<(&str, String)> = log_obj
+            .fields
+            .iter()
+            .map(|(k, v)| (k.as_str(), json_string(v)))
+            .collect();
+
+        [
+            rfc3339_utc(log_obj.timestamp_ms),
+            log_obj.level.to_string(),
+            log_obj.r#type.as_str().to_string(),
+            log_obj.tag.clone(),
+            message,
+            log_obj.repeated.max(1).to_string(),
+            json_object(&fields),
+        ]
+        .iter()
+        .map(|v| csv_value(v))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+impl Reporter for CsvReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        if log_obj.group_event == Some(GroupEvent::End) {
+            return Ok(String::new());
+        }
+        let row = self.format_row(log_obj);
+        let opts = &ctx.options;
+        let sink = if opts.writes_to_err_sink(log_obj.level) {
+            &opts.err_sink
+        } else {
+            &opts.out_sink
+        };
+        let sink = Arc::downgrade(sink);
+        let mut headed = self.headed_sinks.lock();
+        headed.retain(|s| s.strong_count() > 0);
+        if headed.iter().any(|s| s.ptr_eq(&sink)) {
+            Ok(row)
+        } else {
+            headed.push(sink);
+            Ok(format!("{}\n{}", HEADER, row))
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::{ConsolaOptions, ErrorInfo};
+    use crate::util::sink::TestSink;
+
+    fn make_ctx() -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        }
+    }
+
+    fn make_obj(ty: LogType, args: &[&str]) -> LogObject {
+        let mut obj = LogObject::new(ty);
+        obj.timestamp_ms = 1_704_164_645_678;
+        obj.args = args.iter().map(|a| a.to_string()).collect();
+        obj
+    }
+
+    #[test]
+    fn test_value_quoting() {
+        assert_eq!(csv_value("plain text"), "plain text");
+        assert_eq!(csv_value("a,b"), "\"a,b\"");
+        assert_eq!(csv_value("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_value("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_header_written_once() {
+        let reporter = CsvReporter::new();
+        let clone = reporter.clone();
+        let ctx = make_ctx();
+        let first = reporter
+            .format(&make_obj(LogType::Info, &["one"]), &ctx)
+            .unwrap();
+        let second = clone
+            .format(&make_obj(LogType::Info, &["two"]), &ctx)
+            .unwrap();
+        assert_eq!(
+            first,
+            "time,level,type,tag,message,repeat,fields\n\
+             2024-01-02T03:04:05.678Z,3,info,,one,1,{}"
+        );
+        assert_eq!(second, "2024-01-02T03:04:05.678Z,3,info,,two,1,{}");
+    }
+
+    #[test]
+    fn test_header_written_once_per_sink() {
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                out_sink: Arc::new(TestSink::new()),
+                err_sink: Arc::new(TestSink::new()),
+                ..ConsolaOptions::default()
+            }),
+        };
+        let reporter = CsvReporter::new();
+        let rows: Vec<String> = [LogType::Info, LogType::Warn, LogType::Info, LogType::Error]
+            .into_iter()
+            .map(|ty| reporter.format(&make_obj(ty, &["x"]), &ctx).unwrap())
+            .collect();
+        assert!(rows[0].starts_with(HEADER));
+        assert!(rows[1].starts_with(HEADER));
+        assert!(!rows[2].starts_with(HEADER));
+        assert!(!rows[3].starts_with(HEADER));
+    }
+
+    #[test]
+    fn test_row_columns() {
+        let mut obj = make_obj(LogType::Warn, &["disk, almost full", "(repeated 3 times)"]);
+        obj.level = 1;
+        obj.tag = "storage".into();
+        obj.repeated = 3;
+        obj.fields = vec![("mount".into(), "/var".into())];
+        obj.error = Some(ErrorInfo {
+            message: "write failed".into(),
+            ..ErrorInfo::default()
+        });
+        assert_eq!(
+            CsvReporter::new().format_row(&obj),
+            "2024-01-02T03:04:05.678Z,1,warn,storage,\"disk, almost full: write failed\",3,\
+             \"{\"\"mount\"\":\"\"/var\"\"}\""
+        );
+    }
+
+    #[test]
+    fn test_group_end_produces_no_output() {
+        let mut obj = make_obj(LogType::Log, &["build"]);
+        obj.group_event = Some(GroupEvent::End);
+        assert_eq!(CsvReporter::new().format(&obj, &make_ctx()).unwrap(), "");
+    }
+}
@@ -1,12 +1,21 @@
 //! FancyReporter — pure formatter — no I/O. Colors via `anstyle` crate.
 
+use std::borrow::Cow;
 use std::sync::LazyLock;
 
-use crate::constants::{LogLevel, LogType};
-use crate::types::{ErrorInfo, FormatOptions, LogContext, LogObject, Reporter};
-use crate::util::boxes::{BoxOpts, box_text};
-use crate::util::color::{self, get_color};
-use crate::util::string::string_width;
+use crate::constants::{LogLevel, LogType, custom_type_def};
+use crate::reporters::segment::{SegmentKind, build_basic_segments, fields_block};
+use crate::types::format::env_color_override;
+use crate::types::{
+    ErrorInfo, ErrorLayout, FormatOptions, GroupEvent, LogContext, LogObject, Reporter, TypeCase,
+};
+use crate::util::boxes::{BoxOpts, BoxStyle, box_text};
+use crate::util::color::{self, ColorDepth, get_color, paint_always};
+use crate::util::console::{CP_UTF8, console_code_page};
+use crate::util::string::{
+    coalesce_styles, indent_lines, mark_continuation_lines, prefix_first_line, string_width,
+    strip_ansi, truncate_display, truncate_lines, wrap_prefixed,
+};
 
 const TYPE_COLOR_MAP: &[(LogType, &str)] = &[
     (LogType::Info, "cyan"),
@@ -33,16 +42,44 @@ const TYPE_ICONS: &[(LogType, &str, &str)] = &[
 
 fn unicode_supported() -> bool {
     static CACHED: LazyLock<bool> = LazyLock::new(|| {
-        let term = std::env::var("TERM").unwrap_or_default();
-        let lang = std::env::var("LANG").unwrap_or_default();
-        !(cfg!(windows) && (term == "MINGW" || term.contains("cygwin")))
-            || lang.contains("UTF-8")
-            || lang.contains("utf8")
+        detect_unicode(
+            cfg!(windows),
+            &std::env::var("TERM").unwrap_or_default(),
+            &std::env::var("LANG").unwrap_or_default(),
+            console_code_page(),
+        )
     });
     *CACHED
 }
 
+/// Whether CI output should be styled: only when `FORCE_COLOR` asks for it.
+fn ci_colors(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    env_color_override(&lookup) == Some(true)
+}
+
+/// Whether icons can use Unicode. On Windows, MinGW/Cygwin terminals and
+/// consoles whose output code page is not UTF-8 fall back to ASCII unless
+/// `LANG` declares UTF-8.
+fn detect_unicode(windows: bool, term: &str, lang: &str, code_page: Option<u32>) -> bool {
+    let legacy_term = term == "MINGW" || term.contains("cygwin");
+    let legacy_code_page = code_page.is_some_and(|cp| cp != CP_UTF8);
+    !(windows && (legacy_term || legacy_code_page))
+        || lang.contains("UTF-8")
+        || lang.contains("utf8")
+}
+
 fn icon_for(ty: LogType, unicode: bool) -> &'static str {
+    if let LogType::Custom(name) = ty {
+        return custom_type_def(name)
+            .and_then(|d| {
+                if unicode {
+                    d.icon
+                } else {
+                    d.ascii_icon.or(d.icon)
+                }
+            })
+            .unwrap_or("");
+    }
     for &(t, u, a) in TYPE_ICONS {
         if t == ty {
             return if unicode { u } else { a };
@@ -51,7 +88,14 @@ fn icon_for(ty: LogType, unicode: bool) -> &'static str {
     ""
 }
 
-fn type_color_name(ty: LogType, level: LogLevel) -> &'static str {
+/// Color of a type's badge and icon: the custom type's color, else by type,
+/// else by level, else gray.
+pub(crate) fn type_color_name(ty: LogType, level: LogLevel) -> &'static str {
+    if let LogType::Custom(name) = ty
+        && let Some(color) = custom_type_def(name).and_then(|d| d.color)
+    {
+        return color;
+    }
     for &(t, c) in TYPE_COLOR_MAP {
         if t == ty {
             return c;
@@ -65,6 +109,17 @@ fn type_color_name(ty: LogType, level: LogLevel) -> &'static str {
     "gray"
 }
 
+/// Color `text` with a type color: a color name, or a `#rrggbb` hex color
+/// downgraded to `depth`.
+fn paint_type_color(name: &str, text: &str, background: bool, depth: ColorDepth) -> String {
+    match (color::parse_hex_color(name), background) {
+        (Some(c), true) => color::paint_bg(text, c, depth),
+        (Some(c), false) => color::paint(text, c, depth),
+        (None, true) => bg_color_fn(name)(text),
+        (None, false) => get_color(name)(text),
+    }
+}
+
 fn bg_color_fn(name: &str) -> fn(&str) -> String {
     let bg_name = format!("bg_{}", name);
     let f = get_color(&bg_name);
@@ -129,6 +184,9 @@ fn character_format(text: &str) -> String {
 #[derive(Debug, Clone)]
 pub struct FancyReporter {
     unicode: bool,
+    icons: bool,
+    compact: bool,
+    colors: bool,
 }
 
 impl Default for FancyReporter {
@@ -142,29 +200,127 @@ impl FancyReporter {
     pub fn new() -> Self {
         Self {
             unicode: unicode_supported(),
+            icons: true,
+            compact: false,
+            colors: true,
+        }
+    }
+
+    /// Like [`new`](Self::new), but icons are shown only when stdout is a
+    /// terminal, since they often render poorly in files and pipes; entries
+    /// then show their type name instead. Call [`icons`](Self::icons)
+    /// afterwards to force them on or off.
+    pub fn adaptive() -> Self {
+        Self::new().terminal(crate::types::format::stdout_is_terminal())
+    }
+
+    /// Adapt to whether output goes to a terminal, returning the builder for
+    /// chaining. Icons are turned off when `is_terminal` is `false`.
+    pub fn terminal(mut self, is_terminal: bool) -> Self {
+        self.icons &= is_terminal;
+        self
+    }
+
+    /// Preset for CI logs: ASCII only, no icons, compact output, and no ANSI
+    /// styling unless `FORCE_COLOR` is set (`NO_COLOR` always wins).
+    ///
+    /// Output never contains cursor movement, so it is safe for log files and
+    /// CI viewers that render escape sequences literally.
+    pub fn for_ci() -> Self {
+        Self::new()
+            .unicode(false)
+            .icons(false)
+            .compact(true)
+            .colors(ci_colors(|key| std::env::var(key).ok()))
+    }
+
+    /// Override the detected Unicode support, returning the builder for chaining.
+    ///
+    /// When disabled, icons fall back to ASCII. See
+    /// [`enable_windows_utf8`](crate::util::enable_windows_utf8) to make
+    /// legacy Windows consoles render Unicode instead.
+    pub fn unicode(mut self, enabled: bool) -> Self {
+        self.unicode = enabled;
+        self
+    }
+
+    /// Show type icons, returning the builder for chaining.
+    ///
+    /// When disabled, entries show their type name instead.
+    pub fn icons(mut self, enabled: bool) -> Self {
+        self.icons = enabled;
+        self
+    }
+
+    /// Force compact output regardless of [`FormatOptions::compact`],
+    /// returning the builder for chaining.
+    pub fn compact(mut self, enabled: bool) -> Self {
+        self.compact = enabled;
+        self
+    }
+
+    /// Allow ANSI styling, returning the builder for chaining.
+    ///
+    /// When disabled, output is plain text even if colors are globally
+    /// enabled; when enabled, [`color_enabled`](crate::util::color_enabled)
+    /// still decides.
+    pub fn colors(mut self, enabled: bool) -> Self {
+        self.colors = enabled;
+        self
+    }
+
+    fn icon(&self, ty: LogType) -> &'static str {
+        if self.icons {
+            icon_for(ty, self.unicode)
+        } else {
+            ""
         }
     }
 
-    fn format_type(&self, log_obj: &LogObject, is_badge: bool, _opts: &FormatOptions) -> String {
+    fn format_type(&self, log_obj: &LogObject, is_badge: bool, opts: &FormatOptions) -> String {
         let color_name = type_color_name(log_obj.r#type, log_obj.level);
         if is_badge {
-            let type_str = log_obj.r#type.as_str().to_uppercase();
+            let type_str = match opts.type_case {
+                TypeCase::AsIs => log_obj.r#type.as_str().to_uppercase(),
+                case => case.apply(log_obj.r#type.as_str()),
+            };
             let badge = format!(" {} ", type_str);
-            bg_color_fn(color_name)(&color::black(&badge))
+            paint_type_color(color_name, &color::black(&badge), true, opts.color_depth)
         } else {
-            let icon_str = icon_for(log_obj.r#type, self.unicode);
+            let icon_str = self.icon(log_obj.r#type);
+            let type_name = opts.type_case.apply(log_obj.r#type.as_str());
             let display = if !icon_str.is_empty() {
                 icon_str
+            } else if self.icons {
+                log_obj.icon.as_deref().unwrap_or(&type_name)
             } else {
-                log_obj.icon.as_deref().unwrap_or(log_obj.r#type.as_str())
+                &type_name
             };
-            get_color(color_name)(display)
+            paint_type_color(color_name, display, false, opts.color_depth)
         }
     }
 
+    /// The compact `HH:MM:SS I` prefix: time in gray, type initial in the type's color.
+    fn format_time_prefix(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
+        let clock =
+            crate::reporters::basic::BasicReporter::new().format_clock_at(log_obj.timestamp_ms);
+        let initial = log_obj
+            .r#type
+            .as_str()
+            .chars()
+            .next()
+            .map(|c| c.to_uppercase().to_string())
+            .unwrap_or_default();
+        let color_name = type_color_name(log_obj.r#type, log_obj.level);
+        format!(
+            "{} {}",
+            color::gray(&clock),
+            paint_type_color(color_name, &initial, false, opts.color_depth)
+        )
+    }
+
     /// Format an error chain recursively, matching consola-js output format.
-    fn format_error(err: &ErrorInfo, _opts: &FormatOptions, level: usize) -> String {
-        let indent = "  ".repeat(level + 2);
+    pub(crate) fn format_error(err: &ErrorInfo, opts: &FormatOptions, level: usize) -> String {
         let caused_prefix = if level > 0 {
             format!("{}[cause]: {}", "  ".repeat(level), err.message)
         } else {
@@ -173,94 +329,174 @@ impl FancyReporter {
 
         let mut result = caused_prefix;
 
-        if let Some(stack) = &err.stack
-            && !stack.is_empty()
-        {
-            // Blank line before stack
-            result.push('\n');
-            // Format each line with proper indentation
-            for line in stack.lines() {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
-                }
-                // Color the "at" part
-                let formatted = if let Some(loc) = line.strip_prefix("at ") {
-                    format!("{}{}{}", indent, color::gray("at "), color::cyan(loc))
-                } else if let Some(loc) = line.strip_prefix("    at ") {
-                    format!(
-                        "{}{}{}",
-                        indent,
-                        color::gray("at "),
-                        color::cyan(loc.trim())
-                    )
-                } else if line.starts_with("  ") || line.starts_with('\t') {
-                    format!("{}{}", indent, color::cyan(line.trim()))
-                } else {
-                    format!("{}{}", indent, color::cyan(line))
-                };
-                result.push_str(&format!("\n{}", formatted));
-            }
+        if matches!(
+            opts.error_layout,
+            ErrorLayout::StackThenChain | ErrorLayout::StackOnly
+        ) {
+            Self::push_stack(&mut result, err, level);
         }
 
-        if let Some(cause) = &err.cause {
+        if let Some(cause) = &err.cause
+            && opts.error_layout != ErrorLayout::StackOnly
+        {
             result.push_str("\n\n");
-            result.push_str(&Self::format_error(cause, _opts, level + 1));
+            result.push_str(&Self::format_error(cause, opts, level + 1));
+        }
+
+        if level == 0 && opts.error_layout == ErrorLayout::ChainThenStack {
+            let chain = std::iter::successors(Some(err), |e| e.cause.as_deref());
+            for (depth, err) in chain.enumerate() {
+                Self::push_stack(&mut result, err, depth);
+            }
         }
 
         result
     }
 
+    /// Append the colored stack of `err`, if any, after a blank line.
+    fn push_stack(result: &mut String, err: &ErrorInfo, level: usize) {
+        let Some(stack) = err.stack.as_deref().filter(|s| !s.is_empty()) else {
+            return;
+        };
+        let indent = "  ".repeat(level + 2);
+        // Blank line before stack
+        result.push('\n');
+        // Format each line with proper indentation
+        for line in stack.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Color the "at" part
+            let formatted = if let Some(loc) = line.strip_prefix("at ") {
+                format!("{}{}{}", indent, color::gray("at "), color::cyan(loc))
+            } else if let Some(loc) = line.strip_prefix("    at ") {
+                format!(
+                    "{}{}{}",
+                    indent,
+                    color::gray("at "),
+                    color::cyan(loc.trim())
+                )
+            } else if line.starts_with("  ") || line.starts_with('\t') {
+                format!("{}{}", indent, color::cyan(line.trim()))
+            } else {
+                format!("{}{}", indent, color::cyan(line))
+            };
+            result.push_str(&format!("\n{}", formatted));
+        }
+    }
+
     fn format_log_obj(&self, log_obj: &LogObject, opts: &FormatOptions) -> String {
-        let basic = crate::reporters::basic::BasicReporter;
-        let formatted = basic.format_args(&log_obj.args, opts);
-        let mut parts = formatted.split('\n');
-        let message = parts.next().unwrap_or("");
-        let additional: Vec<&str> = parts.collect();
+        let opts = if self.compact && !opts.compact {
+            Cow::Owned(FormatOptions {
+                compact: true,
+                ..opts.clone()
+            })
+        } else {
+            Cow::Borrowed(opts)
+        };
+        let effective = opts.effective();
+        let opts = &*effective;
+        let basic = crate::reporters::basic::BasicReporter::new();
 
         if log_obj.r#type == LogType::Box {
-            let body = if additional.is_empty() {
-                character_format(message)
-            } else {
-                let add = additional.join("\n");
-                character_format(&format!("{}\n{}", message, add))
-            };
+            let body = character_format(&basic.format_message(log_obj, opts));
             return box_text(
                 &body,
                 &BoxOpts {
                     title: log_obj.title.as_ref().map(|t| character_format(t)),
                     style: None,
+                    max_width: opts
+                        .columns
+                        .map(|c| (c as usize).saturating_sub(BoxStyle::default().margin_left)),
                 },
             );
         }
 
-        let date = basic.format_date(opts);
+        let date = if opts.compact {
+            String::new()
+        } else {
+            basic.format_entry_time(log_obj, opts)
+        };
         let colored_date = if !date.is_empty() {
             color::gray(&date)
         } else {
             String::new()
         };
 
-        let is_badge = log_obj.badge || log_obj.level < 2;
-        let type_str = self.format_type(log_obj, is_badge, opts);
+        // Compact mode prefers the type's icon over a badge when it has one.
+        let is_badge = (log_obj.badge || log_obj.level < 2)
+            && (!opts.compact || self.icon(log_obj.r#type).is_empty());
+        let mut trace_id = String::new();
+        let mut type_str = String::new();
+        let mut tag = String::new();
+        let mut label = String::new();
+        let mut parts = Vec::new();
+        let mut additional = None;
+        let mut has_fields = false;
+        let mut has_stack = false;
+        for segment in build_basic_segments(log_obj, opts) {
+            match segment.kind {
+                SegmentKind::TypeBadge if opts.compact && opts.compact_time_prefix => {
+                    type_str = self.format_time_prefix(log_obj, opts)
+                }
+                SegmentKind::TypeBadge => type_str = self.format_type(log_obj, is_badge, opts),
+                SegmentKind::TraceId => trace_id = color::magenta(&format!("#{}", segment.text)),
+                SegmentKind::Tag => tag = color::gray(&segment.text),
+                SegmentKind::Prefix => label = color::cyan(&format!("{}:", segment.text)),
+                SegmentKind::Message if opts.color_message_by_level => {
+                    let color_name = type_color_name(log_obj.r#type, log_obj.level);
+                    let text = character_format(&segment.text);
+                    parts.push(if opts.colors {
+                        paint_always(color_name, &text, opts.color_depth)
+                    } else {
+                        paint_type_color(color_name, &text, false, opts.color_depth)
+                    });
+                }
+                SegmentKind::Message => parts.push(character_format(&segment.text)),
+                SegmentKind::Meta => parts.push(color::gray(&segment.text)),
+                SegmentKind::Repetition => parts.push(color::dim(&segment.text)),
+                SegmentKind::Additional => additional = Some(segment.text),
+                // Rendered below from the fields and error themselves, with colors.
+                SegmentKind::Fields => has_fields = true,
+                SegmentKind::Stack => has_stack = true,
+                _ => parts.push(segment.text),
+            }
+        }
+        let separator = opts.separator.as_str();
+        let message = basic.filter_and_join_with(&parts, separator);
+
+        // Auto-detect terminal width when not set
+        let columns = opts.columns.unwrap_or(0) as usize;
 
-        let tag = if !log_obj.tag.is_empty() {
-            color::gray(&log_obj.tag)
+        // Left side: type + tag + message. When wrapping, everything but the
+        // last wrapped line goes into `head` and the date is placed on the last.
+        let (head, left) = if opts.wrap && columns > 0 && opts.truncate.is_none() {
+            let prefix = basic.filter_and_join_with(&[trace_id, type_str, tag, label], separator);
+            let mut lines = wrap_prefixed(&prefix, &message, separator, columns, opts.wrap_indent);
+            let last = lines.pop().unwrap_or_default();
+            (lines, last)
         } else {
-            String::new()
+            (
+                Vec::new(),
+                basic.filter_and_join_with(&[trace_id, type_str, tag, label, message], separator),
+            )
         };
-
-        // Left side: type + tag + message
-        let left = basic.filter_and_join(&[type_str, tag, character_format(message)]);
         // Right side: just the date, right-aligned to terminal edge
         let right = colored_date;
 
-        // Auto-detect terminal width when not set
-        let columns = opts.columns.unwrap_or(0) as usize;
         let date_width = string_width(&right);
+        let left = match opts.truncate {
+            // Leave room for the date and the two spaces before it.
+            Some(max) if date_width > 0 => {
+                truncate_display(&left, max.saturating_sub(date_width + 2), "…")
+            }
+            Some(max) => truncate_display(&left, max, "…"),
+            None => left,
+        };
         let left_width = string_width(&left);
 
-        let mut line = if columns > 0 && date_width > 0 && left_width + date_width + 2 < columns {
+        let line = if columns > 0 && date_width > 0 && left_width + date_width + 2 < columns {
             // Right-align the date at the terminal edge
             let space = columns.saturating_sub(left_width + date_width + 1);
             format!("{}{}{}", left, " ".repeat(space), right)
@@ -274,19 +510,42 @@ impl FancyReporter {
         } else {
             left
         };
+        let mut line = if head.is_empty() {
+            line
+        } else {
+            format!("{}\n{}", head.join("\n"), line)
+        };
 
         // Append additional lines from args
-        if !additional.is_empty() {
-            line.push_str(&character_format(&format!("\n{}", additional.join("\n"))));
+        if let Some(additional) = additional {
+            let additional = match opts.truncate {
+                Some(max) => truncate_lines(&additional, max),
+                None => additional,
+            };
+            line.push_str(&character_format(&format!("\n{}", additional)));
+        }
+
+        if has_fields {
+            let fields = fields_block(&log_obj.fields, &color::dim(":"));
+            line.push('\n');
+            line.push_str(&match opts.truncate {
+                Some(max) => truncate_lines(&fields, max),
+                None => fields,
+            });
         }
 
         // Append error info (error chain with stack traces)
-        if let Some(err) = &log_obj.error {
+        if has_stack && let Some(err) = &log_obj.error {
             let error_text = Self::format_error(err, opts, 0);
             line.push_str(&format!("\n{}", error_text));
         }
 
-        if is_badge {
+        if opts.mark_continuations {
+            let marker = color::gray(&format!("{} | ", log_obj.r#type.as_str()));
+            line = mark_continuation_lines(&line, &marker);
+        }
+
+        if is_badge && !opts.compact {
             format!("\n{}\n", line)
         } else {
             line
@@ -300,7 +559,24 @@ impl Reporter for FancyReporter {
         log_obj: &LogObject,
         ctx: &LogContext,
     ) -> Result<String, crate::error::ConsolaError> {
-        Ok(self.format_log_obj(log_obj, &ctx.options.format_options))
+        if log_obj.group_event == Some(GroupEvent::End) {
+            return Ok(String::new());
+        }
+        let opts = &ctx.options.format_options;
+        let mut formatted = self.format_log_obj(log_obj, opts);
+        if let Some(name) = &ctx.options.name {
+            formatted = prefix_first_line(
+                &formatted,
+                &format!("{}{}", color::dim(name), opts.separator),
+            );
+        }
+        if !self.colors {
+            formatted = strip_ansi(&formatted);
+        } else if opts.coalesce_styles {
+            formatted = coalesce_styles(&formatted);
+        }
+        let indent = " ".repeat(opts.group_indent * log_obj.group_depth);
+        Ok(indent_lines(&formatted, &indent))
     }
 
     fn clone_box(&self) -> Box<dyn Reporter> {
@@ -314,6 +590,7 @@ mod tests {
     use crate::constants::LogType;
     use crate::types::ConsolaOptions;
     use crate::util::color;
+    use crate::util::string::strip_ansi;
     use std::sync::Arc;
 
     fn make_ctx() -> LogContext {
@@ -348,6 +625,13 @@ mod tests {
             icon: None,
             style: None,
             error: None,
+            group_depth: 0,
+            group_event: None,
+            fields: Vec::new(),
+            repeated: 0,
+            trace_id: None,
+            prefix: None,
+            extensions: Default::default(),
         }
     }
 
@@ -364,6 +648,96 @@ mod tests {
         // Function called successfully — returns a bool without panicking
     }
 
+    #[test]
+    fn test_detect_unicode_by_code_page() {
+        assert!(detect_unicode(true, "", "", Some(CP_UTF8)));
+        assert!(detect_unicode(true, "", "", None));
+        assert!(!detect_unicode(true, "", "", Some(437)));
+        assert!(detect_unicode(true, "", "en_US.UTF-8", Some(437)));
+        assert!(detect_unicode(false, "", "", Some(437)));
+    }
+
+    #[test]
+    fn test_detect_unicode_legacy_terminals() {
+        assert!(!detect_unicode(true, "MINGW", "", None));
+        assert!(!detect_unicode(true, "cygwin", "", None));
+        assert!(detect_unicode(false, "MINGW", "", None));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_unicode_follows_console_code_page() {
+        let expected = detect_unicode(
+            true,
+            &std::env::var("TERM").unwrap_or_default(),
+            &std::env::var("LANG").unwrap_or_default(),
+            console_code_page(),
+        );
+        assert_eq!(FancyReporter::new().unicode, expected);
+    }
+
+    #[test]
+    fn test_for_ci_preset() {
+        let r = FancyReporter::for_ci();
+        assert!(!r.unicode);
+        assert!(!r.icons);
+        assert!(r.compact);
+    }
+
+    #[test]
+    fn test_ci_colors_only_when_forced() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(!ci_colors(env(&[])));
+        assert!(ci_colors(env(&[("FORCE_COLOR", "1")])));
+        assert!(!ci_colors(env(&[("FORCE_COLOR", "1"), ("NO_COLOR", "1")])));
+    }
+
+    #[test]
+    fn test_for_ci_output_is_plain_single_line() {
+        let mut obj = LogObject::new(LogType::Success);
+        obj.args = vec!["built".into(), "\nin 3s".into()];
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        };
+        let out = FancyReporter::for_ci()
+            .colors(false)
+            .format(&obj, &ctx)
+            .unwrap();
+        assert_eq!(out, "success built in 3s");
+        assert!(!out.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_icons_omitted_when_not_a_terminal() {
+        let obj = make_log_obj(LogType::Success, &["done"], "");
+        let format = |r: FancyReporter| r.unicode(true).format(&obj, &make_ctx_no_date()).unwrap();
+
+        let tty = format(FancyReporter::new().colors(false).terminal(true));
+        assert!(tty.contains("✔"), "got: {:?}", tty);
+        let piped = format(FancyReporter::new().colors(false).terminal(false));
+        assert!(!piped.contains("✔"), "got: {:?}", piped);
+        assert!(piped.contains("success done"), "got: {:?}", piped);
+        let forced = format(
+            FancyReporter::new()
+                .colors(false)
+                .terminal(false)
+                .icons(true),
+        );
+        assert!(forced.contains("✔"), "got: {:?}", forced);
+    }
+
+    #[test]
+    fn test_unicode_override() {
+        assert!(!FancyReporter::new().unicode(false).unicode);
+        assert!(FancyReporter::new().unicode(true).unicode);
+    }
+
     #[test]
     fn test_type_color_map_has_entries() {
         let mapped: Vec<LogType> = TYPE_COLOR_MAP.iter().map(|(t, _)| *t).collect();
@@ -399,7 +773,7 @@ mod tests {
     #[test]
     fn test_format_with_icons() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
 
         let obj = make_log_obj(LogType::Info, &["hello"], "");
@@ -415,7 +789,7 @@ mod tests {
     #[test]
     fn test_format_badge_low_level() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
 
         let obj = make_log_obj(LogType::Error, &["err"], "");
@@ -432,7 +806,7 @@ mod tests {
     #[test]
     fn test_format_with_tag() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
         let obj = make_log_obj(LogType::Info, &["hello"], "mytag");
         let result = r.format(&obj, &ctx).unwrap();
@@ -443,7 +817,7 @@ mod tests {
     #[test]
     fn test_format_box() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
         let mut obj = make_log_obj(LogType::Box, &["hello"], "");
         obj.title = Some("title".into());
@@ -453,10 +827,297 @@ mod tests {
         assert!(has_border);
     }
 
+    #[test]
+    fn test_fields_block_below_message() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    date: false,
+                    fields_block: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let mut obj = make_log_obj(LogType::Info, &["config loaded"], "app");
+        obj.fields = vec![
+            ("port".into(), "8080".into()),
+            ("database".into(), "postgres".into()),
+        ];
+        assert_eq!(
+            r.format(&obj, &ctx).unwrap(),
+            "ℹ app config loaded\n  port:     8080\n  database: postgres"
+        );
+    }
+
+    #[test]
+    fn test_format_box_wraps_to_columns() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: Some(30),
+                    date: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let long = "word ".repeat(20);
+        let obj = make_log_obj(LogType::Box, &[long.trim()], "");
+        let result = r.format(&obj, &ctx).unwrap();
+        for line in result.lines() {
+            assert!(string_width(line) <= 30, "line overflows: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_format_box_tiny_columns() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let mut obj = make_log_obj(LogType::Box, &["ready"], "");
+        obj.title = Some("status".into());
+        for columns in 0..=2 {
+            let ctx = LogContext {
+                options: Arc::new(ConsolaOptions {
+                    format_options: crate::types::FormatOptions {
+                        columns: Some(columns),
+                        date: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            };
+            let result = r.format(&obj, &ctx).unwrap();
+            assert!(result.contains('╭') && result.contains('╯'));
+            assert!(result.contains('r') && result.contains('…'));
+        }
+    }
+
+    fn make_ctx_wrap(columns: u16, indent: bool) -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: Some(columns),
+                    date: false,
+                    wrap: true,
+                    wrap_indent: indent,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Every `ESC [` must be followed by parameters and a final `m` on the same line.
+    fn assert_escapes_intact(line: &str) {
+        for (i, _) in line.match_indices('\x1b') {
+            let rest = &line[i + 1..];
+            assert!(rest.starts_with('['), "bare escape in {:?}", line);
+            let end = rest[1..]
+                .find(|c: char| !c.is_ascii_digit() && c != ';')
+                .map(|j| &rest[1 + j..1 + j + 1]);
+            assert_eq!(end, Some("m"), "broken escape in {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_format_wraps_long_message() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = make_ctx_wrap(20, false);
+        let obj = make_log_obj(LogType::Log, &["alpha beta gamma delta epsilon zeta"], "");
+        let result = r.format(&obj, &ctx).unwrap();
+        assert_eq!(result, "log alpha beta gamma\ndelta epsilon zeta");
+    }
+
+    #[test]
+    fn test_format_wrap_indents_under_message() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = make_ctx_wrap(24, true);
+        let obj = make_log_obj(LogType::Log, &["alpha beta gamma delta epsilon"], "tag");
+        let result = r.format(&obj, &ctx).unwrap();
+        let lines: Vec<String> = result.lines().map(strip_ansi).collect();
+        assert_eq!(
+            lines,
+            vec!["log tag alpha beta gamma", "        delta epsilon"]
+        );
+    }
+
+    #[test]
+    fn test_format_wrap_keeps_ansi_intact() {
+        let r = FancyReporter::new().unicode(true);
+        let ctx = make_ctx_wrap(16, true);
+        let colored = "\x1b[31mred words that keep going\x1b[0m and \x1b[1;32mbold green\x1b[0m";
+        let obj = make_log_obj(LogType::Info, &[colored], "");
+        let result = r.format(&obj, &ctx).unwrap();
+        assert!(result.lines().count() > 1);
+        for line in result.lines() {
+            assert_escapes_intact(line);
+            assert!(string_width(line) <= 16, "line overflows: {:?}", line);
+        }
+        assert_eq!(
+            strip_ansi(&result)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            strip_ansi(&format!("{} {}", icon_for(LogType::Info, true), colored))
+        );
+    }
+
+    #[test]
+    fn test_format_wrap_disabled_by_default() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: Some(10),
+                    date: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let obj = make_log_obj(LogType::Log, &["alpha beta gamma"], "");
+        assert_eq!(r.format(&obj, &ctx).unwrap(), "log alpha beta gamma");
+    }
+
+    #[test]
+    fn test_format_inline_error_chain() {
+        let r = FancyReporter::new().colors(false).unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    date: false,
+                    inline_error_chain: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let mut obj = make_log_obj(LogType::Warn, &["failed"], "");
+        obj.error = Some(ErrorInfo {
+            message: "a".into(),
+            cause: Some(Box::new(ErrorInfo {
+                message: "b".into(),
+                cause: Some(Box::new(ErrorInfo {
+                    message: "c".into(),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        let result = strip_ansi(&r.format(&obj, &ctx).unwrap());
+        assert!(
+            result.trim().ends_with("failed: a: b: c"),
+            "got: {result:?}"
+        );
+        assert_eq!(result.trim().lines().count(), 1);
+    }
+
+    fn compact_sample() -> LogObject {
+        let mut obj = make_log_obj(
+            LogType::Error,
+            &["request  failed", "\nretrying\nlater"],
+            "http",
+        );
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            stack: Some("at fetch (src/http.rs:10:5)".into()),
+            cause: Some(Box::new(ErrorInfo {
+                message: "connection reset".into(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        obj
+    }
+
+    fn format_sample(compact: bool, date: bool) -> String {
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: None,
+                    date,
+                    compact,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let r = FancyReporter::new().colors(false).unicode(true);
+        strip_ansi(&r.format(&compact_sample(), &ctx).unwrap())
+    }
+
+    #[test]
+    fn test_format_compact_snapshot() {
+        assert_eq!(
+            format_sample(false, false),
+            "\n ERROR  http request  failed\nretrying\nlater\ntimeout\n\n    at fetch (src/http.rs:10:5)\n\n  [cause]: connection reset\n"
+        );
+        assert_eq!(
+            format_sample(true, false),
+            "✖ http request failed retrying later: timeout: connection reset"
+        );
+        assert_eq!(format_sample(true, true), format_sample(true, false));
+    }
+
+    #[test]
+    fn test_format_compact_time_prefix_snapshot() {
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: None,
+                    compact: true,
+                    compact_time_prefix: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let mut obj = compact_sample();
+        obj.timestamp_ms = 1_704_164_645_678;
+        let out = strip_ansi(&FancyReporter::new().format(&obj, &ctx).unwrap());
+        let clock = crate::reporters::basic::BasicReporter::new().format_clock_at(obj.timestamp_ms);
+        assert_eq!(clock.len(), 8);
+        assert_eq!(
+            out,
+            format!("{clock} E http request failed retrying later: timeout: connection reset")
+        );
+    }
+
+    #[test]
+    fn test_format_mark_continuations() {
+        let r = FancyReporter::new().unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    date: false,
+                    mark_continuations: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let mut obj = make_log_obj(LogType::Info, &["first\nsecond\nthird"], "");
+        obj.error = Some(ErrorInfo {
+            message: "boom".into(),
+            stack: Some("at main (src/main.rs:1:1)".into()),
+            ..Default::default()
+        });
+        let result = strip_ansi(&r.format(&obj, &ctx).unwrap());
+        let lines: Vec<&str> = result.lines().filter(|l| !l.is_empty()).collect();
+        assert!(lines[0].ends_with("first"));
+        assert_eq!(lines.len(), 5);
+        for line in &lines[1..] {
+            assert!(line.starts_with("info | "), "unmarked line: {:?}", line);
+        }
+    }
+
     #[test]
     fn test_format_badge() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
         let mut obj = make_log_obj(LogType::Info, &["hello"], "");
         obj.badge = true;
@@ -465,6 +1126,23 @@ mod tests {
         assert!(result.ends_with('\n'));
     }
 
+    #[test]
+    fn test_badge_comes_from_type_not_message_text() {
+        let ctx = make_ctx_no_date();
+        let r = FancyReporter::new().unicode(true);
+
+        let mut obj = make_log_obj(LogType::Info, &["[note] hi"], "");
+        obj.badge = true;
+        let out = strip_ansi(&r.format(&obj, &ctx).unwrap());
+        assert_eq!(out.trim(), "INFO  [note] hi");
+
+        let ty = crate::constants::define_type("fancy-note", 3, None, None).unwrap();
+        let mut obj = make_log_obj(ty, &["[info] hi"], "");
+        obj.badge = true;
+        let out = strip_ansi(&r.format(&obj, &ctx).unwrap());
+        assert_eq!(out.trim(), "FANCY-NOTE  [info] hi");
+    }
+
     #[test]
     fn test_character_format_backticks() {
         color::set_color_enabled(false);
@@ -541,9 +1219,50 @@ mod tests {
         assert_eq!(type_color_name(LogType::Box, 5), "gray");
     }
 
+    #[test]
+    fn test_custom_type_icon_and_color() {
+        let ty =
+            crate::constants::define_type("fancy-deploy", 3, Some("🚀"), Some("blue")).unwrap();
+        assert_eq!(icon_for(ty, true), "🚀");
+        assert_eq!(icon_for(ty, false), "🚀");
+        assert_eq!(type_color_name(ty, 3), "blue");
+
+        let plain = crate::constants::define_type("fancy-plain", 1, None, None).unwrap();
+        assert_eq!(icon_for(plain, true), "");
+        assert_eq!(type_color_name(plain, 1), "yellow");
+    }
+
+    #[test]
+    fn test_custom_type_renders_registered_icon() {
+        use crate::constants::{CustomTypeDef, define_type_with};
+        let ty = define_type_with(
+            "fancy-ship",
+            CustomTypeDef::new(3)
+                .icon("🚀")
+                .ascii_icon("^")
+                .color("magenta"),
+        )
+        .unwrap();
+        assert_eq!(icon_for(ty, false), "^");
+        assert_eq!(type_color_name(ty, 3), "magenta");
+
+        let ctx = make_ctx_no_date();
+        let obj = make_log_obj(ty, &["shipped"], "");
+        let render = |unicode| {
+            strip_ansi(
+                &FancyReporter::new()
+                    .unicode(unicode)
+                    .format(&obj, &ctx)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(render(true), "🚀 shipped");
+        assert_eq!(render(false), "^ shipped");
+    }
+
     #[test]
     fn test_clone_box() {
-        let r: Box<dyn Reporter> = Box::new(FancyReporter { unicode: true });
+        let r: Box<dyn Reporter> = Box::new(FancyReporter::new().unicode(true));
         let cloned = r.clone_box();
         let ctx = make_ctx_no_date();
         let obj = make_log_obj(LogType::Info, &["test"], "");
@@ -556,7 +1275,7 @@ mod tests {
     #[test]
     fn test_format_date_appears_with_default_opts() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx();
         let obj = make_log_obj(LogType::Info, &["hello"], "");
         let result = r.format(&obj, &ctx).unwrap();
@@ -570,7 +1289,7 @@ mod tests {
     #[test]
     fn test_format_with_error_and_backtrace() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let ctx = make_ctx_no_date();
         let mut obj = make_log_obj(LogType::Error, &["an error occurred"], "");
         obj.error = Some(ErrorInfo {
@@ -597,10 +1316,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_chain_renders_without_additional_lines() {
+        let r = FancyReporter::new().unicode(true);
+        let mut obj = make_log_obj(LogType::Warn, &["request failed"], "");
+        obj.error = Some(ErrorInfo {
+            message: "request failed".into(),
+            cause: Some(Box::new(ErrorInfo {
+                message: "connection reset".into(),
+                cause: Some(Box::new(ErrorInfo {
+                    message: "peer closed".into(),
+                    ..ErrorInfo::default()
+                })),
+                ..ErrorInfo::default()
+            })),
+            ..ErrorInfo::default()
+        });
+        assert!(obj.additional.is_none());
+        let result = strip_ansi(&r.format(&obj, &make_ctx_no_date()).unwrap());
+        let lines: Vec<&str> = result.lines().collect();
+        assert!(lines.len() > 1, "{:?}", result);
+        assert!(result.contains("connection reset"), "{:?}", result);
+        assert!(result.contains("peer closed"), "{:?}", result);
+    }
+
+    #[test]
+    fn test_format_dims_repeat_counter() {
+        let r = FancyReporter::new().unicode(true);
+        let mut obj = make_log_obj(LogType::Info, &["ping", "(repeated 3 times)"], "");
+        obj.repeated = 3;
+        let result = r.format(&obj, &make_ctx_no_date()).unwrap();
+        assert!(
+            result.contains(&color::dim("(repeated 3 times)")),
+            "got: {:?}",
+            result
+        );
+        assert_eq!(
+            strip_ansi(&result),
+            format!("{} ping (repeated 3 times)", icon_for(LogType::Info, true))
+        );
+    }
+
+    #[test]
+    fn test_format_message_resembling_counter_is_not_dimmed() {
+        let r = FancyReporter::new().unicode(true);
+        for text in ["(x marks the spot)", "(repeated 3 times)"] {
+            let obj = make_log_obj(LogType::Info, &[text], "");
+            let result = r.format(&obj, &make_ctx_no_date()).unwrap();
+            assert!(result.contains(text), "got: {:?}", result);
+            if color::color_enabled() {
+                assert!(!result.contains(&color::dim(text)), "got: {:?}", result);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_color_message_by_level() {
+        let r = FancyReporter::new().unicode(true);
+        let obj = make_log_obj(LogType::Error, &["boom"], "");
+        let format = |by_level: bool| {
+            let ctx = LogContext {
+                options: Arc::new(ConsolaOptions {
+                    format_options: crate::types::FormatOptions {
+                        date: false,
+                        colors: true,
+                        color_depth: ColorDepth::Ansi16,
+                        color_message_by_level: by_level,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            };
+            r.format(&obj, &ctx).unwrap().trim_end().to_string()
+        };
+
+        let colored = format(true);
+        assert!(
+            colored.ends_with(" \x1b[31mboom\x1b[0m"),
+            "got: {:?}",
+            colored
+        );
+        let plain = format(false);
+        assert!(plain.ends_with(" boom"), "got: {:?}", plain);
+    }
+
     #[test]
     fn test_format_with_columns_right_aligns_date() {
         color::set_color_enabled(false);
-        let r = FancyReporter { unicode: true };
+        let r = FancyReporter::new().unicode(true);
         let fmt_opts = crate::types::FormatOptions {
             columns: Some(120),
             ..Default::default()
@@ -625,4 +1428,52 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_truncate_caps_line_width() {
+        let r = FancyReporter::new().unicode(true);
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: crate::types::FormatOptions {
+                    columns: Some(80),
+                    date: false,
+                    wrap: true,
+                    truncate: Some(20),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        };
+        let obj = make_log_obj(LogType::Info, &["a very long message that keeps going"], "");
+        let result = r.format(&obj, &ctx).unwrap();
+        assert_eq!(result.lines().count(), 1, "wrapped: {:?}", result);
+        assert_eq!(string_width(&result), 20);
+        assert!(strip_ansi(&result).ends_with('…'));
+        assert_escapes_intact(&result);
+    }
+
+    #[test]
+    fn test_type_case_applies_to_badge_and_label() {
+        let r = FancyReporter::new().unicode(true).icons(false);
+        let render = |ty, case| {
+            let ctx = LogContext {
+                options: Arc::new(ConsolaOptions {
+                    format_options: crate::types::FormatOptions {
+                        columns: None,
+                        date: false,
+                        type_case: case,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            };
+            let obj = make_log_obj(ty, &["msg"], "");
+            strip_ansi(&r.format(&obj, &ctx).unwrap())
+        };
+        assert_eq!(render(LogType::Error, TypeCase::AsIs), "\n ERROR  msg\n");
+        assert_eq!(render(LogType::Error, TypeCase::Lower), "\n error  msg\n");
+        assert_eq!(render(LogType::Error, TypeCase::Title), "\n Error  msg\n");
+        assert_eq!(render(LogType::Log, TypeCase::AsIs), "log msg");
+        assert_eq!(render(LogType::Log, TypeCase::Upper), "LOG msg");
+    }
 }
@@ -0,0 +1,846 @@
+//! JsonReporter — serializes each log entry as a single line of JSON (NDJSON).
+
+use std::sync::Arc;
+
+use crate::error::ConsolaError;
+use crate::sync::Mutex;
+use crate::types::{ErrorInfo, FormatOptions, GroupEvent, LogContext, LogObject, Reporter};
+use crate::util::sink::Sink;
+
+/// Keys of the objects written by [`JsonReporter`], shared by the reporter
+/// and [`json_schema`] so the two cannot drift apart.
+pub mod keys {
+    /// Numeric level of an entry.
+    pub const LEVEL: &str = "level";
+    /// Log type name of an entry, e.g. `info`.
+    pub const TYPE: &str = "type";
+    /// Tag of an entry or group event, empty if none was set.
+    pub const TAG: &str = "tag";
+    /// Logger-wide prefix label of an entry, or `null`.
+    pub const PREFIX: &str = "prefix";
+    /// Name of the logger that wrote an entry, or `null`.
+    pub const LOGGER: &str = "logger";
+    /// Correlation id of an entry, or `null`.
+    pub const TRACE_ID: &str = "trace_id";
+    /// Message text of an entry, or of an error object.
+    pub const MESSAGE: &str = "message";
+    /// Secondary text of an entry, or `null`.
+    pub const ADDITIONAL: &str = "additional";
+    /// Arguments of an entry, as strings.
+    pub const ARGS: &str = "args";
+    /// Structured fields of an entry, as an object of strings.
+    pub const FIELDS: &str = "fields";
+    /// Time of an entry or group event, in milliseconds since the Unix epoch.
+    pub const TIMESTAMP_MS: &str = "timestamp_ms";
+    /// Title of an entry, or `null`.
+    pub const TITLE: &str = "title";
+    /// Whether an entry is shown as a badge.
+    pub const BADGE: &str = "badge";
+    /// Icon of an entry, or `null`.
+    pub const ICON: &str = "icon";
+    /// Style of an entry, or `null`.
+    pub const STYLE: &str = "style";
+    /// Error attached to an entry, or `null`.
+    pub const ERROR: &str = "error";
+    /// Stack trace of an error object, or `null`.
+    pub const STACK: &str = "stack";
+    /// Backtrace of an error object, or `null`.
+    pub const BACKTRACE: &str = "backtrace";
+    /// Cause of an error object, or `null`.
+    pub const CAUSE: &str = "cause";
+    /// Kind of a group event: `group_start` or `group_end`.
+    pub const EVENT: &str = "event";
+    /// Label of a group event.
+    pub const LABEL: &str = "label";
+    /// Nesting depth of a group event.
+    pub const DEPTH: &str = "depth";
+}
+
+/// Escape `s` as a JSON string literal, including the surrounding quotes.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_opt_string(s: Option<&str>) -> String {
+    s.map(json_string).unwrap_or_else(|| "null".into())
+}
+
+/// Render `fields` as a JSON object. Values must already be encoded.
+pub(crate) fn json_object(fields: &[(&str, String)]) -> String {
+    let body: Vec<String> = fields
+        .iter()
+        .map(|(k, v)| format!("{}:{}", json_string(k), v))
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+/// Serialize `err` and its causes. `max_causes` of `0` keeps the whole chain.
+fn error_to_json(err: &ErrorInfo, max_causes: u32) -> String {
+    error_to_json_at(err, max_causes, 0)
+}
+
+fn error_to_json_at(err: &ErrorInfo, max_causes: u32, depth: u32) -> String {
+    let cause = err
+        .cause
+        .as_deref()
+        .filter(|_| max_causes == 0 || depth < max_causes);
+    json_object(&[
+        (keys::MESSAGE, json_string(&err.message)),
+        (keys::STACK, json_opt_string(err.stack.as_deref())),
+        (keys::BACKTRACE, json_opt_string(err.backtrace.as_deref())),
+        (
+            keys::CAUSE,
+            cause
+                .map(|c| error_to_json_at(c, max_causes, depth + 1))
+                .unwrap_or_else(|| "null".into()),
+        ),
+    ])
+}
+
+/// JSON Schema (draft 2020-12) of the objects [`JsonReporter`] writes.
+///
+/// A record is either a log entry or, with `group_events`, a group event.
+/// Every key is always present except an entry's `timestamp_ms`, which
+/// `respect_show_flags` drops when `date` is off. In array mode each element
+/// of the array is such a record.
+pub fn json_schema() -> String {
+    let schema = |ty: &str| json_object(&[("type", ty.to_string())]);
+    let string = || schema(r#""string""#);
+    let opt_string = || schema(r#"["string","null"]"#);
+    let integer = || schema(r#""integer""#);
+    let reference =
+        |name: &str| json_object(&[("$ref", json_string(&format!("#/$defs/{}", name)))]);
+    let object = |properties: &[(&str, String)], optional: &[&str]| {
+        let required: Vec<String> = properties
+            .iter()
+            .map(|(k, _)| *k)
+            .filter(|k| !optional.contains(k))
+            .map(json_string)
+            .collect();
+        json_object(&[
+            ("type", json_string("object")),
+            ("properties", json_object(properties)),
+            ("required", format!("[{}]", required.join(","))),
+            ("additionalProperties", "false".into()),
+        ])
+    };
+    let nullable_error = format!(r#"{{"anyOf":[{},{{"type":"null"}}]}}"#, reference("error"));
+
+    let error = object(
+        &[
+            (keys::MESSAGE, string()),
+            (keys::STACK, opt_string()),
+            (keys::BACKTRACE, opt_string()),
+            (keys::CAUSE, nullable_error.clone()),
+        ],
+        &[],
+    );
+    let entry = object(
+        &[
+            (keys::LEVEL, integer()),
+            (keys::TYPE, string()),
+            (keys::TAG, string()),
+            (keys::PREFIX, opt_string()),
+            (keys::LOGGER, opt_string()),
+            (keys::TRACE_ID, opt_string()),
+            (keys::MESSAGE, opt_string()),
+            (keys::ADDITIONAL, opt_string()),
+            (
+                keys::ARGS,
+                json_object(&[("type", json_string("array")), ("items", string())]),
+            ),
+            (
+                keys::FIELDS,
+                json_object(&[
+                    ("type", json_string("object")),
+                    ("additionalProperties", string()),
+                ]),
+            ),
+            (keys::TIMESTAMP_MS, integer()),
+            (keys::TITLE, opt_string()),
+            (keys::BADGE, schema(r#""boolean""#)),
+            (keys::ICON, opt_string()),
+            (keys::STYLE, opt_string()),
+            (keys::ERROR, nullable_error),
+        ],
+        &[keys::TIMESTAMP_MS],
+    );
+    let group_event = object(
+        &[
+            (
+                keys::EVENT,
+                r#"{"enum":["group_start","group_end"]}"#.to_string(),
+            ),
+            (keys::LABEL, string()),
+            (
+                keys::DEPTH,
+                json_object(&[("type", json_string("integer")), ("minimum", "0".into())]),
+            ),
+            (keys::TAG, string()),
+            (keys::TIMESTAMP_MS, integer()),
+        ],
+        &[],
+    );
+
+    json_object(&[
+        (
+            "$schema",
+            json_string("https://json-schema.org/draft/2020-12/schema"),
+        ),
+        ("title", json_string("consola JsonReporter record")),
+        (
+            "oneOf",
+            format!("[{},{}]", reference("entry"), reference("group_event")),
+        ),
+        (
+            "$defs",
+            json_object(&[
+                ("entry", entry),
+                ("group_event", group_event),
+                ("error", error),
+            ]),
+        ),
+    ])
+}
+
+/// Records buffered by a [`JsonReporter`] in array mode, shared by all its clones.
+///
+/// Whatever is still buffered on [`Reporter::flush`] or when the last clone
+/// is dropped is written to `sink`, so records are not lost if
+/// [`JsonReporter::finish`] is never called. Without an explicit sink, the
+/// `out_sink` of the first logger to format a record is used.
+#[derive(Debug)]
+struct ArrayBuffer {
+    records: Mutex<Vec<String>>,
+    sink: Mutex<Option<Arc<dyn Sink>>>,
+}
+
+impl ArrayBuffer {
+    /// Take the buffered records as a JSON array, or `None` if there are none.
+    fn take_array(&self) -> Option<String> {
+        let records = std::mem::take(&mut *self.records.lock());
+        (!records.is_empty()).then(|| format!("[{}]", records.join(",")))
+    }
+
+    /// Write the buffered records to the sink as one JSON array.
+    fn write_pending(&self) -> std::io::Result<()> {
+        let Some(array) = self.take_array() else {
+            return Ok(());
+        };
+        // Records are only buffered after the sink has been resolved.
+        let Some(sink) = self.sink.lock().clone() else {
+            return Ok(());
+        };
+        sink.write_line(&array)?;
+        sink.flush()
+    }
+}
+
+impl Drop for ArrayBuffer {
+    fn drop(&mut self) {
+        let _ = self.write_pending();
+    }
+}
+
+/// Where [`JsonReporter`] puts the text of an entry's arguments.
+///
+/// Arguments are always written as JSON strings, numbers and booleans
+/// included (`42` becomes `"42"`), so the type of a value never depends on
+/// what its text looks like.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonMessage {
+    /// Each argument is an element of `args`; `message` is `null` (the
+    /// logger folds a message given on the entry into `args`).
+    #[default]
+    Args,
+    /// `message` holds the arguments joined with spaces, as text reporters
+    /// show them; `args` is empty.
+    Message,
+    /// Both `message` with the joined text and `args` with each argument.
+    Both,
+}
+
+/// Formats log entries as newline-delimited JSON, one object per entry.
+///
+/// Group boundaries from [`Consola::group`](crate::Consola::group) are emitted
+/// as `{"event":"group_start",...}` / `{"event":"group_end",...}` objects so
+/// consumers can rebuild the hierarchy. With `group_events` disabled, group
+/// labels are written as ordinary entries and group ends produce no output.
+///
+/// By default every field is written regardless of [`FormatOptions`]. With
+/// `respect_show_flags`, the options that decide what text reporters show are
+/// applied here too: `date: false` drops `timestamp_ms`, and a non-zero
+/// `error_level` caps the number of causes in `error`.
+///
+/// [`message_mode`](Self::message_mode) chooses whether arguments appear in
+/// `args`, as one `message` string, or both (see [`JsonMessage`]).
+///
+/// In [array mode](Self::array_mode) nothing is written per entry; records are
+/// buffered and written as a single JSON array by [`finish`](Self::finish),
+/// or to the sink on [`Consola::shutdown`](crate::Consola::shutdown).
+#[derive(Debug, Clone)]
+pub struct JsonReporter {
+    /// Whether group boundaries are emitted as structured events.
+    pub group_events: bool,
+    /// Whether to omit fields the text reporters would hide under the current
+    /// [`FormatOptions`].
+    pub respect_show_flags: bool,
+    /// Whether argument text is written to `args`, `message`, or both.
+    pub message_mode: JsonMessage,
+    array: Option<Arc<ArrayBuffer>>,
+}
+
+impl Default for JsonReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonReporter {
+    /// Creates a new `JsonReporter` with group events enabled.
+    pub fn new() -> Self {
+        Self {
+            group_events: true,
+            respect_show_flags: false,
+            message_mode: JsonMessage::default(),
+            array: None,
+        }
+    }
+
+    /// Buffer records instead of writing one line per entry, returning the builder for chaining.
+    ///
+    /// Call [`finish`](Self::finish) on a clone of the reporter to write the
+    /// array. Records still buffered on
+    /// [`Consola::shutdown`](crate::Consola::shutdown) or when the last clone
+    /// is dropped are written to the logger's `out_sink`.
+    pub fn array_mode(mut self) -> Self {
+        self.array = Some(Arc::new(ArrayBuffer {
+            records: Mutex::new(Vec::new()),
+            sink: Mutex::new(None),
+        }));
+        self
+    }
+
+    /// Like [`array_mode`](Self::array_mode), but records left unfinished on
+    /// shutdown or drop are written to `sink` instead of the logger's
+    /// `out_sink`.
+    pub fn array_mode_to(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.array = Some(Arc::new(ArrayBuffer {
+            records: Mutex::new(Vec::new()),
+            sink: Mutex::new(Some(sink)),
+        }));
+        self
+    }
+
+    /// Write the records buffered in array mode to `w` as one JSON array
+    /// followed by a newline, and clear the buffer.
+    ///
+    /// Writes `[]` when nothing was buffered, and nothing outside array mode.
+    pub fn finish(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let Some(array) = &self.array else {
+            return Ok(());
+        };
+        let json = array.take_array().unwrap_or_else(|| "[]".into());
+        writeln!(w, "{}", json)?;
+        w.flush()
+    }
+
+    /// Set whether group boundaries are emitted as events, returning the builder for chaining.
+    pub fn group_events(mut self, enabled: bool) -> Self {
+        self.group_events = enabled;
+        self
+    }
+
+    /// Set whether [`FormatOptions`] show flags are honored, returning the builder for chaining.
+    pub fn respect_show_flags(mut self, enabled: bool) -> Self {
+        self.respect_show_flags = enabled;
+        self
+    }
+
+    /// Set where argument text is written, returning the builder for chaining.
+    pub fn message_mode(mut self, mode: JsonMessage) -> Self {
+        self.message_mode = mode;
+        self
+    }
+
+    /// Serialize a regular log entry, including every field.
+    pub fn format_log_obj(&self, log_obj: &LogObject) -> String {
+        self.format_log_obj_with(log_obj, None, None)
+    }
+
+    /// Serialize a regular log entry from the logger named `logger`, hiding
+    /// fields according to `opts` when given.
+    fn format_log_obj_with(
+        &self,
+        log_obj: &LogObject,
+        logger: Option<&str>,
+        opts: Option<&FormatOptions>,
+    ) -> String {
+        let joined = log_obj.args.join(" ");
+        let (message, args) = match self.message_mode {
+            JsonMessage::Args => (log_obj.message.as_deref(), &log_obj.args[..]),
+            JsonMessage::Message => (Some(joined.as_str()), &[][..]),
+            JsonMessage::Both => (Some(joined.as_str()), &log_obj.args[..]),
+        };
+        let args: Vec<String> = args.iter().map(|a| json_string(a)).collect();
+        let max_causes = opts.map_or(0, |o| o.error_level);
+        let entry_fields: Vec<(&str, String)> = log_obj
+            .fields
+            .iter()
+            .map(|(k, v)| (k.as_str(), json_string(v)))
+            .collect();
+        let mut fields = vec![
+            (keys::LEVEL, log_obj.level.to_string()),
+            (keys::TYPE, json_string(log_obj.r#type.as_str())),
+            (keys::TAG, json_string(&log_obj.tag)),
+            (keys::PREFIX, json_opt_string(log_obj.prefix.as_deref())),
+            (keys::LOGGER, json_opt_string(logger)),
+            (keys::TRACE_ID, json_opt_string(log_obj.trace_id.as_deref())),
+            (keys::MESSAGE, json_opt_string(message)),
+            (
+                keys::ADDITIONAL,
+                json_opt_string(log_obj.additional.as_deref()),
+            ),
+            (keys::ARGS, format!("[{}]", args.join(","))),
+            (keys::FIELDS, json_object(&entry_fields)),
+            (keys::TIMESTAMP_MS, log_obj.timestamp_ms.to_string()),
+            (keys::TITLE, json_opt_string(log_obj.title.as_deref())),
+            (keys::BADGE, log_obj.badge.to_string()),
+            (keys::ICON, json_opt_string(log_obj.icon.as_deref())),
+            (keys::STYLE, json_opt_string(log_obj.style.as_deref())),
+            (
+                keys::ERROR,
+                log_obj
+                    .error
+                    .as_ref()
+                    .map(|e| error_to_json(e, max_causes))
+                    .unwrap_or_else(|| "null".into()),
+            ),
+        ];
+        if opts.is_some_and(|o| !o.date) {
+            fields.retain(|(k, _)| *k != keys::TIMESTAMP_MS);
+        }
+        json_object(&fields)
+    }
+
+    fn format_group_event(&self, log_obj: &LogObject, event: GroupEvent) -> String {
+        let name = match event {
+            GroupEvent::Start => "group_start",
+            GroupEvent::End => "group_end",
+        };
+        let label = log_obj.args.first().map(String::as_str).unwrap_or("");
+        json_object(&[
+            (keys::EVENT, json_string(name)),
+            (keys::LABEL, json_string(label)),
+            (keys::DEPTH, log_obj.group_depth.to_string()),
+            (keys::TAG, json_string(&log_obj.tag)),
+            (keys::TIMESTAMP_MS, log_obj.timestamp_ms.to_string()),
+        ])
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        let opts = Some(&ctx.options.format_options).filter(|_| self.respect_show_flags);
+        let json = match log_obj.group_event {
+            Some(event) if self.group_events => self.format_group_event(log_obj, event),
+            Some(GroupEvent::End) => String::new(),
+            _ => self.format_log_obj_with(log_obj, ctx.options.name.as_deref(), opts),
+        };
+        match &self.array {
+            Some(array) => {
+                if !json.is_empty() {
+                    array
+                        .sink
+                        .lock()
+                        .get_or_insert_with(|| ctx.options.out_sink.clone());
+                    array.records.lock().push(json);
+                }
+                Ok(String::new())
+            }
+            None => Ok(json),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        match &self.array {
+            Some(array) => array.write_pending(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::ConsolaOptions;
+    use std::sync::Arc;
+
+    fn make_ctx() -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        }
+    }
+
+    fn parse(s: &str) -> serde_json::Value {
+        serde_json::from_str(s).unwrap_or_else(|e| panic!("invalid JSON {:?}: {}", s, e))
+    }
+
+    #[test]
+    fn test_emitted_records_match_schema() {
+        let schema = parse(&json_schema());
+        let validator = jsonschema::draft202012::new(&schema).expect("valid JSON Schema");
+
+        let mut obj = LogObject::new(LogType::Error);
+        obj.tag = "db".into();
+        obj.args = vec!["query failed".into()];
+        obj.fields = vec![("table".into(), "users".into())];
+        obj.trace_id = Some("4bf92f35".into());
+        obj.prefix = Some("api".into());
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            stack: Some("at main".into()),
+            cause: Some(Box::new(ErrorInfo {
+                message: "reset".into(),
+                ..ErrorInfo::default()
+            })),
+            ..ErrorInfo::default()
+        });
+        let no_date = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: FormatOptions {
+                    date: false,
+                    ..FormatOptions::default()
+                },
+                ..ConsolaOptions::default()
+            }),
+        };
+        let mut group = LogObject::new(LogType::Log);
+        group.args = vec!["build".into()];
+        group.group_event = Some(GroupEvent::Start);
+
+        let records = [
+            JsonReporter::new().format(&obj, &make_ctx()).unwrap(),
+            JsonReporter::new()
+                .message_mode(JsonMessage::Both)
+                .respect_show_flags(true)
+                .format(&obj, &no_date)
+                .unwrap(),
+            JsonReporter::new().format(&group, &make_ctx()).unwrap(),
+        ];
+        for record in &records {
+            let errors: Vec<String> = validator
+                .iter_errors(&parse(record))
+                .map(|e| e.to_string())
+                .collect();
+            assert!(errors.is_empty(), "{}: {:?}", record, errors);
+        }
+
+        let mut extra = parse(&records[0]);
+        extra["unexpected"] = serde_json::json!(1);
+        assert!(!validator.is_valid(&extra));
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn test_single_value_args_by_message_mode() {
+        let format = |mode, arg: &str| {
+            let mut obj = LogObject::new(LogType::Info);
+            obj.args = vec![arg.into()];
+            parse(
+                &JsonReporter::new()
+                    .message_mode(mode)
+                    .format(&obj, &make_ctx())
+                    .unwrap(),
+            )
+        };
+
+        let v = format(JsonMessage::Args, "42");
+        assert_eq!(v["message"], serde_json::Value::Null);
+        assert_eq!(v["args"], serde_json::json!(["42"]));
+
+        let v = format(JsonMessage::Message, "42");
+        assert_eq!(v["message"], "42");
+        assert_eq!(v["args"], serde_json::json!([]));
+
+        let v = format(JsonMessage::Both, "true");
+        assert_eq!(v["message"], "true");
+        assert_eq!(v["args"], serde_json::json!(["true"]));
+
+        let v = format(JsonMessage::Args, "true");
+        assert_eq!(v["args"][0], "true");
+    }
+
+    #[test]
+    fn test_format_regular_entry() {
+        let r = JsonReporter::new();
+        let mut obj = LogObject::new(LogType::Warn);
+        obj.tag = "db".into();
+        obj.args = vec!["slow \"query\"".into(), "42".into()];
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["type"], "warn");
+        assert_eq!(v["level"], 1);
+        assert_eq!(v["tag"], "db");
+        assert_eq!(v["args"][0], "slow \"query\"");
+        assert_eq!(v["args"][1], "42");
+        assert_eq!(v["fields"], serde_json::json!({}));
+        assert!(v["error"].is_null());
+        assert!(v["trace_id"].is_null());
+    }
+
+    #[test]
+    fn test_format_trace_id() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.trace_id = Some("4bf92f3577b34da6a3ce929d0e0e4736".into());
+        let v = parse(&JsonReporter::new().format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+
+    #[test]
+    fn test_format_fields() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.fields = vec![
+            ("port".into(), "8080".into()),
+            ("host".into(), "a\"b".into()),
+        ];
+        let v = parse(&JsonReporter::new().format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["fields"]["port"], "8080");
+        assert_eq!(v["fields"]["host"], "a\"b");
+
+        // The text reporters' block layout leaves the nested object as is.
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: FormatOptions {
+                    fields_block: true,
+                    ..FormatOptions::default()
+                },
+                ..ConsolaOptions::default()
+            }),
+        };
+        let r = JsonReporter::new().respect_show_flags(true);
+        let v = parse(&r.format(&obj, &ctx).unwrap());
+        assert_eq!(
+            v["fields"],
+            serde_json::json!({"port": "8080", "host": "a\"b"})
+        );
+    }
+
+    #[test]
+    fn test_format_error_chain() {
+        let r = JsonReporter::new();
+        let mut obj = LogObject::new(LogType::Error);
+        obj.error = Some(ErrorInfo {
+            message: "outer".into(),
+            stack: None,
+            backtrace: None,
+            cause: Some(Box::new(ErrorInfo {
+                message: "inner".into(),
+                stack: Some("at x".into()),
+                backtrace: None,
+                cause: None,
+            })),
+        });
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["error"]["message"], "outer");
+        assert_eq!(v["error"]["cause"]["message"], "inner");
+        assert_eq!(v["error"]["cause"]["stack"], "at x");
+    }
+
+    fn make_ctx_with(format_options: FormatOptions) -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options,
+                ..ConsolaOptions::default()
+            }),
+        }
+    }
+
+    fn chained_error() -> ErrorInfo {
+        ErrorInfo {
+            message: "outer".into(),
+            stack: None,
+            backtrace: None,
+            cause: Some(Box::new(ErrorInfo {
+                message: "inner".into(),
+                stack: None,
+                backtrace: None,
+                cause: Some(Box::new(ErrorInfo {
+                    message: "root".into(),
+                    ..ErrorInfo::default()
+                })),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_show_flags_ignored_by_default() {
+        let r = JsonReporter::new();
+        let mut obj = LogObject::new(LogType::Error);
+        obj.error = Some(chained_error());
+        let ctx = make_ctx_with(FormatOptions {
+            date: false,
+            error_level: 1,
+            ..FormatOptions::default()
+        });
+        let v = parse(&r.format(&obj, &ctx).unwrap());
+        assert!(v["timestamp_ms"].is_number());
+        assert_eq!(v["error"]["cause"]["cause"]["message"], "root");
+    }
+
+    #[test]
+    fn test_respect_show_flags_omits_timestamp_without_date() {
+        let r = JsonReporter::new().respect_show_flags(true);
+        let obj = LogObject::new(LogType::Info);
+        let ctx = make_ctx_with(FormatOptions {
+            date: false,
+            ..FormatOptions::default()
+        });
+        let v = parse(&r.format(&obj, &ctx).unwrap());
+        assert!(v.get("timestamp_ms").is_none());
+
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert!(v["timestamp_ms"].is_number());
+    }
+
+    #[test]
+    fn test_respect_show_flags_caps_error_chain() {
+        let r = JsonReporter::new().respect_show_flags(true);
+        let mut obj = LogObject::new(LogType::Error);
+        obj.error = Some(chained_error());
+        let ctx = make_ctx_with(FormatOptions {
+            error_level: 1,
+            ..FormatOptions::default()
+        });
+        let v = parse(&r.format(&obj, &ctx).unwrap());
+        assert_eq!(v["error"]["cause"]["message"], "inner");
+        assert!(v["error"]["cause"]["cause"].is_null());
+    }
+
+    #[test]
+    fn test_array_mode_finish_writes_valid_array() {
+        let r = JsonReporter::new().array_mode();
+        let ctx = make_ctx();
+        for (ty, msg) in [
+            (LogType::Info, "a"),
+            (LogType::Warn, "b"),
+            (LogType::Error, "c"),
+        ] {
+            let mut obj = LogObject::new(ty);
+            obj.args = vec![msg.into()];
+            assert_eq!(r.clone_box().format(&obj, &ctx).unwrap(), "");
+        }
+
+        let mut out = Vec::new();
+        r.finish(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("]\n"));
+        let v = parse(&out);
+        let records = v.as_array().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["args"][0], "a");
+        assert_eq!(records[1]["type"], "warn");
+        assert_eq!(records[2]["type"], "error");
+
+        let mut again = Vec::new();
+        r.finish(&mut again).unwrap();
+        assert_eq!(again, b"[]\n");
+    }
+
+    #[test]
+    fn test_array_mode_flushes_on_drop() {
+        let sink = crate::util::sink::TestSink::new();
+        let r = JsonReporter::new().array_mode_to(Arc::new(sink.clone()));
+        let copy = r.clone();
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["kept".into()];
+        copy.format(&obj, &make_ctx()).unwrap();
+
+        drop(copy);
+        assert_eq!(sink.contents(), "");
+        drop(r);
+        let v = parse(sink.contents().trim_end());
+        assert_eq!(v[0]["args"][0], "kept");
+    }
+
+    #[test]
+    fn test_array_mode_writes_to_out_sink_on_shutdown() {
+        let out = crate::util::sink::TestSink::new();
+        let r = JsonReporter::new().array_mode();
+        let c = crate::Consola::new(ConsolaOptions {
+            reporters: vec![Box::new(r.clone()) as Box<dyn Reporter>],
+            out_sink: Arc::new(out.clone()),
+            ..ConsolaOptions::default()
+        });
+        c.info("first");
+        c.info("second");
+        assert_eq!(out.contents(), "");
+
+        c.shutdown();
+        let v = parse(out.contents().trim_end());
+        assert_eq!(v[0]["args"][0], "first");
+        assert_eq!(v[1]["args"][0], "second");
+        drop((c, r));
+        assert_eq!(out.lines().len(), 1);
+    }
+
+    #[test]
+    fn test_finish_outside_array_mode_writes_nothing() {
+        let mut out = Vec::new();
+        JsonReporter::new().finish(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_format_group_events() {
+        let r = JsonReporter::new();
+        let mut obj = LogObject::new(LogType::Log);
+        obj.args = vec!["build".into()];
+        obj.group_depth = 1;
+        obj.group_event = Some(GroupEvent::Start);
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["event"], "group_start");
+        assert_eq!(v["label"], "build");
+        assert_eq!(v["depth"], 1);
+
+        obj.group_event = Some(GroupEvent::End);
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["event"], "group_end");
+    }
+
+    #[test]
+    fn test_format_group_events_disabled() {
+        let r = JsonReporter::new().group_events(false);
+        let mut obj = LogObject::new(LogType::Log);
+        obj.args = vec!["build".into()];
+        obj.group_event = Some(GroupEvent::Start);
+        let v = parse(&r.format(&obj, &make_ctx()).unwrap());
+        assert_eq!(v["args"][0], "build");
+        assert!(v.get("event").is_none());
+
+        obj.group_event = Some(GroupEvent::End);
+        assert_eq!(r.format(&obj, &make_ctx()).unwrap(), "");
+    }
+}
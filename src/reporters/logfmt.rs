@@ -0,0 +1,288 @@
+//! LogfmtReporter — renders each log entry as a line of `key=value` pairs.
+
+use crate::error::ConsolaError;
+use crate::types::{GroupEvent, LogContext, LogObject, Reporter, TimePrecision};
+
+/// Quote `value` if logfmt requires it: empty values and values containing
+/// spaces, `=`, quotes, or control characters. Anything else (including
+/// numbers and booleans) is written bare.
+fn logfmt_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c == ' ' || c == '=' || c == '"' || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Format milliseconds since the Unix epoch as an RFC 3339 UTC timestamp
+/// (`2024-01-02T03:04:05.678Z`).
+pub(crate) fn rfc3339_utc(ms: i64) -> String {
+    rfc3339_utc_with(ms, TimePrecision::AsIs)
+}
+
+/// Like [`rfc3339_utc`] with the fractional digits set by `precision`.
+pub(crate) fn rfc3339_utc_with(ms: i64, precision: TimePrecision) -> String {
+    let secs = ms.div_euclid(1000);
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        precision.fraction(ms, 3)
+    )
+}
+
+/// Formats log entries as [logfmt](https://brandur.org/logfmt) lines:
+/// `time=... level=info tag=db msg="hello world" cause="..."`.
+///
+/// Keys are always written in the same order: `time` (only when
+/// `FormatOptions::date` is set), `level`, `trace_id`, `tag`, `prefix`, `msg`, `title`, `repeat`
+/// (when the entry summarizes more than one throttled duplicate), `group`
+/// (for group starts), and `cause` with the error chain flattened to
+/// `message: cause: cause`, followed by the entry's fields in insertion
+/// order. Empty optional keys are omitted. Group ends produce no output.
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtReporter;
+
+impl LogfmtReporter {
+    /// Creates a new `LogfmtReporter`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render a log entry as a logfmt line.
+    pub fn format_log_obj(&self, log_obj: &LogObject, date: bool) -> String {
+        self.format_log_obj_with(log_obj, date, TimePrecision::AsIs)
+    }
+
+    fn format_log_obj_with(
+        &self,
+        log_obj: &LogObject,
+        date: bool,
+        precision: TimePrecision,
+    ) -> String {
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        if date {
+            fields.push(("time", rfc3339_utc_with(log_obj.timestamp_ms, precision)));
+        }
+        fields.push(("level", log_obj.r#type.as_str().to_string()));
+        if let Some(trace_id) = &log_obj.trace_id {
+            fields.push(("trace_id", trace_id.clone()));
+        }
+        if !log_obj.tag.is_empty() {
+            fields.push(("tag", log_obj.tag.clone()));
+        }
+        if let Some(prefix) = &log_obj.prefix {
+            fields.push(("prefix", prefix.clone()));
+        }
+        let mut args: &[String] = &log_obj.args;
+        if log_obj.repeated > 1 {
+            // Drop the "(repeated N times)" suffix in favor of `repeat=N`.
+            args = &args[..args.len().saturating_sub(1)];
+        }
+        fields.push(("msg", args.join(" ")));
+        if let Some(title) = &log_obj.title {
+            fields.push(("title", title.clone()));
+        }
+        if log_obj.repeated > 1 {
+            fields.push(("repeat", log_obj.repeated.to_string()));
+        }
+        if log_obj.group_event == Some(GroupEvent::Start) {
+            fields.push(("group", "start".to_string()));
+        }
+        if let Some(err) = &log_obj.error {
+            let chain = err.inline_chain(0);
+            if !chain.is_empty() {
+                fields.push(("cause", chain));
+            }
+        }
+        fields.extend(log_obj.fields.iter().map(|(k, v)| (k.as_str(), v.clone())));
+
+        fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, logfmt_value(v)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Reporter for LogfmtReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        if log_obj.group_event == Some(GroupEvent::End) {
+            return Ok(String::new());
+        }
+        let opts = &ctx.options.format_options;
+        Ok(self.format_log_obj_with(log_obj, opts.date, opts.time_precision))
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::{ConsolaOptions, ErrorInfo, FormatOptions};
+    use std::sync::Arc;
+
+    fn make_ctx(date: bool) -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions {
+                format_options: FormatOptions {
+                    date,
+                    ..FormatOptions::default()
+                },
+                ..ConsolaOptions::default()
+            }),
+        }
+    }
+
+    fn make_obj(ty: LogType, args: &[&str]) -> LogObject {
+        let mut obj = LogObject::new(ty);
+        obj.timestamp_ms = 1_704_164_645_678;
+        obj.args = args.iter().map(|a| a.to_string()).collect();
+        obj
+    }
+
+    #[test]
+    fn test_value_quoting() {
+        assert_eq!(logfmt_value("plain"), "plain");
+        assert_eq!(logfmt_value("42"), "42");
+        assert_eq!(logfmt_value("true"), "true");
+        assert_eq!(logfmt_value(""), "\"\"");
+        assert_eq!(logfmt_value("hello world"), "\"hello world\"");
+        assert_eq!(logfmt_value("a=b"), "\"a=b\"");
+        assert_eq!(
+            logfmt_value("say \"hi\"\\now\nnext"),
+            "\"say \\\"hi\\\"\\\\now\\nnext\""
+        );
+    }
+
+    #[test]
+    fn test_rfc3339_utc() {
+        assert_eq!(rfc3339_utc(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(rfc3339_utc(1_704_164_645_678), "2024-01-02T03:04:05.678Z");
+        assert_eq!(rfc3339_utc(951_782_400_000), "2000-02-29T00:00:00.000Z");
+        assert_eq!(rfc3339_utc(-1), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn test_rfc3339_utc_precision() {
+        let ms = 1_704_164_645_678;
+        assert_eq!(
+            rfc3339_utc_with(ms, TimePrecision::Seconds),
+            "2024-01-02T03:04:05Z"
+        );
+        assert_eq!(
+            rfc3339_utc_with(ms, TimePrecision::Millis),
+            "2024-01-02T03:04:05.678Z"
+        );
+        assert_eq!(
+            rfc3339_utc_with(ms, TimePrecision::Micros),
+            "2024-01-02T03:04:05.678000Z"
+        );
+        assert_eq!(
+            rfc3339_utc_with(ms, TimePrecision::Nanos),
+            "2024-01-02T03:04:05.678000000Z"
+        );
+    }
+
+    #[test]
+    fn test_key_order() {
+        let mut obj = make_obj(LogType::Warn, &["disk", "almost full"]);
+        obj.tag = "storage".into();
+        obj.title = Some("Heads up".into());
+        obj.error = Some(ErrorInfo {
+            message: "write failed".into(),
+            cause: Some(Box::new(ErrorInfo {
+                message: "no space".into(),
+                ..ErrorInfo::default()
+            })),
+            ..ErrorInfo::default()
+        });
+        let line = LogfmtReporter.format(&obj, &make_ctx(true)).unwrap();
+        assert_eq!(
+            line,
+            "time=2024-01-02T03:04:05.678Z level=warn tag=storage msg=\"disk almost full\" \
+             title=\"Heads up\" cause=\"write failed: no space\""
+        );
+    }
+
+    #[test]
+    fn test_date_disabled_omits_time() {
+        let obj = make_obj(LogType::Info, &["ready"]);
+        let line = LogfmtReporter.format(&obj, &make_ctx(false)).unwrap();
+        assert_eq!(line, "level=info msg=ready");
+    }
+
+    #[test]
+    fn test_repeat_count() {
+        let mut obj = make_obj(LogType::Info, &["ping", "(repeated 3 times)"]);
+        obj.repeated = 3;
+        let line = LogfmtReporter.format(&obj, &make_ctx(false)).unwrap();
+        assert_eq!(line, "level=info msg=ping repeat=3");
+
+        obj.args = vec!["ping".into()];
+        obj.repeated = 1;
+        let line = LogfmtReporter.format(&obj, &make_ctx(false)).unwrap();
+        assert_eq!(line, "level=info msg=ping");
+    }
+
+    #[test]
+    fn test_fields_follow_fixed_keys() {
+        let mut obj = make_obj(LogType::Info, &["listening"]);
+        obj.fields = vec![
+            ("port".into(), "8080".into()),
+            ("addr".into(), "0.0.0.0 ".into()),
+        ];
+        let line = LogfmtReporter.format(&obj, &make_ctx(false)).unwrap();
+        assert_eq!(line, "level=info msg=listening port=8080 addr=\"0.0.0.0 \"");
+    }
+
+    #[test]
+    fn test_group_events() {
+        let mut obj = make_obj(LogType::Log, &["build"]);
+        obj.group_event = Some(GroupEvent::Start);
+        let line = LogfmtReporter.format(&obj, &make_ctx(false)).unwrap();
+        assert_eq!(line, "level=log msg=build group=start");
+
+        obj.group_event = Some(GroupEvent::End);
+        assert_eq!(LogfmtReporter.format(&obj, &make_ctx(false)).unwrap(), "");
+    }
+}
@@ -0,0 +1,130 @@
+//! MetricsReporter — tallies records per log type before delegating.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::constants::LogType;
+use crate::error::ConsolaError;
+use crate::sync::Mutex;
+use crate::types::{GroupEvent, LogContext, LogObject, Reporter};
+
+/// Reporter that counts records by log type and then forwards them to an
+/// inner reporter, whose output it returns unchanged.
+///
+/// A throttle summary counts as the number of entries it stands for, so the
+/// tallies match what was logged rather than what was written. Group ends
+/// are not counted. Clones share their counts, so a clone kept outside the
+/// logger sees every record.
+#[derive(Debug)]
+pub struct MetricsReporter {
+    inner: Box<dyn Reporter>,
+    counts: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl Clone for MetricsReporter {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone_box(),
+            counts: self.counts.clone(),
+        }
+    }
+}
+
+impl MetricsReporter {
+    /// Creates a `MetricsReporter` forwarding to `inner`.
+    pub fn new(inner: impl Reporter + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            counts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Number of records counted so far, keyed by type name (e.g. `"warn"`).
+    pub fn counts(&self) -> HashMap<String, u64> {
+        self.counts.lock().clone()
+    }
+
+    /// Number of records of type `ty` counted so far.
+    pub fn count(&self, ty: LogType) -> u64 {
+        self.counts.lock().get(ty.as_str()).copied().unwrap_or(0)
+    }
+
+    /// Reset every count to zero.
+    pub fn reset(&self) {
+        self.counts.lock().clear();
+    }
+}
+
+impl Reporter for MetricsReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        if log_obj.group_event != Some(GroupEvent::End) {
+            *self
+                .counts
+                .lock()
+                .entry(log_obj.r#type.as_str().to_string())
+                .or_default() += u64::from(log_obj.repeated.max(1));
+        }
+        self.inner.format(log_obj, ctx)
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporters::BasicReporter;
+
+    #[test]
+    fn test_counts_and_delegates() {
+        let metrics = MetricsReporter::new(BasicReporter::new());
+        let mut obj = LogObject::new(LogType::Warn);
+        obj.args = vec!["disk".into()];
+        assert_eq!(
+            metrics.render_to_string(&obj).unwrap(),
+            BasicReporter::new().render_to_string(&obj).unwrap()
+        );
+
+        obj.repeated = 4;
+        metrics.clone_box().render_to_string(&obj).unwrap();
+        obj.group_event = Some(GroupEvent::End);
+        metrics.render_to_string(&obj).unwrap();
+        assert_eq!(metrics.count(LogType::Warn), 5);
+        assert_eq!(metrics.count(LogType::Error), 0);
+
+        metrics.reset();
+        assert!(metrics.counts().is_empty());
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct FlushCounter(Arc<Mutex<u32>>);
+
+    impl Reporter for FlushCounter {
+        fn format(&self, _: &LogObject, _: &LogContext) -> Result<String, ConsolaError> {
+            Ok(String::new())
+        }
+
+        fn clone_box(&self) -> Box<dyn Reporter> {
+            Box::new(self.clone())
+        }
+
+        fn flush(&self) -> std::io::Result<()> {
+            *self.0.lock() += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flush_reaches_inner_reporter() {
+        let inner = FlushCounter::default();
+        let metrics = MetricsReporter::new(inner.clone());
+        metrics.flush().unwrap();
+        assert_eq!(*inner.0.lock(), 1);
+    }
+}
@@ -2,15 +2,46 @@
 //!
 //! Each module provides a reporter implementation with a different formatting style:
 //! [`basic`] for plain text, [`browser`] for web console output, and [`fancy`] for
-//! colored terminal output.
+//! colored terminal output. [`json`] emits newline-delimited JSON, [`logfmt`] emits
+//! `key=value` lines, [`csv`] emits spreadsheet rows, [`multi`] fans one record
+//! out to several reporters, and [`metrics`] counts records per type. With the `proto` feature, `proto` writes binary
+//! protobuf records, and with the `syslog` feature, `syslog` sends entries to syslog.
 
 /// Plain-text reporter that formats log messages without colors or icons.
 pub mod basic;
 /// Browser console reporter with runtime browser detection.
 pub mod browser;
+/// CSV reporter producing a header row and one row per log entry.
+pub mod csv;
 /// Fancy reporter with colors, icons, and rich formatting for terminal output.
 pub mod fancy;
+/// JSON reporter producing one object per log entry.
+pub mod json;
+/// logfmt reporter producing one `key=value` line per log entry.
+pub mod logfmt;
+/// Reporter that counts records per log type before delegating.
+pub mod metrics;
+/// Reporter that forwards each record to several child reporters.
+pub mod multi;
+/// Protobuf reporter producing length-delimited binary records.
+#[cfg(feature = "proto")]
+pub mod proto;
+/// Classified line segments shared by the text reporters.
+pub mod segment;
+/// Syslog reporter sending each entry over a Unix or UDP socket.
+#[cfg(feature = "syslog")]
+pub mod syslog;
 
 pub use basic::BasicReporter;
 pub use browser::BrowserReporter;
+pub use csv::CsvReporter;
 pub use fancy::FancyReporter;
+pub use json::{JsonMessage, JsonReporter};
+pub use logfmt::LogfmtReporter;
+pub use metrics::MetricsReporter;
+pub use multi::MultiReporter;
+#[cfg(feature = "proto")]
+pub use proto::ProtoReporter;
+pub use segment::{Segment, SegmentKind, build_basic_segments, message_start_column};
+#[cfg(feature = "syslog")]
+pub use syslog::SyslogReporter;
@@ -0,0 +1,243 @@
+//! MultiReporter — fans a single record out to several child reporters.
+
+use crate::error::ConsolaError;
+use crate::types::{LogContext, LogObject, Reporter};
+
+/// Reporter that forwards every record to each of its children.
+///
+/// Children run in insertion order and the non-empty outputs of those that
+/// succeed are joined with newlines. A failing child does not stop the
+/// others: its error is written to the `err_sink` as a reporter error, the
+/// same way `Consola` reports a failing top-level reporter. Only when every
+/// child fails is the first error returned.
+#[derive(Debug, Default)]
+pub struct MultiReporter {
+    reporters: Vec<Box<dyn Reporter>>,
+}
+
+impl Clone for MultiReporter {
+    fn clone(&self) -> Self {
+        Self {
+            reporters: self.reporters.iter().map(|r| r.clone_box()).collect(),
+        }
+    }
+}
+
+impl MultiReporter {
+    /// Creates an empty `MultiReporter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a child reporter, returning the builder for chaining.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.reporters.push(Box::new(reporter));
+        self
+    }
+
+    /// Append an already boxed child reporter, returning the builder for chaining.
+    pub fn add_boxed(mut self, reporter: Box<dyn Reporter>) -> Self {
+        self.reporters.push(reporter);
+        self
+    }
+
+    /// Number of child reporters.
+    pub fn len(&self) -> usize {
+        self.reporters.len()
+    }
+
+    /// Whether no child reporters have been added.
+    pub fn is_empty(&self) -> bool {
+        self.reporters.is_empty()
+    }
+}
+
+impl Reporter for MultiReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        let mut outputs = Vec::with_capacity(self.reporters.len());
+        let mut succeeded = false;
+        let mut latched = false;
+        let mut errors = Vec::new();
+        for reporter in &self.reporters {
+            match reporter.format(log_obj, ctx) {
+                Ok(formatted) => {
+                    succeeded = true;
+                    if !formatted.is_empty() {
+                        outputs.push(formatted);
+                    }
+                }
+                Err(ConsolaError::SinkLatched) => latched = true,
+                Err(e) => errors.push(e),
+            }
+        }
+        let mut errors = errors.into_iter();
+        // With no child left to write the entry, the first error is the
+        // reporter's own and `Consola` reports it.
+        let first_err = if succeeded {
+            None
+        } else {
+            errors
+                .next()
+                .or_else(|| latched.then_some(ConsolaError::SinkLatched))
+        };
+        for e in errors {
+            let _ = ctx
+                .options
+                .err_sink
+                .write_line(&format!("[consola] reporter error: {}", e));
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(outputs.join("\n")),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    /// Flush every child, returning the first error after trying them all.
+    fn flush(&self) -> std::io::Result<()> {
+        let mut first_err = None;
+        for reporter in &self.reporters {
+            if let Err(e) = reporter.flush() {
+                first_err.get_or_insert(e);
+            }
+        }
+        first_err.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::ConsolaOptions;
+    use crate::util::sink::TestSink;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default)]
+    struct MemoryReporter {
+        records: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Reporter for MemoryReporter {
+        fn format(&self, log_obj: &LogObject, _ctx: &LogContext) -> Result<String, ConsolaError> {
+            let line = log_obj.args.join(" ");
+            self.records.lock().unwrap().push(line.clone());
+            Ok(line)
+        }
+
+        fn clone_box(&self) -> Box<dyn Reporter> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct FailingReporter(&'static str);
+
+    impl Reporter for FailingReporter {
+        fn format(&self, _log_obj: &LogObject, _ctx: &LogContext) -> Result<String, ConsolaError> {
+            Err(ConsolaError::Reporter(self.0.into()))
+        }
+
+        fn clone_box(&self) -> Box<dyn Reporter> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn make_ctx() -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        }
+    }
+
+    fn make_ctx_with_err_sink(err_sink: &TestSink) -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions {
+                err_sink: Arc::new(err_sink.clone()),
+                ..ConsolaOptions::default()
+            }),
+        }
+    }
+
+    fn make_log_obj(msg: &str) -> LogObject {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec![msg.to_string()];
+        obj
+    }
+
+    #[test]
+    fn test_multi_reporter_fans_out() {
+        let a = MemoryReporter::default();
+        let b = MemoryReporter::default();
+        let multi = MultiReporter::new().add(a.clone()).add(b.clone());
+        let ctx = make_ctx();
+        assert_eq!(
+            multi.format(&make_log_obj("one"), &ctx).unwrap(),
+            "one\none"
+        );
+        multi.format(&make_log_obj("two"), &ctx).unwrap();
+        assert_eq!(*a.records.lock().unwrap(), vec!["one", "two"]);
+        assert_eq!(*b.records.lock().unwrap(), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_multi_reporter_keeps_output_of_succeeding_children() {
+        let after = MemoryReporter::default();
+        let multi = MultiReporter::new()
+            .add(FailingReporter("first"))
+            .add(after.clone())
+            .add(FailingReporter("second"));
+        let err_sink = TestSink::new();
+        let out = multi
+            .format(&make_log_obj("x"), &make_ctx_with_err_sink(&err_sink))
+            .unwrap();
+        assert_eq!(out, "x");
+        assert_eq!(*after.records.lock().unwrap(), vec!["x"]);
+        assert_eq!(
+            err_sink.lines(),
+            vec![
+                "[consola] reporter error: reporter error: first",
+                "[consola] reporter error: reporter error: second",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_reporter_returns_first_error_when_all_fail() {
+        let multi = MultiReporter::new()
+            .add(FailingReporter("first"))
+            .add(FailingReporter("second"));
+        let err_sink = TestSink::new();
+        let err = multi
+            .format(&make_log_obj("x"), &make_ctx_with_err_sink(&err_sink))
+            .unwrap_err();
+        assert_eq!(err, ConsolaError::Reporter("first".into()));
+        assert_eq!(
+            err_sink.lines(),
+            vec!["[consola] reporter error: reporter error: second"]
+        );
+    }
+
+    #[test]
+    fn test_multi_reporter_empty() {
+        let multi = MultiReporter::new();
+        assert!(multi.is_empty());
+        assert_eq!(multi.format(&make_log_obj("x"), &make_ctx()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_multi_reporter_clone_box() {
+        let multi = MultiReporter::new()
+            .add(MemoryReporter::default())
+            .add_boxed(Box::new(MemoryReporter::default()));
+        let cloned = multi.clone_box();
+        assert_eq!(multi.len(), 2);
+        assert_eq!(
+            cloned.format(&make_log_obj("x"), &make_ctx()).unwrap(),
+            "x\nx"
+        );
+    }
+}
@@ -0,0 +1,556 @@
+//! ProtoReporter — writes each log entry as a length-delimited protobuf record.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::constants::LogLevel;
+use crate::error::ConsolaError;
+use crate::types::{ErrorInfo, GroupEvent, LogContext, LogObject, Reporter};
+use crate::util::sink::{RecordSink, Sink, WriterSink};
+
+/// The `.proto` definition of the records written by [`ProtoReporter`].
+pub const SCHEMA: &str = include_str!("../../proto/consola.proto");
+
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const LEN: u8 = 2;
+const FIXED32: u8 = 5;
+
+/// Deepest error cause chain [`decode_records`] accepts.
+pub const MAX_ERROR_DEPTH: usize = 64;
+
+/// Writes log entries as binary [protobuf](https://protobuf.dev) records, a
+/// compact alternative to [`JsonReporter`](crate::reporters::JsonReporter)
+/// for ingestion pipelines.
+///
+/// Each entry is encoded as a `LogRecord` (see [`SCHEMA`]) prefixed with its
+/// length as a varint, and written to the reporter's own sink; the line
+/// handed back to `Consola` is empty, so nothing reaches the text sinks.
+/// Like `Consola`'s sinks, the sink is flushed for entries at or above
+/// `flush_level` and stops being written after `write_failure_limit`
+/// consecutive failures. Group ends produce no record. Use
+/// [`decode_records`] to read the stream back.
+#[derive(Debug, Clone)]
+pub struct ProtoReporter {
+    out: RecordSink,
+}
+
+impl ProtoReporter {
+    /// Creates a reporter writing records to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self::with_sink(Arc::new(WriterSink::new(writer)))
+    }
+
+    /// Creates a reporter writing records to `sink`, e.g. an
+    /// [`AsyncSink`](crate::AsyncSink) to keep encoding output off the
+    /// logging thread. The sink must support
+    /// [`write_bytes`](Sink::write_bytes).
+    pub fn with_sink(sink: Arc<dyn Sink>) -> Self {
+        Self {
+            out: RecordSink::new(sink),
+        }
+    }
+
+    /// Encode `log_obj` as a `LogRecord` message body, without the length prefix.
+    pub fn encode(log_obj: &LogObject) -> Vec<u8> {
+        let mut buf = Vec::new();
+        put_int(&mut buf, 1, i64::from(log_obj.level));
+        put_str(&mut buf, 2, log_obj.r#type.as_str());
+        put_str(&mut buf, 3, &log_obj.tag);
+        put_opt_str(&mut buf, 4, log_obj.message.as_deref());
+        put_opt_str(&mut buf, 5, log_obj.additional.as_deref());
+        for arg in &log_obj.args {
+            put_bytes(&mut buf, 6, arg.as_bytes());
+        }
+        put_int(&mut buf, 7, log_obj.timestamp_ms);
+        put_opt_str(&mut buf, 8, log_obj.title.as_deref());
+        for (key, value) in &log_obj.fields {
+            let mut field = Vec::new();
+            put_str(&mut field, 1, key);
+            put_str(&mut field, 2, value);
+            put_bytes(&mut buf, 9, &field);
+        }
+        put_int(&mut buf, 10, i64::from(log_obj.repeated));
+        if let Some(err) = &log_obj.error {
+            put_bytes(&mut buf, 11, &encode_error(err));
+        }
+        put_opt_str(&mut buf, 12, log_obj.trace_id.as_deref());
+        buf
+    }
+
+    /// Encode `log_obj` as a length-delimited `LogRecord`.
+    pub fn encode_delimited(log_obj: &LogObject) -> Vec<u8> {
+        let body = Self::encode(log_obj);
+        let mut buf = Vec::with_capacity(body.len() + 5);
+        put_varint(&mut buf, body.len() as u64);
+        buf.extend_from_slice(&body);
+        buf
+    }
+}
+
+impl Reporter for ProtoReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        if log_obj.group_event != Some(GroupEvent::End) {
+            self.out.write(
+                &Self::encode_delimited(log_obj),
+                log_obj.level,
+                &ctx.options,
+            )?;
+        }
+        Ok(String::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// A `LogRecord` read back by [`decode_records`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtoRecord {
+    /// Numeric level.
+    pub level: LogLevel,
+    /// Log type name, e.g. `info`.
+    pub r#type: String,
+    /// Tag, empty if none was set.
+    pub tag: String,
+    /// Primary message text, if any.
+    pub message: Option<String>,
+    /// Secondary text, if any.
+    pub additional: Option<String>,
+    /// Positional arguments.
+    pub args: Vec<String>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: i64,
+    /// Title, if any.
+    pub title: Option<String>,
+    /// Structured fields in insertion order.
+    pub fields: Vec<(String, String)>,
+    /// Number of throttled duplicates the record summarizes.
+    pub repeated: u32,
+    /// Attached error and its cause chain.
+    pub error: Option<ErrorInfo>,
+    /// Correlation id, if any.
+    pub trace_id: Option<String>,
+}
+
+/// Decode a stream of length-delimited `LogRecord`s as written by
+/// [`ProtoReporter`].
+///
+/// Fields not in [`SCHEMA`] are skipped. Returns
+/// [`ConsolaError::Decode`] for truncated or malformed input, including an
+/// error whose cause chain is deeper than [`MAX_ERROR_DEPTH`].
+pub fn decode_records(mut buf: &[u8]) -> Result<Vec<ProtoRecord>, ConsolaError> {
+    let mut records = Vec::new();
+    while !buf.is_empty() {
+        let len = read_varint(&mut buf)? as usize;
+        let body = take(&mut buf, len)?;
+        records.push(decode_record(body)?);
+    }
+    Ok(records)
+}
+
+fn decode_record(mut buf: &[u8]) -> Result<ProtoRecord, ConsolaError> {
+    let mut record = ProtoRecord::default();
+    while !buf.is_empty() {
+        let (field, wire) = read_key(&mut buf)?;
+        match (field, wire) {
+            (1, VARINT) => record.level = read_varint(&mut buf)? as i64 as LogLevel,
+            (2, LEN) => record.r#type = read_string(&mut buf)?,
+            (3, LEN) => record.tag = read_string(&mut buf)?,
+            (4, LEN) => record.message = Some(read_string(&mut buf)?),
+            (5, LEN) => record.additional = Some(read_string(&mut buf)?),
+            (6, LEN) => record.args.push(read_string(&mut buf)?),
+            (7, VARINT) => record.timestamp_ms = read_varint(&mut buf)? as i64,
+            (8, LEN) => record.title = Some(read_string(&mut buf)?),
+            (9, LEN) => record.fields.push(decode_field(read_len(&mut buf)?)?),
+            (10, VARINT) => record.repeated = read_varint(&mut buf)? as u32,
+            (11, LEN) => record.error = Some(decode_error(read_len(&mut buf)?)?),
+            (12, LEN) => record.trace_id = Some(read_string(&mut buf)?),
+            _ => skip(&mut buf, wire)?,
+        }
+    }
+    Ok(record)
+}
+
+fn decode_field(mut buf: &[u8]) -> Result<(String, String), ConsolaError> {
+    let (mut key, mut value) = (String::new(), String::new());
+    while !buf.is_empty() {
+        match read_key(&mut buf)? {
+            (1, LEN) => key = read_string(&mut buf)?,
+            (2, LEN) => value = read_string(&mut buf)?,
+            (_, wire) => skip(&mut buf, wire)?,
+        }
+    }
+    Ok((key, value))
+}
+
+/// Encode `err` with each cause nested in field 4 of its parent, building
+/// from the innermost cause outwards so deep chains need no recursion.
+fn encode_error(err: &ErrorInfo) -> Vec<u8> {
+    let chain: Vec<&ErrorInfo> = std::iter::successors(Some(err), |e| e.cause.as_deref()).collect();
+    chain.into_iter().rev().fold(Vec::new(), |cause, err| {
+        let mut buf = Vec::new();
+        put_str(&mut buf, 1, &err.message);
+        put_opt_str(&mut buf, 2, err.stack.as_deref());
+        put_opt_str(&mut buf, 3, err.backtrace.as_deref());
+        if err.cause.is_some() {
+            put_bytes(&mut buf, 4, &cause);
+        }
+        buf
+    })
+}
+
+/// Decode an error and its cause chain iteratively, rejecting chains deeper
+/// than [`MAX_ERROR_DEPTH`] so untrusted input cannot exhaust the stack.
+fn decode_error(buf: &[u8]) -> Result<ErrorInfo, ConsolaError> {
+    let mut chain = Vec::new();
+    let mut next = Some(buf);
+    while let Some(mut buf) = next.take() {
+        if chain.len() == MAX_ERROR_DEPTH {
+            return Err(decode_error_msg("error chain too deep"));
+        }
+        let mut err = ErrorInfo::default();
+        while !buf.is_empty() {
+            match read_key(&mut buf)? {
+                (1, LEN) => err.message = read_string(&mut buf)?,
+                (2, LEN) => err.stack = Some(read_string(&mut buf)?),
+                (3, LEN) => err.backtrace = Some(read_string(&mut buf)?),
+                (4, LEN) => next = Some(read_len(&mut buf)?),
+                (_, wire) => skip(&mut buf, wire)?,
+            }
+        }
+        chain.push(err);
+    }
+    let mut chain = chain.into_iter().rev();
+    let innermost = chain.next().unwrap_or_default();
+    Ok(chain.fold(innermost, |cause, mut err| {
+        err.cause = Some(Box::new(cause));
+        err
+    }))
+}
+
+fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn put_key(buf: &mut Vec<u8>, field: u32, wire: u8) {
+    put_varint(buf, (u64::from(field) << 3) | u64::from(wire));
+}
+
+/// Write an `int32`/`int64`/`uint32` field, omitting the proto3 default `0`.
+/// Negative values are sign-extended to ten bytes, as protobuf requires.
+fn put_int(buf: &mut Vec<u8>, field: u32, value: i64) {
+    if value != 0 {
+        put_key(buf, field, VARINT);
+        put_varint(buf, value as u64);
+    }
+}
+
+fn put_bytes(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    put_key(buf, field, LEN);
+    put_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Write a plain `string` field, omitting the proto3 default `""`.
+fn put_str(buf: &mut Vec<u8>, field: u32, value: &str) {
+    if !value.is_empty() {
+        put_bytes(buf, field, value.as_bytes());
+    }
+}
+
+/// Write an `optional string` field, keeping `Some("")` distinct from `None`.
+fn put_opt_str(buf: &mut Vec<u8>, field: u32, value: Option<&str>) {
+    if let Some(value) = value {
+        put_bytes(buf, field, value.as_bytes());
+    }
+}
+
+fn decode_error_msg(what: &str) -> ConsolaError {
+    ConsolaError::Decode(what.to_string())
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, ConsolaError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf
+            .split_first()
+            .ok_or_else(|| decode_error_msg("truncated varint"))?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(decode_error_msg("varint too long"))
+}
+
+fn read_key(buf: &mut &[u8]) -> Result<(u64, u8), ConsolaError> {
+    let key = read_varint(buf)?;
+    Ok((key >> 3, (key & 0x7) as u8))
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], ConsolaError> {
+    if buf.len() < len {
+        return Err(decode_error_msg("truncated record"));
+    }
+    let (head, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(head)
+}
+
+fn read_len<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], ConsolaError> {
+    let len = read_varint(buf)? as usize;
+    take(buf, len)
+}
+
+fn read_string(buf: &mut &[u8]) -> Result<String, ConsolaError> {
+    String::from_utf8(read_len(buf)?.to_vec()).map_err(|_| decode_error_msg("invalid UTF-8"))
+}
+
+fn skip(buf: &mut &[u8], wire: u8) -> Result<(), ConsolaError> {
+    match wire {
+        VARINT => read_varint(buf).map(drop),
+        FIXED64 => take(buf, 8).map(drop),
+        LEN => read_len(buf).map(drop),
+        FIXED32 => take(buf, 4).map(drop),
+        _ => Err(decode_error_msg("unsupported wire type")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{LogType, log_levels};
+    use crate::types::ConsolaOptions;
+
+    /// Writer appending to a buffer the test keeps a handle to.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn make_ctx() -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_every_field() {
+        let mut obj = LogObject::new(LogType::Error);
+        obj.level = log_levels::SILENT;
+        obj.tag = "db".into();
+        obj.message = Some(String::new());
+        obj.additional = Some("more".into());
+        obj.args = vec!["query failed".into(), "ünïcode".into()];
+        obj.timestamp_ms = -1;
+        obj.title = Some("Oops".into());
+        obj.fields = vec![("table".into(), "users".into()), ("ms".into(), "".into())];
+        obj.repeated = 3;
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            stack: Some("at main (src/main.rs:1:1)".into()),
+            backtrace: None,
+            cause: Some(Box::new(ErrorInfo {
+                message: "socket closed".into(),
+                ..ErrorInfo::default()
+            })),
+        });
+        obj.trace_id = Some("4bf92f35".into());
+
+        let records = decode_records(&ProtoReporter::encode_delimited(&obj)).unwrap();
+        assert_eq!(
+            records,
+            vec![ProtoRecord {
+                level: obj.level,
+                r#type: "error".into(),
+                tag: obj.tag.clone(),
+                message: obj.message.clone(),
+                additional: obj.additional.clone(),
+                args: obj.args.clone(),
+                timestamp_ms: -1,
+                title: obj.title.clone(),
+                fields: obj.fields.clone(),
+                repeated: 3,
+                error: obj.error.clone(),
+                trace_id: obj.trace_id.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_defaults_are_omitted() {
+        let mut obj = LogObject::new(LogType::Warn);
+        obj.timestamp_ms = 0;
+        obj.level = 0;
+        // Only the type name is written: key, length, "warn".
+        assert_eq!(ProtoReporter::encode(&obj), b"\x12\x04warn");
+    }
+
+    #[test]
+    fn test_reporter_writes_delimited_stream() {
+        let buf = SharedBuf::default();
+        let r = ProtoReporter::new(buf.clone());
+        for msg in ["one", "two"] {
+            let mut obj = LogObject::new(LogType::Info);
+            obj.args = vec![msg.into()];
+            assert_eq!(r.format(&obj, &make_ctx()).unwrap(), "");
+        }
+        let mut end = LogObject::new(LogType::Log);
+        end.group_event = Some(GroupEvent::End);
+        r.format(&end, &make_ctx()).unwrap();
+
+        let records = decode_records(&buf.0.lock().unwrap()).unwrap();
+        let args: Vec<&str> = records.iter().map(|r| r.args[0].as_str()).collect();
+        assert_eq!(args, vec!["one", "two"]);
+        assert!(records.iter().all(|r| r.level == log_levels::INFO));
+    }
+
+    #[test]
+    fn test_decode_skips_unknown_fields() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["hi".into()];
+        let mut body = ProtoReporter::encode(&obj);
+        // Field 99 as a varint, then field 100 as a length-delimited blob.
+        body.extend_from_slice(&[0x98, 0x06, 0x2a, 0xa2, 0x06, 0x01, 0xff]);
+        let mut framed = Vec::new();
+        put_varint(&mut framed, body.len() as u64);
+        framed.extend_from_slice(&body);
+        assert_eq!(decode_records(&framed).unwrap()[0].args, vec!["hi"]);
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_input() {
+        let obj = LogObject::new(LogType::Info);
+        let framed = ProtoReporter::encode_delimited(&obj);
+        assert_eq!(
+            decode_records(&framed[..framed.len() - 1]),
+            Err(ConsolaError::Decode("truncated record".into()))
+        );
+    }
+
+    fn error_chain(depth: usize) -> ErrorInfo {
+        (1..depth).fold(
+            ErrorInfo {
+                message: "root".into(),
+                ..ErrorInfo::default()
+            },
+            |cause, i| ErrorInfo {
+                message: format!("level {}", i),
+                cause: Some(Box::new(cause)),
+                ..ErrorInfo::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_decode_caps_error_chain_depth() {
+        let mut obj = LogObject::new(LogType::Error);
+        obj.error = Some(error_chain(MAX_ERROR_DEPTH));
+        let records = decode_records(&ProtoReporter::encode_delimited(&obj)).unwrap();
+        assert_eq!(records[0].error, obj.error);
+
+        obj.error = Some(error_chain(MAX_ERROR_DEPTH + 1));
+        assert_eq!(
+            decode_records(&ProtoReporter::encode_delimited(&obj)),
+            Err(ConsolaError::Decode("error chain too deep".into()))
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_deeply_nested_input() {
+        // Nested cause fields far beyond `MAX_ERROR_DEPTH`.
+        let body = (0..10_000).fold(Vec::new(), |inner, _| {
+            let mut buf = Vec::new();
+            put_bytes(&mut buf, 4, &inner);
+            buf
+        });
+        let mut record = Vec::new();
+        put_bytes(&mut record, 11, &body);
+        let mut framed = Vec::new();
+        put_varint(&mut framed, record.len() as u64);
+        framed.extend_from_slice(&record);
+        assert_eq!(
+            decode_records(&framed),
+            Err(ConsolaError::Decode("error chain too deep".into()))
+        );
+    }
+
+    #[derive(Default)]
+    struct BrokenPipe(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_latches_after_write_failure_limit() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let r = ProtoReporter::new(BrokenPipe(attempts.clone()));
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                write_failure_limit: 2,
+                ..ConsolaOptions::default()
+            }),
+        };
+        let obj = LogObject::new(LogType::Info);
+        assert!(matches!(
+            r.format(&obj, &ctx),
+            Err(ConsolaError::Reporter(_))
+        ));
+        assert!(matches!(
+            r.format(&obj, &ctx),
+            Err(ConsolaError::Reporter(_))
+        ));
+        assert_eq!(r.format(&obj, &ctx), Err(ConsolaError::SinkLatched));
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_flush_reaches_buffered_writer() {
+        let buf = SharedBuf::default();
+        let r = ProtoReporter::new(std::io::BufWriter::new(buf.clone()));
+        let obj = LogObject::new(LogType::Info);
+        r.format(&obj, &make_ctx()).unwrap();
+        assert!(buf.0.lock().unwrap().is_empty());
+        r.flush().unwrap();
+        assert_eq!(decode_records(&buf.0.lock().unwrap()).unwrap().len(), 1);
+
+        // Errors are flushed as they are written under the default `flush_level`.
+        r.format(&LogObject::new(LogType::Error), &make_ctx())
+            .unwrap();
+        assert_eq!(decode_records(&buf.0.lock().unwrap()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_schema_lists_record_fields() {
+        assert!(SCHEMA.contains("message LogRecord"));
+        assert!(SCHEMA.contains("optional string trace_id = 12;"));
+    }
+}
@@ -0,0 +1,357 @@
+//! Classified parts of a text log line, shared by the text reporters.
+
+use crate::reporters::basic::BasicReporter;
+use crate::types::{FormatOptions, LogObject};
+use crate::util::string::{collapse_whitespace, string_width};
+
+/// Role of a [`Segment`] within a log line.
+///
+/// Reporters style segments by kind rather than by inspecting their text, so
+/// a message that happens to look like a badge or a repeat counter is still
+/// rendered as a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentKind {
+    /// Shortened correlation id of the entry.
+    TraceId,
+    /// Time the entry was logged.
+    Timestamp,
+    /// Log type label, e.g. `info`.
+    TypeBadge,
+    /// Entry tag.
+    Tag,
+    /// Logger-wide label of the entry, see [`LogObject::prefix`].
+    Prefix,
+    /// First line of the message, including an inline error chain.
+    Message,
+    /// `(repeated N times)` counter of a throttle summary.
+    Repetition,
+    /// Structured fields rendered as `key=value` pairs.
+    Meta,
+    /// Message lines after the first.
+    Additional,
+    /// Structured fields as an aligned block below the message, with
+    /// [`FormatOptions::fields_block`].
+    Fields,
+    /// Error chain with stack traces, rendered below the line.
+    Stack,
+    /// Icon shown in place of the type label.
+    Icon,
+    /// Reporter-specific content.
+    Custom,
+}
+
+/// A piece of unstyled log line text and its role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Role of the text.
+    pub kind: SegmentKind,
+    /// Unstyled text, without brackets or colors.
+    pub text: String,
+}
+
+impl Segment {
+    /// Creates a segment of `kind`.
+    pub fn new(kind: SegmentKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// Number of leading characters of a trace id shown by text reporters.
+pub const TRACE_ID_DISPLAY_LEN: usize = 8;
+
+/// Split `log_obj` into segments in display order.
+///
+/// Produces `TraceId` (the first [`TRACE_ID_DISPLAY_LEN`] characters of the
+/// id), `TypeBadge`, `Tag`, `Prefix`, `Message`, `Meta`, `Repetition`,
+/// `Additional`, `Fields` and `Stack` segments, omitting empty ones. Fields
+/// form either `Meta` or, with `fields_block`, `Fields`. In compact mode
+/// the message is collapsed onto one line and errors are inlined, so neither
+/// `Additional`, `Fields` nor `Stack` appears. `Timestamp`, `Icon` and `Custom` are
+/// left to reporters that show them.
+pub fn build_basic_segments(log_obj: &LogObject, opts: &FormatOptions) -> Vec<Segment> {
+    let opts = opts.effective();
+    let basic = BasicReporter::new();
+
+    let mut args: &[String] = &log_obj.args;
+    let mut counter = None;
+    if log_obj.repeated > 1
+        && let Some((last, rest)) = args.split_last()
+    {
+        counter = Some(last.clone());
+        args = rest;
+    }
+    let text = basic.format_args(args, &opts);
+
+    let (message, additional) = if opts.compact {
+        let message = basic.with_inline_error(text, log_obj, &opts);
+        (collapse_whitespace(&message), None)
+    } else {
+        match text.split_once('\n') {
+            Some((first, rest)) => (
+                basic.with_inline_error(first.trim_end().to_string(), log_obj, &opts),
+                Some(rest.to_string()),
+            ),
+            None => (basic.with_inline_error(text, log_obj, &opts), None),
+        }
+    };
+
+    let (meta, fields) = if opts.fields_block && !opts.compact {
+        (None, Some(fields_block(&log_obj.fields, ":")))
+    } else {
+        let meta = log_obj
+            .fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (Some(meta), None)
+    };
+
+    let stack = log_obj
+        .error
+        .as_ref()
+        .filter(|_| !opts.inline_error_chain)
+        .map(|err| BasicReporter::format_error(err, &opts, 0));
+
+    let trace_id = log_obj
+        .trace_id
+        .as_ref()
+        .map(|id| id.chars().take(TRACE_ID_DISPLAY_LEN).collect());
+
+    [
+        (SegmentKind::TraceId, trace_id),
+        (
+            SegmentKind::TypeBadge,
+            Some(log_obj.r#type.as_str().to_string()),
+        ),
+        (SegmentKind::Tag, Some(log_obj.tag.clone())),
+        (SegmentKind::Prefix, log_obj.prefix.clone()),
+        (SegmentKind::Message, Some(message)),
+        (SegmentKind::Meta, meta),
+        (SegmentKind::Repetition, counter),
+        (SegmentKind::Additional, additional),
+        (SegmentKind::Fields, fields),
+        (SegmentKind::Stack, stack),
+    ]
+    .into_iter()
+    .filter_map(|(kind, text)| {
+        text.filter(|t| !t.is_empty())
+            .map(|t| Segment::new(kind, t))
+    })
+    .collect()
+}
+
+/// Render `fields` one per line as `key: value`, indented by two columns,
+/// with values aligned past the longest key. `separator` follows each key
+/// and may be styled. Lines of multi-line values continue under the value.
+pub(crate) fn fields_block(fields: &[(String, String)], separator: &str) -> String {
+    let key_width = fields
+        .iter()
+        .map(|(k, _)| string_width(k))
+        .max()
+        .unwrap_or(0);
+    let value_indent = format!("\n{}", " ".repeat(3 + key_width + string_width(separator)));
+    fields
+        .iter()
+        .map(|(k, v)| {
+            let pad = " ".repeat(key_width - string_width(k));
+            format!(
+                "  {}{}{} {}",
+                k,
+                separator,
+                pad,
+                v.replace('\n', &value_indent)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Display column at which [`BasicReporter`] starts the message of
+/// `log_obj`, i.e. the width of the trace id, type, tag and prefix plus the
+/// [`separator`](FormatOptions::separator) after each. `0` when there is no
+/// prefix.
+///
+/// Colors do not count, and a logger name shown before the prefix adds its
+/// own width plus the separator.
+pub fn message_start_column(log_obj: &LogObject, opts: &FormatOptions) -> usize {
+    let opts = opts.effective();
+    build_basic_segments(log_obj, &opts)
+        .iter()
+        .filter_map(|segment| match segment.kind {
+            SegmentKind::TraceId => Some(1 + string_width(&segment.text)),
+            SegmentKind::TypeBadge => Some(2 + string_width(&opts.type_case.apply(&segment.text))),
+            SegmentKind::Tag => Some(2 + string_width(&segment.text)),
+            SegmentKind::Prefix => Some(1 + string_width(&segment.text)),
+            _ => None,
+        })
+        .map(|width| width + string_width(&opts.separator))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::ErrorInfo;
+
+    fn kinds(segments: &[Segment]) -> Vec<SegmentKind> {
+        segments.iter().map(|s| s.kind).collect()
+    }
+
+    fn full_obj() -> LogObject {
+        let mut obj = LogObject::new(LogType::Warn);
+        obj.tag = "db".into();
+        obj.args = vec![
+            "slow query".into(),
+            "\nSELECT 1".into(),
+            "(repeated 2 times)".into(),
+        ];
+        obj.repeated = 2;
+        obj.fields = vec![("ms".into(), "950".into())];
+        obj.error = Some(ErrorInfo {
+            message: "timeout".into(),
+            ..ErrorInfo::default()
+        });
+        obj
+    }
+
+    #[test]
+    fn test_segments_carry_kinds() {
+        let segments = build_basic_segments(&full_obj(), &FormatOptions::default());
+        assert_eq!(
+            segments,
+            vec![
+                Segment::new(SegmentKind::TypeBadge, "warn"),
+                Segment::new(SegmentKind::Tag, "db"),
+                Segment::new(SegmentKind::Message, "slow query"),
+                Segment::new(SegmentKind::Meta, "ms=950"),
+                Segment::new(SegmentKind::Repetition, "(repeated 2 times)"),
+                Segment::new(SegmentKind::Additional, "SELECT 1"),
+                Segment::new(SegmentKind::Stack, "timeout"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_segments_are_omitted() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["hello".into()];
+        let segments = build_basic_segments(&obj, &FormatOptions::default());
+        assert_eq!(
+            kinds(&segments),
+            vec![SegmentKind::TypeBadge, SegmentKind::Message]
+        );
+    }
+
+    #[test]
+    fn test_compact_folds_additional_and_stack_into_message() {
+        let opts = FormatOptions {
+            compact: true,
+            ..FormatOptions::default()
+        };
+        let segments = build_basic_segments(&full_obj(), &opts);
+        assert_eq!(
+            kinds(&segments),
+            vec![
+                SegmentKind::TypeBadge,
+                SegmentKind::Tag,
+                SegmentKind::Message,
+                SegmentKind::Meta,
+                SegmentKind::Repetition,
+            ]
+        );
+        assert_eq!(segments[2].text, "slow query SELECT 1: timeout");
+    }
+
+    #[test]
+    fn test_fields_block_aligns_values() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["config".into()];
+        obj.fields = vec![
+            ("host".into(), "localhost".into()),
+            ("timeout".into(), "30s".into()),
+            ("motd".into(), "hello\nworld".into()),
+        ];
+        let opts = FormatOptions {
+            fields_block: true,
+            ..FormatOptions::default()
+        };
+        let segments = build_basic_segments(&obj, &opts);
+        assert_eq!(
+            segments.last().unwrap(),
+            &Segment::new(
+                SegmentKind::Fields,
+                "  host:    localhost\n  timeout: 30s\n  motd:    hello\n           world"
+            )
+        );
+        assert!(!kinds(&segments).contains(&SegmentKind::Meta));
+
+        let compact = FormatOptions {
+            compact: true,
+            ..opts
+        };
+        let kinds = kinds(&build_basic_segments(&obj, &compact));
+        assert!(kinds.contains(&SegmentKind::Meta) && !kinds.contains(&SegmentKind::Fields));
+    }
+
+    #[test]
+    fn test_trace_id_is_shortened_and_leads() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["hello".into()];
+        obj.trace_id = Some("4bf92f3577b34da6a3ce929d0e0e4736".into());
+        let segments = build_basic_segments(&obj, &FormatOptions::default());
+        assert_eq!(segments[0], Segment::new(SegmentKind::TraceId, "4bf92f35"));
+    }
+
+    #[test]
+    fn test_message_start_column_matches_rendered_prefix() {
+        use crate::types::TypeCase;
+        use crate::util::string::strip_ansi;
+
+        let mut traced = LogObject::new(LogType::Info);
+        traced.tag = "数据库".into();
+        traced.args = vec!["hello".into()];
+        traced.trace_id = Some("4bf92f3577b34da6".into());
+        traced.prefix = Some("api".into());
+        let configs = [
+            FormatOptions::default(),
+            FormatOptions {
+                colors: true,
+                type_case: TypeCase::Upper,
+                ..FormatOptions::default()
+            },
+            FormatOptions {
+                compact: true,
+                ..FormatOptions::default()
+            },
+            FormatOptions {
+                separator: " | ".into(),
+                ..FormatOptions::default()
+            },
+        ];
+        for obj in [full_obj(), traced] {
+            let mut obj = obj;
+            obj.args[0] = "MSG".into();
+            for opts in &configs {
+                let line = strip_ansi(&BasicReporter::new().format_log_obj(&obj, opts));
+                let rendered = string_width(&line[..line.find("MSG").unwrap()]);
+                assert_eq!(message_start_column(&obj, opts), rendered, "{:?}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn test_counter_only_taken_from_throttle_summaries() {
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["(x marks the spot)".into()];
+        let segments = build_basic_segments(&obj, &FormatOptions::default());
+        assert_eq!(
+            segments[1],
+            Segment::new(SegmentKind::Message, "(x marks the spot)")
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! SyslogReporter — sends each log entry to syslog.
+
+use std::io::Write;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+
+use syslog::{Formatter3164, LogFormat};
+
+use crate::constants::{LogType, log_levels};
+use crate::error::ConsolaError;
+use crate::reporters::segment::{SegmentKind, build_basic_segments};
+use crate::types::{FormatOptions, GroupEvent, LogContext, LogObject, Reporter};
+use crate::util::sink::{RecordSink, Sink, WriterSink};
+
+pub use syslog::{Facility, Severity};
+
+/// The syslog severity of `log_obj`.
+///
+/// `fatal` entries map to `LOG_EMERG`; other entries map by level: errors to
+/// `LOG_ERR`, warnings to `LOG_WARNING`, `log` and `info`-level entries to
+/// `LOG_INFO`, and debug and trace entries to `LOG_DEBUG`.
+pub fn severity_for(log_obj: &LogObject) -> Severity {
+    if log_obj.r#type == LogType::Fatal {
+        return Severity::LOG_EMERG;
+    }
+    match log_obj.level {
+        level if level <= log_levels::ERROR => Severity::LOG_ERR,
+        log_levels::WARN => Severity::LOG_WARNING,
+        level if level <= log_levels::INFO => Severity::LOG_INFO,
+        _ => Severity::LOG_DEBUG,
+    }
+}
+
+/// Sends log entries to syslog as RFC 3164 messages.
+///
+/// The entry's tag becomes the program name of the message, falling back to
+/// the executable name for untagged entries. The message is the entry's
+/// one-line compact rendering: prefix, message, fields and, for throttle
+/// summaries, the `(repeated N times)` counter. Each message goes to the
+/// reporter's own [`Sink`] as one record; like
+/// [`ProtoReporter`](crate::reporters::ProtoReporter), the line handed back
+/// to `Consola` is empty, so nothing reaches the text sinks. Group ends
+/// produce no message. Requires the `syslog` Cargo feature.
+///
+/// The sink follows the instance's `flush_level` and `write_failure_limit`
+/// like `Consola`'s own sinks.
+#[derive(Debug, Clone)]
+pub struct SyslogReporter {
+    out: RecordSink,
+    formatter: Formatter3164,
+}
+
+impl SyslogReporter {
+    /// Creates a reporter writing messages to `writer`, e.g. a socket set up
+    /// by the caller. The writer is flushed after every message, so
+    /// buffered sockets deliver each one right away.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self::with_sink(Arc::new(WriterSink::new(writer).auto_flush()))
+    }
+
+    /// Creates a reporter sending messages to `sink`, e.g. an
+    /// [`AsyncSink`](crate::AsyncSink) over an auto-flushing
+    /// [`WriterSink`] to keep socket writes off the logging thread. The sink
+    /// must support [`write_bytes`](Sink::write_bytes).
+    pub fn with_sink(sink: Arc<dyn Sink>) -> Self {
+        Self {
+            out: RecordSink::new(sink),
+            formatter: Formatter3164::default(),
+        }
+    }
+
+    /// Creates a reporter connected to the local syslog daemon's Unix socket
+    /// (`/dev/log`, `/var/run/syslog` or `/var/run/log`).
+    pub fn unix() -> Result<Self, ConsolaError> {
+        let logger = syslog::unix(Formatter3164::default())
+            .map_err(|e| ConsolaError::Reporter(e.to_string()))?;
+        Ok(Self::new(logger.backend))
+    }
+
+    /// Creates a reporter sending UDP datagrams from `local` to `server`.
+    pub fn udp<T: ToSocketAddrs>(local: T, server: T) -> Result<Self, ConsolaError> {
+        let logger = syslog::udp(Formatter3164::default(), local, server)
+            .map_err(|e| ConsolaError::Reporter(e.to_string()))?;
+        Ok(Self::new(logger.backend))
+    }
+
+    /// Set the facility of every message (`LOG_USER` by default), returning the builder for chaining.
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.formatter.facility = facility;
+        self
+    }
+
+    /// The one-line message sent for `log_obj`.
+    pub fn format_message(log_obj: &LogObject, opts: &FormatOptions) -> String {
+        let opts = FormatOptions {
+            compact: true,
+            colors: false,
+            ..opts.clone()
+        };
+        build_basic_segments(log_obj, &opts)
+            .into_iter()
+            .filter_map(|segment| match segment.kind {
+                SegmentKind::Prefix => Some(format!("{}:", segment.text)),
+                SegmentKind::Message | SegmentKind::Meta | SegmentKind::Repetition => {
+                    Some(segment.text)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Reporter for SyslogReporter {
+    fn format(&self, log_obj: &LogObject, ctx: &LogContext) -> Result<String, ConsolaError> {
+        if log_obj.group_event == Some(GroupEvent::End) {
+            return Ok(String::new());
+        }
+        let mut formatter = self.formatter.clone();
+        if !log_obj.tag.is_empty() {
+            formatter.process = log_obj.tag.clone();
+        }
+        let message = Self::format_message(log_obj, &ctx.options.format_options);
+        // Render first so each message reaches the socket in one write,
+        // i.e. as one datagram.
+        let mut packet = Vec::new();
+        formatter
+            .format(&mut packet, severity_for(log_obj), message)
+            .map_err(|e| ConsolaError::Reporter(e.to_string()))?;
+        self.out.write(&packet, log_obj.level, &ctx.options)?;
+        Ok(String::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConsolaOptions;
+
+    /// Writer standing in for the syslog socket, keeping each message.
+    #[derive(Clone, Default)]
+    struct MockSocket(Arc<std::sync::Mutex<Vec<String>>>);
+
+    /// Socket whose writes fail, counting the attempts.
+    #[derive(Clone, Default)]
+    struct BrokenSocket(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl Write for BrokenSocket {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(std::io::ErrorKind::ConnectionRefused.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Write for MockSocket {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(buf).into_owned());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn make_ctx() -> LogContext {
+        LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        }
+    }
+
+    #[test]
+    fn test_severity_mapping() {
+        let severity = |ty| severity_for(&LogObject::new(ty)) as u8;
+        assert_eq!(severity(LogType::Fatal), Severity::LOG_EMERG as u8);
+        assert_eq!(severity(LogType::Error), Severity::LOG_ERR as u8);
+        assert_eq!(severity(LogType::Warn), Severity::LOG_WARNING as u8);
+        assert_eq!(severity(LogType::Log), Severity::LOG_INFO as u8);
+        assert_eq!(severity(LogType::Info), Severity::LOG_INFO as u8);
+        assert_eq!(severity(LogType::Success), Severity::LOG_INFO as u8);
+        assert_eq!(severity(LogType::Debug), Severity::LOG_DEBUG as u8);
+        assert_eq!(severity(LogType::Trace), Severity::LOG_DEBUG as u8);
+    }
+
+    #[test]
+    fn test_delivers_records_with_tag_as_program() {
+        let socket = MockSocket::default();
+        let reporter = SyslogReporter::new(socket.clone()).facility(Facility::LOG_DAEMON);
+
+        let mut obj = LogObject::new(LogType::Warn);
+        obj.tag = "db".into();
+        obj.args = vec!["slow query".into(), "(repeated 4 times)".into()];
+        obj.repeated = 4;
+        obj.fields = vec![("ms".into(), "950".into())];
+        assert_eq!(reporter.format(&obj, &make_ctx()).unwrap(), "");
+
+        obj.group_event = Some(GroupEvent::End);
+        reporter.format(&obj, &make_ctx()).unwrap();
+
+        let sent = socket.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        // LOG_DAEMON (3 << 3) | LOG_WARNING (4)
+        assert!(sent[0].starts_with("<28>"), "{}", sent[0]);
+        assert!(
+            sent[0].ends_with(&format!(
+                "db[{}]: slow query ms=950 (repeated 4 times)",
+                std::process::id()
+            )),
+            "{}",
+            sent[0]
+        );
+    }
+
+    #[test]
+    fn test_buffered_writer_delivers_each_message() {
+        let socket = MockSocket::default();
+        let reporter = SyslogReporter::new(std::io::BufWriter::new(socket.clone()));
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["started".into()];
+        reporter.format(&obj, &make_ctx()).unwrap();
+        obj.args = vec!["ready".into()];
+        reporter.format(&obj, &make_ctx()).unwrap();
+
+        let sent = socket.0.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].ends_with("started") && sent[1].ends_with("ready"));
+    }
+
+    #[test]
+    fn test_latches_after_write_failure_limit() {
+        let socket = BrokenSocket::default();
+        let reporter = SyslogReporter::new(socket.clone());
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions {
+                write_failure_limit: 2,
+                ..ConsolaOptions::default()
+            }),
+        };
+        let obj = LogObject::new(LogType::Info);
+        assert!(matches!(
+            reporter.format(&obj, &ctx),
+            Err(ConsolaError::Reporter(_))
+        ));
+        let Err(ConsolaError::Reporter(msg)) = reporter.format(&obj, &ctx) else {
+            panic!("expected the latching error");
+        };
+        assert!(
+            msg.starts_with("stopped writing after 2 failed writes"),
+            "{msg}"
+        );
+        assert_eq!(reporter.format(&obj, &ctx), Err(ConsolaError::SinkLatched));
+        assert_eq!(socket.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
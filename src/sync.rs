@@ -1,5 +1,5 @@
-//! Mutex abstraction that uses `parking_lot::Mutex` when the `parking_lot` feature
-//! is enabled, falling back to `std::sync::Mutex` otherwise.
+//! Mutex and condition variable abstractions that use `parking_lot` when the
+//! `parking_lot` feature is enabled, falling back to `std::sync` otherwise.
 
 #[cfg(feature = "parking_lot")]
 mod imp {
@@ -14,6 +14,16 @@ mod imp {
         pub fn lock(&self) -> parking_lot::MutexGuard<'_, T> {
             self.0.lock()
         }
+
+        pub fn into_inner(self) -> T {
+            self.0.into_inner()
+        }
+    }
+
+    impl<T: Default> Default for Mutex<T> {
+        fn default() -> Self {
+            Self::new(T::default())
+        }
     }
 
     impl<T: std::fmt::Debug> std::fmt::Debug for Mutex<T> {
@@ -21,12 +31,33 @@ mod imp {
             self.0.fmt(f)
         }
     }
+
+    pub type MutexGuard<'a, T> = parking_lot::MutexGuard<'a, T>;
+
+    /// A condition variable backed by `parking_lot::Condvar`.
+    #[derive(Debug, Default)]
+    pub struct Condvar(parking_lot::Condvar);
+
+    impl Condvar {
+        pub fn new() -> Self {
+            Self(parking_lot::Condvar::new())
+        }
+
+        pub fn wait<'a, T>(&self, mut guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+            self.0.wait(&mut guard);
+            guard
+        }
+
+        pub fn notify_all(&self) {
+            self.0.notify_all();
+        }
+    }
 }
 
 #[cfg(not(feature = "parking_lot"))]
 mod imp {
     use std::sync::Mutex as StdMutex;
-    use std::sync::MutexGuard;
+    pub use std::sync::MutexGuard;
 
     /// A mutex backed by `std::sync::Mutex`, wrapping its poisoned-lock API
     /// to match `parking_lot::Mutex`'s infallible lock interface.
@@ -41,6 +72,16 @@ mod imp {
             // Ignore poison — we do not track panics across threads.
             self.0.lock().unwrap_or_else(|e| e.into_inner())
         }
+
+        pub fn into_inner(self) -> T {
+            self.0.into_inner().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+
+    impl<T: Default> Default for Mutex<T> {
+        fn default() -> Self {
+            Self::new(T::default())
+        }
     }
 
     impl<T: std::fmt::Debug> std::fmt::Debug for Mutex<T> {
@@ -48,9 +89,28 @@ mod imp {
             self.0.fmt(f)
         }
     }
+
+    /// A condition variable backed by `std::sync::Condvar`, ignoring poison
+    /// like [`Mutex`].
+    #[derive(Debug, Default)]
+    pub struct Condvar(std::sync::Condvar);
+
+    impl Condvar {
+        pub fn new() -> Self {
+            Self(std::sync::Condvar::new())
+        }
+
+        pub fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+            self.0.wait(guard).unwrap_or_else(|e| e.into_inner())
+        }
+
+        pub fn notify_all(&self) {
+            self.0.notify_all();
+        }
+    }
 }
 
-pub use imp::Mutex;
+pub use imp::{Condvar, Mutex};
 
 #[cfg(test)]
 mod tests {
@@ -81,6 +141,22 @@ mod tests {
         assert!(!debug.is_empty());
     }
 
+    #[test]
+    fn test_condvar_wakes_waiter() {
+        let pair = std::sync::Arc::new((Mutex::new(false), Condvar::new()));
+        let waker = pair.clone();
+        let handle = std::thread::spawn(move || {
+            *waker.0.lock() = true;
+            waker.1.notify_all();
+        });
+        let mut ready = pair.0.lock();
+        while !*ready {
+            ready = pair.1.wait(ready);
+        }
+        drop(ready);
+        handle.join().unwrap();
+    }
+
     #[test]
     fn test_mutex_multiple_locks() {
         let m = Mutex::new(0);
@@ -0,0 +1,102 @@
+//! Typed values attached to log entries for custom reporters.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Store holding at most one value per Rust type, for context that custom
+/// reporters and bridges pass along with an entry (e.g. a request id
+/// struct).
+///
+/// Built-in reporters ignore extensions, and they are not part of an entry's
+/// [`fingerprint`](super::LogObject::fingerprint), so entries that only differ
+/// in their extensions are throttled as repeats. Values are reference
+/// counted: cloning an entry shares its extensions instead of copying them.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value`, replacing any earlier value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// The stored value of type `T`, if any.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Remove the stored value of type `T`, returning whether there was one.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Number of stored values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no value is stored.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Copy every value of `other` into this store, replacing values of the
+    /// same type.
+    pub fn extend(&mut self, other: &Extensions) {
+        self.values.extend(
+            other
+                .values
+                .iter()
+                .map(|(ty, value)| (*ty, Arc::clone(value))),
+        );
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u64);
+
+    #[test]
+    fn test_one_value_per_type() {
+        let mut ext = Extensions::new();
+        ext.insert(RequestId(1));
+        ext.insert(RequestId(2));
+        ext.insert("label");
+        assert_eq!(ext.get::<RequestId>(), Some(&RequestId(2)));
+        assert_eq!(ext.get::<&str>(), Some(&"label"));
+        assert_eq!(ext.get::<u32>(), None);
+        assert_eq!(ext.len(), 2);
+
+        let mut merged = Extensions::new();
+        merged.insert(RequestId(9));
+        merged.insert(7u32);
+        merged.extend(&ext);
+        assert_eq!(merged.get::<RequestId>(), Some(&RequestId(2)));
+        assert_eq!(merged.get::<u32>(), Some(&7));
+
+        assert!(ext.remove::<RequestId>());
+        assert!(!ext.remove::<RequestId>());
+        assert_eq!(ext.len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 //! Formatting options, terminal width detection, and error info.
 
+use crate::util::color::ColorDepth;
+
 /// Controls formatting behavior of log output.
 #[derive(Debug, Clone)]
 pub struct FormatOptions {
@@ -7,12 +9,84 @@ pub struct FormatOptions {
     pub columns: Option<u16>,
     /// Whether to include a timestamp prefix in log output.
     pub date: bool,
-    /// Whether to use ANSI color codes in formatted output.
+    /// How the timestamp prefix shows the entry's time when `date` is set.
+    pub time_mode: TimeMode,
+    /// Start of the elapsed time shown by [`TimeMode::Relative`], in
+    /// milliseconds since the Unix epoch. [`Consola`](crate::Consola) sets it
+    /// to its creation time when unset; without it, relative timestamps fall
+    /// back to absolute ones.
+    pub time_origin_ms: Option<i64>,
+    /// Fractional digits of timestamps: the text reporters' time prefix and
+    /// the logfmt `time` field.
+    pub time_precision: TimePrecision,
+    /// Whether to use ANSI color codes in formatted output. The basic
+    /// reporter colors its type badge only when this is set.
     pub colors: bool,
-    /// Whether to use compact formatting (single-line output).
+    /// Colors the terminal can display, detected from the environment by
+    /// default. RGB colors (such as `#rrggbb` custom type colors) are
+    /// downgraded to the closest color this depth supports.
+    pub color_depth: ColorDepth,
+    /// Letter case of the log type name in badges and labels.
+    pub type_case: TypeCase,
+    /// Whether to use compact formatting: terse single-line output for CI logs.
+    ///
+    /// Text reporters collapse whitespace (including extra lines) into single
+    /// spaces and render error chains inline. The fancy reporter also drops
+    /// the date and shows the type's icon instead of a badge when it has one.
+    /// Box entries are left unchanged.
     pub compact: bool,
+    /// In compact mode, whether the fancy reporter opens each line with one
+    /// dense `HH:MM:SS I` segment — the entry's time and the type's initial
+    /// in the type's color — in place of the icon or badge.
+    pub compact_time_prefix: bool,
     /// Maximum error level to display in stack traces.
     pub error_level: u32,
+    /// How multi-line error output arranges stack traces and the cause chain.
+    pub error_layout: ErrorLayout,
+    /// Whether to color the message text with the log type's color
+    /// (e.g. errors fully red), not just the badge/icon. The message is
+    /// painted when colors are globally enabled or [`colors`](Self::colors)
+    /// is set.
+    pub color_message_by_level: bool,
+    /// Whether to word-wrap messages to `columns`. Wrapping is ANSI-aware and
+    /// measures display width, so colored and wide characters wrap correctly.
+    pub wrap: bool,
+    /// Truncate each line of the message to this many display columns,
+    /// ending cut lines with `…`, instead of letting long lines run on.
+    /// Takes precedence over `wrap`; error stacks are not truncated.
+    pub truncate: Option<usize>,
+    /// Whether wrapped continuation lines are indented to align under the
+    /// start of the message (after the type and tag).
+    pub wrap_indent: bool,
+    /// Whether to render error chains on the log line as `msg: cause: cause`
+    /// instead of multi-line blocks with stack traces. When `error_level` is
+    /// non-zero it caps the number of causes shown.
+    pub inline_error_chain: bool,
+    /// Whether to prefix each continuation line of multi-line output (stacks,
+    /// error chains, extra lines) with `<type> | ` so that grepping for the
+    /// type also finds them.
+    pub mark_continuations: bool,
+    /// Whether the basic reporter indents the lines after the first line of a
+    /// multi-line message to start under the message (past the logger name,
+    /// type and tag), rather than at column 0.
+    /// Applies whether or not `wrap` is set; when wrapping, it implies `wrap_indent`.
+    pub align_multiline: bool,
+    /// Number of spaces text reporters indent output by per open
+    /// [`group`](crate::Consola::group).
+    pub group_indent: usize,
+    /// Whether text reporters render structured fields below the message as
+    /// an aligned `key: value` block, one field per line, instead of inline
+    /// `key=value` pairs. Useful for config dumps. Ignored in compact mode.
+    pub fields_block: bool,
+    /// Whether text reporters merge adjacent segments sharing a style into
+    /// one run instead of resetting and reopening the style between them
+    /// (see [`coalesce_styles`](crate::util::coalesce_styles)). Only the
+    /// escape sequences change, not the rendered output.
+    pub coalesce_styles: bool,
+    /// Text placed between the segments of a log line (trace id, type, tag,
+    /// prefix, message, fields and repeat counter) by text reporters, e.g.
+    /// `" | "` or a tab. Wrapping and alignment account for its width.
+    pub separator: String,
 }
 
 impl Default for FormatOptions {
@@ -20,11 +94,243 @@ impl Default for FormatOptions {
         Self {
             columns: terminal_width(),
             date: true,
+            time_mode: TimeMode::default(),
+            time_origin_ms: None,
+            time_precision: TimePrecision::default(),
             colors: false,
-            compact: true,
+            color_depth: ColorDepth::detect(),
+            type_case: TypeCase::default(),
+            // Off so default loggers keep dates and multi-line errors.
+            compact: false,
+            compact_time_prefix: false,
             error_level: 0,
+            error_layout: ErrorLayout::default(),
+            color_message_by_level: false,
+            wrap: false,
+            truncate: None,
+            wrap_indent: false,
+            inline_error_chain: false,
+            mark_continuations: false,
+            align_multiline: false,
+            group_indent: 2,
+            fields_block: false,
+            coalesce_styles: false,
+            separator: " ".to_string(),
+        }
+    }
+}
+
+/// Letter case applied to the log type name by text reporters
+/// (see [`FormatOptions::type_case`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeCase {
+    /// Each reporter's own style: lowercase labels, uppercase fancy badges.
+    #[default]
+    AsIs,
+    /// `info`
+    Lower,
+    /// `INFO`
+    Upper,
+    /// `Info`
+    Title,
+}
+
+impl TypeCase {
+    /// Apply the case to `name`; [`AsIs`](Self::AsIs) returns it unchanged.
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            Self::AsIs => name.to_string(),
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Title => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|first| {
+                        first
+                            .to_uppercase()
+                            .chain(chars.flat_map(char::to_lowercase))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Arrangement of stack traces and causes when an error is rendered below
+/// the log line (see [`FormatOptions::error_layout`]).
+///
+/// Inline error chains (`inline_error_chain` and compact mode) never show
+/// stacks and are not affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorLayout {
+    /// Each error's stack directly below its message, followed by its cause.
+    #[default]
+    StackThenChain,
+    /// The messages of the whole chain first, then the stack of each error
+    /// in chain order.
+    ChainThenStack,
+    /// The top-level error and its stack; causes are omitted.
+    StackOnly,
+    /// The messages of the whole chain; stacks are omitted.
+    ChainOnly,
+}
+
+/// Timestamp style of text reporters (see [`FormatOptions::time_mode`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeMode {
+    /// Local wall-clock time, e.g. `3:04:05 PM`.
+    #[default]
+    Absolute,
+    /// Time elapsed since [`FormatOptions::time_origin_ms`], e.g. `+1.250s`.
+    Relative,
+    /// No timestamp, like setting `date` to `false`.
+    None,
+}
+
+/// Fractional digits of a timestamp (see [`FormatOptions::time_precision`]).
+///
+/// Entries are timestamped in milliseconds, so the digits past the third are
+/// always zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimePrecision {
+    /// Each reporter's own style: whole seconds for wall-clock times,
+    /// milliseconds for elapsed times and machine-readable formats.
+    #[default]
+    AsIs,
+    /// `3:04:05 PM`
+    Seconds,
+    /// `3:04:05.678 PM`
+    Millis,
+    /// `3:04:05.678000 PM`
+    Micros,
+    /// `3:04:05.678000000 PM`
+    Nanos,
+}
+
+impl TimePrecision {
+    /// The fraction of a second in `ms` as `.` plus the configured digits,
+    /// or an empty string for whole seconds. [`AsIs`](Self::AsIs) shows
+    /// `default_digits`.
+    pub(crate) fn fraction(self, ms: i64, default_digits: usize) -> String {
+        let digits = match self {
+            Self::AsIs => default_digits,
+            Self::Seconds => 0,
+            Self::Millis => 3,
+            Self::Micros => 6,
+            Self::Nanos => 9,
+        };
+        if digits == 0 {
+            return String::new();
+        }
+        let mut fraction = format!("{:03}", ms.rem_euclid(1000));
+        fraction.truncate(digits);
+        format!(".{:0<width$}", fraction, width = digits)
+    }
+}
+
+impl FormatOptions {
+    /// The options text reporters actually apply: compact mode implies
+    /// inline error chains and no wrapping.
+    pub(crate) fn effective(&self) -> std::borrow::Cow<'_, FormatOptions> {
+        if !self.compact {
+            return std::borrow::Cow::Borrowed(self);
+        }
+        std::borrow::Cow::Owned(FormatOptions {
+            inline_error_chain: true,
+            wrap: false,
+            mark_continuations: false,
+            ..self.clone()
+        })
+    }
+
+    /// Build format options from the process environment.
+    ///
+    /// See [`FormatOptions::from_env_vars`] for the variables consulted.
+    /// Without a `NO_COLOR` or `FORCE_COLOR` override, `colors` is enabled
+    /// when stdout is a terminal, so piped output stays free of ANSI codes.
+    pub fn from_env() -> Self {
+        Self::from_env_vars_for(|key| std::env::var(key).ok(), stdout_is_terminal())
+    }
+
+    /// Like [`from_env_vars`](Self::from_env_vars), with `colors` defaulting
+    /// to `is_terminal` when neither `NO_COLOR` nor `FORCE_COLOR` decides.
+    ///
+    /// `FORCE_COLOR` still enables colors when output is not a terminal.
+    pub fn from_env_vars_for(lookup: impl Fn(&str) -> Option<String>, is_terminal: bool) -> Self {
+        let colors = env_color_override(&lookup).unwrap_or(is_terminal);
+        Self {
+            colors,
+            ..Self::from_env_vars(lookup)
         }
     }
+
+    /// Build format options from an environment lookup function.
+    ///
+    /// | Variable | Effect |
+    /// |---|---|
+    /// | `NO_COLOR` | disables `colors` |
+    /// | `FORCE_COLOR` | enables `colors` (`FORCE_COLOR=0` disables) |
+    /// | `COLORTERM`, `TERM`, `FORCE_COLOR` | set `color_depth` (see [`ColorDepth::from_env_vars`]) |
+    /// | `CONSOLA_COMPACT` | sets `compact` (`1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`) |
+    /// | `CONSOLA_DATE` | sets `date` (same boolean spellings) |
+    /// | `CONSOLA_COLUMNS`, then `COLUMNS` | sets `columns` |
+    ///
+    /// Unset or unparsable variables leave the [`Default`] value in place.
+    pub fn from_env_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut opts = Self::default();
+        if let Some(colors) = env_color_override(&lookup) {
+            opts.colors = colors;
+        }
+        opts.color_depth = ColorDepth::from_env_vars(&lookup);
+        if let Some(compact) = lookup("CONSOLA_COMPACT").and_then(|v| parse_env_bool(&v)) {
+            opts.compact = compact;
+        }
+        if let Some(date) = lookup("CONSOLA_DATE").and_then(|v| parse_env_bool(&v)) {
+            opts.date = date;
+        }
+        if let Some(columns) = lookup("CONSOLA_COLUMNS")
+            .or_else(|| lookup("COLUMNS"))
+            .and_then(|v| v.trim().parse::<u16>().ok())
+        {
+            opts.columns = Some(columns);
+        }
+        opts
+    }
+}
+
+/// Resolve the `NO_COLOR` / `FORCE_COLOR` environment overrides.
+///
+/// Returns `None` when neither variable forces a decision. `NO_COLOR` wins
+/// over `FORCE_COLOR`, and `FORCE_COLOR=0` (or `false`) counts as disabling.
+pub(crate) fn env_color_override(lookup: &impl Fn(&str) -> Option<String>) -> Option<bool> {
+    if lookup("NO_COLOR").is_some() {
+        return Some(false);
+    }
+    lookup("FORCE_COLOR").map(|v| !matches!(v.trim(), "0" | "false"))
+}
+
+/// Parse a boolean environment value, accepting the usual spellings.
+pub(crate) fn parse_env_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether stdout is a terminal; always `false` on WASM.
+pub(crate) fn stdout_is_terminal() -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::io::IsTerminal;
+        std::io::stdout().is_terminal()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        false
+    }
 }
 
 /// Attempt to detect terminal width at runtime.
@@ -32,8 +338,7 @@ impl Default for FormatOptions {
 pub fn terminal_width() -> Option<u16> {
     #[cfg(not(target_arch = "wasm32"))]
     {
-        use std::io::IsTerminal;
-        if std::io::stdout().is_terminal() {
+        if stdout_is_terminal() {
             terminal_size::terminal_size().map(|(width, _)| width.0)
         } else {
             None
@@ -46,7 +351,7 @@ pub fn terminal_width() -> Option<u16> {
 }
 
 /// Information about an error for rich error-chain formatting.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ErrorInfo {
     /// The error message.
     pub message: String,
@@ -57,3 +362,96 @@ pub struct ErrorInfo {
     /// The cause of this error (next in the chain).
     pub cause: Option<Box<ErrorInfo>>,
 }
+
+impl ErrorInfo {
+    /// Build an `ErrorInfo` from `err`, following [`source`](std::error::Error::source)
+    /// to fill the `cause` chain. No stack or backtrace is captured.
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        Self {
+            message: err.to_string(),
+            stack: None,
+            backtrace: None,
+            cause: err
+                .source()
+                .map(|source| Box::new(Self::from_error(source))),
+        }
+    }
+
+    /// Capture a backtrace of the current thread into `stack` / `backtrace`,
+    /// regardless of `RUST_BACKTRACE`.
+    ///
+    /// Frames are cleaned up with [`parse_error_stack`](crate::util::error::parse_error_stack).
+    #[cfg(feature = "backtrace")]
+    pub fn with_backtrace(self) -> Self {
+        self.with_captured(std::backtrace::Backtrace::force_capture())
+    }
+
+    #[cfg(feature = "backtrace")]
+    pub(crate) fn with_captured(mut self, bt: std::backtrace::Backtrace) -> Self {
+        if bt.status() == std::backtrace::BacktraceStatus::Captured {
+            let raw = bt.to_string();
+            self.stack = Some(crate::util::error::parse_error_stack(&raw).join("\n"));
+            self.backtrace = Some(raw);
+        }
+        self
+    }
+
+    /// Render the error and its causes on one line as `message: cause: cause`.
+    ///
+    /// Empty messages (e.g. auto-captured backtraces) are skipped. When
+    /// `max_causes` is non-zero, at most that many causes follow the message.
+    pub fn inline_chain(&self, max_causes: u32) -> String {
+        let mut parts = Vec::new();
+        let mut current = Some(self);
+        let mut depth = 0;
+        while let Some(err) = current {
+            if max_causes > 0 && depth > max_causes {
+                break;
+            }
+            if !err.message.is_empty() {
+                parts.push(err.message.as_str());
+            }
+            current = err.cause.as_deref();
+            depth += 1;
+        }
+        parts.join(": ")
+    }
+
+    /// This error with its cause chain cut after `max_causes` causes; `0`
+    /// keeps the whole chain.
+    pub fn truncated(&self, max_causes: u32) -> Self {
+        let mut err = self.clone();
+        if max_causes > 0 {
+            let mut remaining = max_causes;
+            let mut current = err.cause.as_deref_mut();
+            while let Some(cause) = current {
+                remaining -= 1;
+                if remaining == 0 {
+                    cause.cause = None;
+                    break;
+                }
+                current = cause.cause.as_deref_mut();
+            }
+        }
+        err
+    }
+
+    /// This error with its cause chain and stack replaced by a
+    /// `(same cause as above)` note, for an error that was just printed in full.
+    ///
+    /// Errors with neither a cause nor a stack are returned unchanged.
+    pub fn abbreviated(&self) -> Self {
+        if self.cause.is_none() && self.stack.is_none() && self.backtrace.is_none() {
+            return self.clone();
+        }
+        let message = if self.message.is_empty() {
+            "(same cause as above)".to_string()
+        } else {
+            format!("{} (same cause as above)", self.message)
+        };
+        Self {
+            message,
+            ..Self::default()
+        }
+    }
+}
@@ -1,17 +1,29 @@
 //! Core types: log entries, the reporter trait, and consola options.
 
+pub mod extensions;
 pub mod format;
 pub mod prompt;
+pub mod target_levels;
+pub mod throttle;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::constants::{LogLevel, LogType, log_levels};
+use crate::constants::{LogLevel, LogType, log_levels, parse_log_level};
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::redact::Redactor;
+use crate::util::sink::Sink;
+use crate::util::sink::{StderrSink, StdoutSink};
 
-pub use format::{ErrorInfo, FormatOptions};
+pub use extensions::Extensions;
+pub use format::{ErrorInfo, ErrorLayout, FormatOptions, TimeMode, TimePrecision, TypeCase};
 pub use prompt::{
-    ConfirmPromptOptions, MultiSelectOptions, PromptCommonOptions, PromptOptions, SelectOption,
-    SelectPromptOptions, TextPromptOptions,
+    AutocompletePromptOptions, ConfirmPromptOptions, MultiSelectOptions, NumberPromptOptions,
+    PasswordPromptOptions, PromptCommonOptions, PromptOptions, SelectOption, SelectPromptOptions,
+    TextPromptOptions,
 };
+pub use target_levels::TargetLevels;
+pub use throttle::{RateLimit, ThrottleConfig};
 
 /// Partial log input used to construct a fully resolved `LogObject`.
 ///
@@ -41,6 +53,16 @@ pub struct LogObjectInput {
     pub style: Option<String>,
     /// Optional error information for error-level logs.
     pub error: Option<ErrorInfo>,
+    /// Structured `key=value` fields, in insertion order.
+    pub fields: Vec<(String, String)>,
+    /// Optional correlation id shared by every entry of a request or trace.
+    pub trace_id: Option<String>,
+    /// Optional label, such as a component name, shown between the type and
+    /// the message. Usually set logger-wide through
+    /// [`Consola::with_prefix`](crate::Consola::with_prefix).
+    pub prefix: Option<String>,
+    /// Typed values for custom reporters, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl LogObjectInput {
@@ -55,6 +77,40 @@ impl LogObjectInput {
         self
     }
 
+    /// Attach `err` and its `source()` chain, returning the builder for chaining.
+    ///
+    /// With the `backtrace` feature, a backtrace is also captured when enabled
+    /// through `RUST_BACKTRACE` / `RUST_LIB_BACKTRACE`; otherwise this is free.
+    pub fn attach_error(mut self, err: &(dyn std::error::Error + 'static)) -> Self {
+        let info = ErrorInfo::from_error(err);
+        #[cfg(feature = "backtrace")]
+        let info = info.with_captured(std::backtrace::Backtrace::capture());
+        self.error = Some(info);
+        self
+    }
+
+    /// Append an error as a positional arg, returning the builder for chaining.
+    ///
+    /// Errors are not flattened into the message text: the first error arg is
+    /// attached like [`attach_error`](Self::attach_error), so its full chain is
+    /// rendered below the message wherever the arg appears in the list. If an
+    /// error is already attached, `err` is added to the args as its one-line
+    /// `message: cause: cause` chain instead.
+    pub fn error_arg(self, err: &(dyn std::error::Error + 'static)) -> Self {
+        if self.error.is_some() {
+            let chain = ErrorInfo::from_error(err).inline_chain(0);
+            return self.arg(chain);
+        }
+        self.attach_error(err)
+    }
+
+    /// Attach a typed value for custom reporters (see [`Extensions`]),
+    /// returning the builder for chaining.
+    pub fn extension<T: std::any::Any + Send + Sync>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
     /// Set the tag, returning the builder for chaining.
     pub fn tag(mut self, tag: impl Into<String>) -> Self {
         self.tag = Some(tag.into());
@@ -68,8 +124,13 @@ impl LogObjectInput {
     }
 
     /// Append a single positional arg, returning the builder for chaining.
-    pub fn arg(mut self, arg: impl Into<String>) -> Self {
-        self.args.push(arg.into());
+    ///
+    /// Accepts anything [`Display`](std::fmt::Display)able, so strings, `char`s
+    /// and numbers of every width can be passed without casting. Numbers are
+    /// rendered as Rust prints them: large `u64`/`usize` values keep full
+    /// precision and floats use the shortest round-tripping form.
+    pub fn arg(mut self, arg: impl std::fmt::Display) -> Self {
+        self.args.push(arg.to_string());
         self
     }
 
@@ -90,6 +151,36 @@ impl LogObjectInput {
         self.additional = Some(addl.into());
         self
     }
+
+    /// Attach a structured field, returning the builder for chaining.
+    ///
+    /// Setting a key that is already present replaces its value in place.
+    pub fn field(mut self, key: impl Into<String>, value: impl std::fmt::Display) -> Self {
+        set_field(&mut self.fields, key.into(), value.to_string());
+        self
+    }
+
+    /// Set the trace id, returning the builder for chaining.
+    pub fn trace_id(mut self, id: impl Into<String>) -> Self {
+        self.trace_id = Some(id.into());
+        self
+    }
+}
+
+/// Set `key` to `value` in `fields`, replacing an existing value for the key
+/// in place (last writer wins) or appending a new entry.
+pub(crate) fn set_field(fields: &mut Vec<(String, String)>, key: String, value: String) {
+    match fields.iter_mut().find(|(k, _)| *k == key) {
+        Some((_, v)) => *v = value,
+        None => fields.push((key, value)),
+    }
+}
+
+/// Merge `overrides` into `base` with [`set_field`] semantics.
+pub(crate) fn merge_fields(base: &mut Vec<(String, String)>, overrides: &[(String, String)]) {
+    for (key, value) in overrides {
+        set_field(base, key.clone(), value.clone());
+    }
 }
 
 /// A fully resolved log entry passed to reporters for formatting.
@@ -121,6 +212,46 @@ pub struct LogObject {
     pub style: Option<String>,
     /// Optional error information for error-level logs.
     pub error: Option<ErrorInfo>,
+    /// Number of open [`group`](crate::Consola::group) blocks this entry was logged in.
+    pub group_depth: usize,
+    /// Set when this entry marks the start or end of a group rather than a regular log.
+    pub group_event: Option<GroupEvent>,
+    /// Structured `key=value` fields, in insertion order with unique keys.
+    pub fields: Vec<(String, String)>,
+    /// Number of throttled duplicates this entry summarizes, `0` for a regular entry.
+    ///
+    /// Text reporters see the count as a trailing `(repeated N times)` arg;
+    /// structured reporters can use this field instead.
+    pub repeated: u32,
+    /// Correlation id linking this entry to a request or trace, if any.
+    ///
+    /// Text reporters show it as a short `#id` prefix; structured reporters
+    /// write it under a `trace_id` key.
+    pub trace_id: Option<String>,
+    /// Label shown by text reporters between the type and tag and the
+    /// message, if any.
+    ///
+    /// Not part of the [`fingerprint`](Self::fingerprint), so entries that
+    /// only differ in their prefix are throttled together unless
+    /// [`ConsolaOptions::fingerprint_prefix`] is set.
+    pub prefix: Option<String>,
+    /// Typed values for custom reporters. Ignored by the built-in reporters
+    /// and not part of the [`fingerprint`](Self::fingerprint); clones share
+    /// the values.
+    pub extensions: Extensions,
+}
+
+/// Group boundary carried by a [`LogObject`] emitted from
+/// [`Consola::group`](crate::Consola::group) / [`Consola::group_end`](crate::Consola::group_end).
+///
+/// Text reporters print the start label and indent nested output; structured
+/// reporters can emit the boundaries as events instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupEvent {
+    /// A group was opened; the label is in `args[0]`.
+    Start,
+    /// The innermost group was closed; its label is in `args[0]`.
+    End,
 }
 
 impl LogObject {
@@ -143,9 +274,75 @@ impl LogObject {
             icon: None,
             style: None,
             error: None,
+            group_depth: 0,
+            group_event: None,
+            fields: Vec::new(),
+            repeated: 0,
+            trace_id: None,
+            prefix: None,
+            extensions: Extensions::new(),
         }
     }
 
+    /// Attach a typed value for custom reporters, replacing any earlier value
+    /// of the same type.
+    pub fn insert_ext<T: std::any::Any + Send + Sync>(&mut self, value: T) {
+        self.extensions.insert(value);
+    }
+
+    /// The attached value of type `T`, if any.
+    pub fn get_ext<T: std::any::Any + Send + Sync>(&self) -> Option<&T> {
+        self.extensions.get()
+    }
+
+    /// Stable identity used to detect repeated entries for throttling.
+    ///
+    /// The fingerprint covers the type, level, tag, message, args, fields,
+    /// trace id and whether the entry was logged through a `_raw` method, but not the
+    /// timestamp. Each component is encoded as a tag byte, a big-endian `u32`
+    /// payload length and the payload, in this order:
+    ///
+    /// | tag    | payload                                   |
+    /// |--------|-------------------------------------------|
+    /// | `0x01` | type name (UTF-8)                         |
+    /// | `0x02` | level (`i32`, big-endian)                 |
+    /// | `0x03` | tag (UTF-8)                               |
+    /// | `0x04` | message (UTF-8), only when set            |
+    /// | `0x05` | one per arg (UTF-8)                       |
+    /// | `0x06` | one per field: key, then `0x07` and value |
+    /// | `0x08` | `1` for raw entries, `0` otherwise        |
+    /// | `0x09` | trace id (UTF-8), only when set           |
+    ///
+    /// The encoding does not depend on `Debug` output, so it only changes
+    /// when this documented layout does.
+    pub fn fingerprint(&self, raw: bool) -> Vec<u8> {
+        fn push(out: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+            out.push(tag);
+            out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+            out.extend_from_slice(payload);
+        }
+
+        let mut out = Vec::new();
+        push(&mut out, 0x01, self.r#type.as_str().as_bytes());
+        push(&mut out, 0x02, &self.level.to_be_bytes());
+        push(&mut out, 0x03, self.tag.as_bytes());
+        if let Some(message) = &self.message {
+            push(&mut out, 0x04, message.as_bytes());
+        }
+        for arg in &self.args {
+            push(&mut out, 0x05, arg.as_bytes());
+        }
+        for (key, value) in &self.fields {
+            push(&mut out, 0x06, key.as_bytes());
+            push(&mut out, 0x07, value.as_bytes());
+        }
+        push(&mut out, 0x08, &[u8::from(raw)]);
+        if let Some(trace_id) = &self.trace_id {
+            push(&mut out, 0x09, trace_id.as_bytes());
+        }
+        out
+    }
+
     /// Return the timestamp as a jiff Zoned (feature = "jiff", default).
     /// Returns `None` if the timestamp is invalid.
     #[cfg(feature = "jiff")]
@@ -195,6 +392,26 @@ pub(crate) fn now_ms() -> i64 {
         .unwrap_or(0)
 }
 
+/// Per-type counters kept by a `Consola` instance for the current session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogStats {
+    /// Entries passed to the reporters.
+    pub emitted: u64,
+    /// Entries swallowed by repeat throttling.
+    pub suppressed: u64,
+    /// Entries rejected by the level filter.
+    pub filtered: u64,
+    /// Entries for which at least one reporter returned an error.
+    pub dropped: u64,
+}
+
+impl LogStats {
+    /// Total number of entries seen, across all outcomes.
+    pub fn total(&self) -> u64 {
+        self.emitted + self.suppressed + self.filtered + self.dropped
+    }
+}
+
 /// Context passed to reporters alongside the log object.
 #[derive(Debug, Clone)]
 pub struct LogContext {
@@ -214,6 +431,30 @@ pub trait Reporter: std::fmt::Debug + Send + Sync {
     ) -> Result<String, crate::error::ConsolaError>;
     /// Clone the reporter into a boxed trait object.
     fn clone_box(&self) -> Box<dyn Reporter>;
+
+    /// Flush output the reporter writes itself rather than returning from
+    /// [`format`](Self::format). Called by
+    /// [`Consola::shutdown`](crate::Consola::shutdown); the default does
+    /// nothing.
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Format `log_obj` with default [`ConsolaOptions`], without setting up a
+    /// [`LogContext`]. Handy for tests and one-off rendering.
+    fn render_to_string(&self, log_obj: &LogObject) -> Result<String, crate::error::ConsolaError> {
+        let ctx = LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        };
+        self.format(log_obj, &ctx)
+    }
+
+    /// Like [`render_to_string`](Self::render_to_string), with ANSI escape
+    /// sequences removed for plain-text comparisons.
+    fn render_plain(&self, log_obj: &LogObject) -> Result<String, crate::error::ConsolaError> {
+        self.render_to_string(log_obj)
+            .map(|s| crate::util::string::strip_ansi(&s))
+    }
 }
 
 impl Clone for Box<dyn Reporter> {
@@ -222,45 +463,245 @@ impl Clone for Box<dyn Reporter> {
     }
 }
 
+/// What a log call does while logging is paused and the pause queue holds
+/// [`ConsolaOptions::pause_queue_capacity`] log calls already.
+///
+/// Discarded calls are counted as dropped in the stats and by
+/// [`Consola::queue_dropped_count`](crate::Consola::queue_dropped_count).
+/// Group starts and ends are always queued, whatever the policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueOverflow {
+    /// Discard the oldest queued call to make room.
+    #[default]
+    DropOldest,
+    /// Discard the new call, keeping the earliest context.
+    DropNewest,
+    /// Wait until logging is resumed or the queue is discarded.
+    ///
+    /// Calls from the thread that paused logging are dropped as with
+    /// `DropNewest` instead, since that thread would wait for itself. Any
+    /// other thread that only resumes logging after its own log calls return
+    /// still deadlocks, so never pair this with such a thread.
+    Block,
+}
+
 /// Configuration options for a `Consola` instance.
 #[derive(Debug)]
 pub struct ConsolaOptions {
+    /// Logger-wide name, shown as a dim prefix by text reporters and as
+    /// `logger` in JSON. Unlike a tag, it identifies the instance rather than
+    /// individual entries.
+    pub name: Option<String>,
     /// List of reporters that format and output log entries.
     pub reporters: Vec<Box<dyn Reporter>>,
     /// Minimum log level that will be displayed.
     pub level: LogLevel,
     /// Default field values applied to every log entry.
     pub defaults: LogObjectInput,
-    /// Minimum interval (ms) between duplicate log messages.
+    /// Minimum interval (ms) between duplicate log messages; `0` disables
+    /// throttling.
     pub throttle: u64,
     /// Minimum number of occurrences before throttling activates.
     pub throttle_min: u32,
+    /// Throttle settings for individual log types, keyed by type name (e.g.
+    /// `"error"`), used instead of `throttle` and `throttle_min` for entries
+    /// of that type.
+    pub throttle_by_type: HashMap<String, ThrottleConfig>,
+    /// Cap on the number of entries emitted per second after throttling;
+    /// entries over the cap are dropped. `None` (the default) for no cap.
+    /// Like the cardinality guard, it needs a [`Clock`] with a monotonic
+    /// time.
+    pub rate_limit: Option<RateLimit>,
+    /// Window (ms) within which an error chain identical to the previously
+    /// emitted one is abbreviated to `(same cause as above)`. `0` (the
+    /// default) always prints the full chain.
+    ///
+    /// Unlike `throttle`, the entry itself is still emitted; only its cause
+    /// chain and stack are dropped. Each emitted chain restarts the window.
+    pub error_chain_window: u64,
+    /// Maximum number of distinct entries emitted per `cardinality_window`.
+    /// `0` (the default) disables the guard.
+    ///
+    /// Protects output from floods of unique messages (e.g. ones embedding a
+    /// timestamp) that throttling cannot collapse. Once the limit is reached,
+    /// new distinct entries are suppressed for the rest of the window, with a
+    /// warning when suppression starts and a count of suppressed entries
+    /// when the next window begins. Entries already seen in the window still
+    /// pass. Memory use is bounded by the limit.
+    pub cardinality_limit: u32,
+    /// Window (ms) for `cardinality_limit` (default `1000`).
+    pub cardinality_window: u64,
     /// Formatting options for reporters.
     pub format_options: FormatOptions,
+    /// Sink receiving output for levels above `stderr_level` (default
+    /// stdout).
+    pub out_sink: Arc<dyn Sink>,
+    /// Sink receiving output at or below `stderr_level`, and reporter errors
+    /// (default stderr).
+    pub err_sink: Arc<dyn Sink>,
+    /// Most verbose level routed to `err_sink` (default [`log_levels::WARN`]).
+    ///
+    /// Uses the same ordering as filtering: an entry goes to `err_sink` when
+    /// `entry.level <= stderr_level`. Custom types with negative levels are
+    /// therefore always treated as errors, and very large levels as output.
+    /// Set to [`log_levels::ERROR`] to keep warnings on stdout.
+    pub stderr_level: LogLevel,
+    /// Send every entry to `out_sink`, ignoring `stderr_level`.
+    /// Reporter errors still go to `err_sink`.
+    pub stdout_only: bool,
+    /// Most verbose level whose entries flush their sink right after being
+    /// written (default [`log_levels::ERROR`]).
+    ///
+    /// Less severe entries are left to the sink's own buffering, trading
+    /// durability for throughput. Set to [`log_levels::SILENT`] to never
+    /// flush, or [`log_levels::VERBOSE`] to flush after every entry.
+    pub flush_level: LogLevel,
+    /// Number of consecutive failed writes after which a sink is latched
+    /// off and no longer written to. `0` (the default) never latches.
+    ///
+    /// Avoids formatting and writing every entry to a stream that cannot
+    /// accept it, such as stdout redirected to a closed pipe. A successful
+    /// write resets the count. When a sink latches, a notice with the last
+    /// error is written once to the other sink, and entries meant for the
+    /// latched sink are counted as dropped.
+    pub write_failure_limit: u32,
+    /// Maximum number of log calls queued while logging is
+    /// [paused](crate::Consola::pause_logs). `0` (the default) is unbounded.
+    /// Group starts and ends do not count.
+    pub pause_queue_capacity: usize,
+    /// What a call made while the pause queue is full does; see
+    /// [`QueueOverflow`].
+    pub pause_queue_overflow: QueueOverflow,
+    /// Time source for entry timestamps and the `throttle` window.
+    /// Install a [`MockClock`](crate::util::MockClock) for deterministic tests.
+    pub clock: Arc<dyn Clock>,
+    /// Per-target level overrides for records received through the `log`
+    /// crate bridge. Matching targets use their own level instead of `level`.
+    pub target_levels: TargetLevels,
+    /// Redactors applied in order to each entry before it is throttled and
+    /// emitted, e.g. a `RegexRedactor` (feature `regex`) masking tokens.
+    /// Group labels and `log` / `tracing` bridge records are redacted too.
+    pub redactors: Vec<Arc<dyn Redactor>>,
+    /// Whether an entry's [`prefix`](LogObject::prefix) counts towards
+    /// throttling, so that identical messages under different prefixes are
+    /// not treated as repeats.
+    pub fingerprint_prefix: bool,
 }
 
 impl Clone for ConsolaOptions {
     fn clone(&self) -> Self {
         Self {
+            name: self.name.clone(),
             reporters: self.reporters.clone(),
             level: self.level,
             defaults: self.defaults.clone(),
             throttle: self.throttle,
             throttle_min: self.throttle_min,
+            throttle_by_type: self.throttle_by_type.clone(),
+            rate_limit: self.rate_limit,
+            error_chain_window: self.error_chain_window,
+            cardinality_limit: self.cardinality_limit,
+            cardinality_window: self.cardinality_window,
             format_options: self.format_options.clone(),
+            out_sink: self.out_sink.clone(),
+            err_sink: self.err_sink.clone(),
+            stderr_level: self.stderr_level,
+            stdout_only: self.stdout_only,
+            flush_level: self.flush_level,
+            write_failure_limit: self.write_failure_limit,
+            pause_queue_capacity: self.pause_queue_capacity,
+            pause_queue_overflow: self.pause_queue_overflow,
+            clock: self.clock.clone(),
+            target_levels: self.target_levels.clone(),
+            redactors: self.redactors.clone(),
+            fingerprint_prefix: self.fingerprint_prefix,
+        }
+    }
+}
+
+impl ConsolaOptions {
+    /// Build options from the process environment.
+    ///
+    /// See [`ConsolaOptions::from_env_vars`] for the variables consulted.
+    /// Like [`FormatOptions::from_env`], colors default to whether stdout is
+    /// a terminal.
+    pub fn from_env() -> Self {
+        Self::from_env_vars_for(|key| std::env::var(key).ok(), format::stdout_is_terminal())
+    }
+
+    /// Like [`from_env_vars`](Self::from_env_vars), with format options from
+    /// [`FormatOptions::from_env_vars_for`] so `colors` defaults to
+    /// `is_terminal`.
+    pub fn from_env_vars_for(lookup: impl Fn(&str) -> Option<String>, is_terminal: bool) -> Self {
+        let format_options = FormatOptions::from_env_vars_for(&lookup, is_terminal);
+        Self::from_env_with(lookup, format_options)
+    }
+
+    /// Build options from an environment lookup function, so every entry point
+    /// reads `CONSOLA_*` and the standard variables the same way.
+    ///
+    /// - `CONSOLA_LEVEL`: numeric level or type name (e.g. `4`, `debug`)
+    /// - `CONSOLA_FORMAT`: `basic` or `fancy` selects the reporter
+    /// - format variables handled by [`FormatOptions::from_env_vars`]
+    ///
+    /// Unset or unparsable variables leave the [`Default`] value in place.
+    pub fn from_env_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let format_options = FormatOptions::from_env_vars(&lookup);
+        Self::from_env_with(lookup, format_options)
+    }
+
+    /// Read the `CONSOLA_*` variables on top of already built format options.
+    fn from_env_with(
+        lookup: impl Fn(&str) -> Option<String>,
+        format_options: FormatOptions,
+    ) -> Self {
+        let mut opts = Self::default();
+        if let Some(level) = lookup("CONSOLA_LEVEL").and_then(|v| parse_log_level(&v)) {
+            opts.level = level;
         }
+        opts.reporters = match lookup("CONSOLA_FORMAT").as_deref().map(str::trim) {
+            Some("basic") => vec![Box::new(crate::reporters::BasicReporter::new())],
+            Some("fancy") => vec![Box::new(crate::reporters::FancyReporter::adaptive())],
+            _ => Vec::new(),
+        };
+        opts.format_options = format_options;
+        opts
+    }
+
+    /// Whether an entry at `level` is written to `err_sink` rather than
+    /// `out_sink`, based on `stderr_level` and `stdout_only`.
+    pub(crate) fn writes_to_err_sink(&self, level: LogLevel) -> bool {
+        !self.stdout_only && level <= self.stderr_level
     }
 }
 
 impl Default for ConsolaOptions {
     fn default() -> Self {
         Self {
+            name: None,
             reporters: Vec::new(),
             level: log_levels::INFO,
             defaults: LogObjectInput::default(),
             throttle: 1000,
             throttle_min: 5,
+            throttle_by_type: HashMap::new(),
+            rate_limit: None,
+            error_chain_window: 0,
+            cardinality_limit: 0,
+            cardinality_window: 1000,
             format_options: FormatOptions::default(),
+            out_sink: Arc::new(StdoutSink),
+            err_sink: Arc::new(StderrSink),
+            stderr_level: log_levels::WARN,
+            stdout_only: false,
+            flush_level: log_levels::ERROR,
+            write_failure_limit: 0,
+            pause_queue_capacity: 0,
+            pause_queue_overflow: QueueOverflow::default(),
+            clock: Arc::new(SystemClock),
+            target_levels: TargetLevels::default(),
+            redactors: Vec::new(),
+            fingerprint_prefix: false,
         }
     }
 }
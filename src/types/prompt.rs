@@ -72,6 +72,44 @@ pub struct MultiSelectOptions {
     pub required: Option<bool>,
 }
 
+/// Options for a masked password prompt. Input is never echoed.
+#[derive(Debug, Clone)]
+pub struct PasswordPromptOptions {
+    /// Shared prompt options.
+    pub common: PromptCommonOptions,
+    /// Placeholder text displayed inside the input field.
+    pub placeholder: Option<String>,
+}
+
+/// Options for a numeric input prompt.
+///
+/// Input that does not parse as a number, or falls outside `min`/`max`,
+/// is rejected and the user is asked again.
+#[derive(Debug, Clone)]
+pub struct NumberPromptOptions {
+    /// Shared prompt options.
+    pub common: PromptCommonOptions,
+    /// Default value returned if the user provides no input.
+    pub default: Option<f64>,
+    /// Smallest accepted value (inclusive).
+    pub min: Option<f64>,
+    /// Largest accepted value (inclusive).
+    pub max: Option<f64>,
+}
+
+/// Options for a text prompt with autocompletion from a list of candidates.
+#[derive(Debug, Clone)]
+pub struct AutocompletePromptOptions {
+    /// Shared prompt options.
+    pub common: PromptCommonOptions,
+    /// Candidates suggested while typing (case-insensitive substring match).
+    pub candidates: Vec<String>,
+    /// Default value returned if the user provides no input.
+    pub default: Option<String>,
+    /// Placeholder text displayed inside the input field.
+    pub placeholder: Option<String>,
+}
+
 /// Union of all supported prompt option types.
 #[derive(Debug, Clone)]
 pub enum PromptOptions {
@@ -83,4 +121,10 @@ pub enum PromptOptions {
     Select(SelectPromptOptions),
     /// Multiple selection from a list.
     MultiSelect(MultiSelectOptions),
+    /// Masked password input.
+    Password(PasswordPromptOptions),
+    /// Validated numeric input.
+    Number(NumberPromptOptions),
+    /// Text input with autocompletion.
+    Autocomplete(AutocompletePromptOptions),
 }
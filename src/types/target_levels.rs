@@ -0,0 +1,141 @@
+//! Per-target level overrides for records bridged from the `log` crate.
+
+use crate::constants::{LogLevel, parse_log_level};
+use crate::error::ConsolaError;
+
+/// Level overrides keyed by target (module path), like `env_logger`'s
+/// `RUST_LOG=mycrate=debug,other=warn`.
+///
+/// A target directive matches its own path and every module below it, so
+/// `mycrate` covers `mycrate::db` but not `mycrate2`. The most specific
+/// matching directive wins. A directive without a target sets the fallback
+/// for targets no other directive matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetLevels {
+    default: Option<LogLevel>,
+    targets: Vec<(String, LogLevel)>,
+}
+
+impl TargetLevels {
+    /// Create an empty set of overrides; every target uses the instance level.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse comma-separated directives such as `info,mycrate=debug,mycrate::db=off`.
+    ///
+    /// Levels accept the same spellings as [`parse_log_level`].
+    pub fn parse(directives: &str) -> Result<Self, ConsolaError> {
+        let mut levels = Self::new();
+        for directive in directives.split(',').map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    levels = levels.target(target.trim(), parse_directive_level(level)?);
+                }
+                None => levels = levels.default_level(parse_directive_level(directive)?),
+            }
+        }
+        Ok(levels)
+    }
+
+    /// Set the level for `target` and its submodules, returning the builder for chaining.
+    pub fn target(mut self, target: impl Into<String>, level: LogLevel) -> Self {
+        let target = target.into();
+        self.targets.retain(|(t, _)| *t != target);
+        self.targets.push((target, level));
+        self
+    }
+
+    /// Set the level for targets without a matching directive, returning the builder for chaining.
+    pub fn default_level(mut self, level: LogLevel) -> Self {
+        self.default = Some(level);
+        self
+    }
+
+    /// Whether no directive has been configured.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.targets.is_empty()
+    }
+
+    /// The level configured for `target`, or `None` to use the instance level.
+    pub fn level_for(&self, target: &str) -> Option<LogLevel> {
+        self.targets
+            .iter()
+            .filter(|(t, _)| target_matches(t, target))
+            .max_by_key(|(t, _)| t.len())
+            .map(|(_, level)| *level)
+            .or(self.default)
+    }
+}
+
+impl std::str::FromStr for TargetLevels {
+    type Err = ConsolaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Whether `directive` names `target` or one of its parent modules.
+fn target_matches(directive: &str, target: &str) -> bool {
+    target
+        .strip_prefix(directive)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+fn parse_directive_level(level: &str) -> Result<LogLevel, ConsolaError> {
+    parse_log_level(level).ok_or_else(|| ConsolaError::InvalidDirective(level.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::log_levels;
+
+    #[test]
+    fn test_parse_directives() {
+        let levels = TargetLevels::parse("info, mycrate=debug,other=WARN ,,noisy=off").unwrap();
+        assert_eq!(levels.level_for("unrelated"), Some(log_levels::INFO));
+        assert_eq!(levels.level_for("mycrate"), Some(log_levels::DEBUG));
+        assert_eq!(levels.level_for("other"), Some(log_levels::WARN));
+        assert_eq!(levels.level_for("noisy"), Some(log_levels::SILENT));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_level() {
+        assert_eq!(
+            TargetLevels::parse("mycrate=loud"),
+            Err(ConsolaError::InvalidDirective("loud".into()))
+        );
+    }
+
+    #[test]
+    fn test_prefix_matching_respects_module_boundaries() {
+        let levels = TargetLevels::new().target("mycrate", log_levels::DEBUG);
+        assert_eq!(
+            levels.level_for("mycrate::db::pool"),
+            Some(log_levels::DEBUG)
+        );
+        assert_eq!(levels.level_for("mycrate2"), None);
+    }
+
+    #[test]
+    fn test_most_specific_directive_wins() {
+        let levels = TargetLevels::parse("mycrate::db=warn,mycrate=trace").unwrap();
+        assert_eq!(
+            levels.level_for("mycrate::db::pool"),
+            Some(log_levels::WARN)
+        );
+        assert_eq!(levels.level_for("mycrate::http"), Some(log_levels::TRACE));
+    }
+
+    #[test]
+    fn test_empty_has_no_overrides() {
+        let levels = TargetLevels::parse("").unwrap();
+        assert!(levels.is_empty());
+        assert_eq!(levels.level_for("anything"), None);
+    }
+}
@@ -0,0 +1,107 @@
+//! Repeat throttling and rate limiting settings.
+
+/// How identical entries logged in quick succession are folded into a
+/// `(repeated N times)` summary.
+///
+/// Mirrors [`ConsolaOptions::throttle`](crate::ConsolaOptions::throttle) and
+/// [`throttle_min`](crate::ConsolaOptions::throttle_min); see
+/// [`Consola::set_throttle`](crate::Consola::set_throttle).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleConfig {
+    /// Window (ms) within which an identical entry counts as a repeat; `0`
+    /// disables throttling.
+    pub window_ms: u64,
+    /// Number of identical entries emitted before repeats are folded.
+    pub min_count: u32,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 1000,
+            min_count: 5,
+        }
+    }
+}
+
+impl ThrottleConfig {
+    /// Create the default config: a 1000 ms window and a `min_count` of 5.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A config that never throttles: every entry is emitted as logged.
+    pub fn disabled() -> Self {
+        Self {
+            window_ms: 0,
+            min_count: 0,
+        }
+    }
+
+    /// Set the repeat window in milliseconds, returning the builder for chaining.
+    pub fn window(mut self, window_ms: u64) -> Self {
+        self.window_ms = window_ms;
+        self
+    }
+
+    /// Set the number of entries emitted before repeats are folded, returning the builder for chaining.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Whether this config never throttles.
+    pub fn is_disabled(&self) -> bool {
+        self.window_ms == 0
+    }
+}
+
+/// Cap on the number of entries emitted per second, whatever their content
+/// (see [`ConsolaOptions::rate_limit`](crate::ConsolaOptions::rate_limit)).
+///
+/// Works as a token bucket holding up to `burst` entries and refilled at
+/// `per_second` entries per second with millisecond resolution. An entry
+/// arriving at an empty bucket is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Sustained number of entries allowed per second.
+    pub per_second: u32,
+    /// Number of entries allowed in a burst, i.e. the bucket's capacity.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Allow `per_second` entries per second, with bursts of the same size.
+    pub fn new(per_second: u32) -> Self {
+        Self {
+            per_second,
+            burst: per_second,
+        }
+    }
+
+    /// Set the number of entries allowed in a burst, returning the builder for chaining.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_and_disabled() {
+        let config = ThrottleConfig::new().window(250).min_count(2);
+        assert_eq!(
+            config,
+            ThrottleConfig {
+                window_ms: 250,
+                min_count: 2
+            }
+        );
+        assert!(!config.is_disabled());
+        assert!(ThrottleConfig::disabled().is_disabled());
+        assert!(config.window(0).is_disabled());
+    }
+}
@@ -1,7 +1,7 @@
 //! Box drawing utilities.
 
 use crate::util::color::get_color;
-use crate::util::string::string_width;
+use crate::util::string::{string_width, truncate_display, wrap_text};
 
 /// A box border style (owned strings to allow colorization).
 #[derive(Debug, Clone)]
@@ -98,7 +98,7 @@ fn style_preset(name: &str) -> BoxBorderStyle {
     }
 }
 
-/// Box style configuration — border color, preset style, vertical alignment, padding, and margins.
+/// Box style configuration — border color, preset style, alignment, padding, and margins.
 ///
 /// Created with [`BoxStyle::default()`] or constructed directly. Used by [`BoxOpts`]
 /// and consumed by [`box_text`].
@@ -110,7 +110,11 @@ pub struct BoxStyle {
     pub border_style: String,
     /// Vertical alignment of content ("top", "center", "bottom").
     pub valign: String,
-    /// Padding width inside the box around the content.
+    /// Horizontal alignment of content lines ("left", "center", "right")
+    /// within the widest line or title.
+    pub align: String,
+    /// Padding width inside the box around the content. Odd values are
+    /// rounded up to the next even width.
     pub padding: usize,
     /// Number of spaces to indent the box from the left.
     pub margin_left: usize,
@@ -126,6 +130,7 @@ impl Default for BoxStyle {
             border_color: "white".into(),
             border_style: "rounded".into(),
             valign: "center".into(),
+            align: "left".into(),
             padding: 2,
             margin_left: 1,
             margin_top: 1,
@@ -143,6 +148,13 @@ pub struct BoxOpts {
     pub title: Option<String>,
     /// Optional box style configuration.
     pub style: Option<BoxStyle>,
+    /// Maximum total width of the box, borders included (margins excluded).
+    ///
+    /// Content lines wider than the available inner width are word-wrapped and
+    /// an over-long title is truncated with an ellipsis. A width too small
+    /// for the borders and padding is raised to fit one column of content
+    /// rather than failing. `None` lets the box grow to fit its widest line.
+    pub max_width: Option<usize>,
 }
 
 /// Apply `color_fn` to the border portion (after `left_space` margin) of a line.
@@ -167,7 +179,7 @@ fn color_border_line(
 /// Draw a styled box around `text` using the given options.
 ///
 /// Supports configurable border style, border color, title, padding, margins,
-/// and vertical alignment. The title is centered within the top border line.
+/// and horizontal and vertical alignment. The title is centered within the top border line.
 /// Returns the fully formatted box as a single string with newlines.
 pub fn box_text(text: &str, opts: &BoxOpts) -> String {
     let style = opts.style.clone().unwrap_or_default();
@@ -179,20 +191,32 @@ pub fn box_text(text: &str, opts: &BoxOpts) -> String {
     // individual coloring, to avoid coloring content text)
     let v = color_fn(&preset.v);
 
-    let text_lines: Vec<&str> = text.split('\n').collect();
     let padding_offset = if style.padding.is_multiple_of(2) {
         style.padding
     } else {
         style.padding + 1
     };
 
+    // Room left for content once both borders and both paddings are accounted for.
+    let inner_limit = opts
+        .max_width
+        .map(|w| w.saturating_sub(2 + 2 * padding_offset).max(1));
+    let text_lines: Vec<String> = match inner_limit {
+        Some(limit) => wrap_text(text, limit),
+        None => text.split('\n').map(String::from).collect(),
+    };
+    let title = opts.title.as_ref().map(|t| match inner_limit {
+        Some(limit) => truncate_display(t, limit, "…"),
+        None => t.clone(),
+    });
+
     let max_line_width = text_lines
         .iter()
         .map(|l| string_width(l))
         .max()
         .unwrap_or(0);
 
-    let title_width = opts.title.as_ref().map(|t| string_width(t)).unwrap_or(0);
+    let title_width = title.as_ref().map(|t| string_width(t)).unwrap_or(0);
     let width = max_line_width.max(title_width) + padding_offset;
     let width_offset = width + padding_offset;
 
@@ -210,7 +234,7 @@ pub fn box_text(text: &str, opts: &BoxOpts) -> String {
 
     // Top border — built as plain text then colored once to avoid per-char ANSI breaks
     // between the corner and the horizontal run, which can cause visible seams.
-    if let Some(title) = &opts.title {
+    if let Some(title) = &title {
         let left_count = (width - title_width) / 2;
         let right_count = width - title_width - left_count + padding_offset;
         let raw = format!(
@@ -245,9 +269,16 @@ pub fn box_text(text: &str, opts: &BoxOpts) -> String {
         let content = if i < valign_offset || i >= valign_offset + text_lines.len() {
             " ".repeat(width_offset)
         } else {
-            let line = text_lines[i - valign_offset];
-            let right = " ".repeat(width - string_width(line));
-            let left_pad = " ".repeat(padding_offset);
+            let line = &text_lines[i - valign_offset];
+            // Free columns beside the line, excluding the padding.
+            let free = width - padding_offset - string_width(line);
+            let shift = match style.align.as_str() {
+                "center" => free / 2,
+                "right" => free,
+                _ => 0,
+            };
+            let left_pad = " ".repeat(padding_offset + shift);
+            let right = " ".repeat(width - string_width(line) - shift);
             format!("{}{}{}", left_pad, line, right)
         };
         // All lines use same structure: colored v on both sides, plain content in between.
@@ -282,6 +313,7 @@ mod tests {
         assert_eq!(style.border_color, "white");
         assert_eq!(style.border_style, "rounded");
         assert_eq!(style.valign, "center");
+        assert_eq!(style.align, "left");
         assert_eq!(style.padding, 2);
         assert_eq!(style.margin_left, 1);
         assert_eq!(style.margin_top, 1);
@@ -460,6 +492,94 @@ mod tests {
         assert!(result.contains('╯'));
     }
 
+    #[test]
+    fn test_box_opts_max_width_default() {
+        assert!(BoxOpts::default().max_width.is_none());
+    }
+
+    fn box_lines(text: &str, opts: &BoxOpts) -> Vec<String> {
+        box_text(text, opts)
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_box_text_wraps_long_lines() {
+        let opts = BoxOpts {
+            max_width: Some(20),
+            ..Default::default()
+        };
+        let lines = box_lines("the quick brown fox jumps over the lazy dog", &opts);
+        for line in &lines {
+            // margin_left (1) + max_width (20)
+            assert_eq!(string_width(line), 21, "misaligned line: {:?}", line);
+        }
+        let body: String = lines
+            .iter()
+            .map(|l| l.trim_matches(|c| c == ' ' || c == '│'))
+            .filter(|l| !l.is_empty() && !l.starts_with('╭') && !l.starts_with('╰'))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(body, "the quick brown fox jumps over the lazy dog");
+    }
+
+    #[test]
+    fn test_box_text_hard_breaks_long_tokens() {
+        let opts = BoxOpts {
+            max_width: Some(10),
+            ..Default::default()
+        };
+        let lines = box_lines("abcdefghijklmnop", &opts);
+        assert!(lines.iter().all(|l| string_width(l) == 11));
+        assert!(lines.iter().any(|l| l.contains("abcd")));
+        assert!(lines.iter().any(|l| l.contains("mnop")));
+    }
+
+    #[test]
+    fn test_box_text_wraps_cjk_aligned() {
+        let opts = BoxOpts {
+            max_width: Some(15),
+            ..Default::default()
+        };
+        let lines = box_lines("你好世界你好世界你好世界", &opts);
+        assert!(lines.len() > 3, "expected wrapped content: {:?}", lines);
+        // Double-width chars cannot fill the odd last column, so the box may be
+        // one narrower than the limit, but every line must share one width.
+        let width = string_width(&lines[0]);
+        assert!(width <= 16);
+        for line in &lines {
+            assert_eq!(string_width(line), width, "misaligned line: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_box_text_truncates_long_title() {
+        let opts = BoxOpts {
+            title: Some("a very long title that cannot fit".into()),
+            max_width: Some(20),
+            ..Default::default()
+        };
+        let lines = box_lines("hi", &opts);
+        assert!(
+            lines[0].contains('…'),
+            "title not truncated: {:?}",
+            lines[0]
+        );
+        assert!(!lines[0].contains("cannot fit"));
+        for line in &lines {
+            assert_eq!(string_width(line), 21, "misaligned line: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_box_text_no_wrap_without_max_width() {
+        let long = "x".repeat(200);
+        let result = box_text(&long, &BoxOpts::default());
+        assert!(result.contains(&long));
+    }
+
     #[test]
     fn test_box_text_style_alternate_names() {
         // doubleSingle and double_single are equivalent
@@ -481,4 +601,76 @@ mod tests {
         };
         assert_eq!(box_text("x", &opts_a), box_text("x", &opts_b));
     }
+
+    fn plain_box(text: &str, title: Option<&str>, align: &str, padding: usize) -> String {
+        let opts = BoxOpts {
+            title: title.map(String::from),
+            style: Some(BoxStyle {
+                align: align.into(),
+                padding,
+                margin_left: 0,
+                margin_top: 0,
+                margin_bottom: 0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        box_text(text, &opts)
+    }
+
+    #[test]
+    fn test_box_text_align_center_and_right() {
+        assert_eq!(
+            plain_box("ok\nfailed", None, "center", 0),
+            "╭──────╮\n│  ok  │\n│failed│\n╰──────╯"
+        );
+        assert_eq!(
+            plain_box("ok", Some("status"), "right", 0),
+            "╭status╮\n│    ok│\n╰──────╯"
+        );
+    }
+
+    #[test]
+    fn test_box_text_padding_keeps_borders_aligned() {
+        assert_eq!(
+            plain_box("hi", None, "center", 4),
+            "╭──────────╮\n\
+             │          │\n\
+             │          │\n\
+             │    hi    │\n\
+             │          │\n\
+             │          │\n\
+             ╰──────────╯"
+        );
+        // Odd padding rounds up to the next even width.
+        assert_eq!(
+            plain_box("hi", None, "left", 3),
+            plain_box("hi", None, "left", 4)
+        );
+        for line in plain_box("centered\nx", Some("t"), "center", 2).lines() {
+            assert_eq!(string_width(line), 14, "misaligned line: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_box_text_tiny_max_width_does_not_panic() {
+        for max_width in 0..=2 {
+            for (text, title) in [
+                ("hello world", None),
+                ("你好", Some("标题")),
+                ("", Some("t")),
+            ] {
+                let opts = BoxOpts {
+                    title: title.map(String::from),
+                    max_width: Some(max_width),
+                    ..Default::default()
+                };
+                let lines = box_lines(text, &opts);
+                let width = string_width(&lines[0]);
+                for line in &lines {
+                    assert_eq!(string_width(line), width, "misaligned line: {:?}", line);
+                }
+            }
+        }
+    }
 }
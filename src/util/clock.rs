@@ -0,0 +1,116 @@
+//! Time sources used for timestamps and throttling.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::sync::Mutex;
+
+/// Source of the current time.
+///
+/// `Consola` reads the clock once per log call: `now_ms` becomes the entry's
+/// timestamp and `now` drives the repeat-throttling window (see
+/// [`ConsolaOptions`](crate::ConsolaOptions)).
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    /// Wall-clock time in milliseconds since the Unix epoch.
+    fn now_ms(&self) -> i64;
+
+    /// Monotonic time used to measure throttle windows, or `None` where no
+    /// monotonic clock is available (which disables throttling).
+    fn now(&self) -> Option<Instant>;
+}
+
+/// Clock reading the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        crate::types::now_ms()
+    }
+
+    fn now(&self) -> Option<Instant> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Some(Instant::now());
+        #[cfg(target_arch = "wasm32")]
+        return None;
+    }
+}
+
+/// Manually driven clock for deterministic tests.
+///
+/// Time only moves when [`advance`](Self::advance) is called. Clones share
+/// the same time, so a clone can be installed in `Consola` while the original
+/// is kept to advance it between log calls.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    start_ms: i64,
+    start: Instant,
+    offset: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// Create a clock frozen at `start_ms` milliseconds since the Unix epoch.
+    pub fn new(start_ms: i64) -> Self {
+        Self {
+            start_ms,
+            start: Instant::now(),
+            offset: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.offset.lock() += by;
+    }
+
+    /// Time elapsed since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.offset.lock()
+    }
+}
+
+impl Clock for MockClock {
+    fn now_ms(&self) -> i64 {
+        self.start_ms + self.elapsed().as_millis() as i64
+    }
+
+    fn now(&self) -> Option<Instant> {
+        Some(self.start + self.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_is_frozen_until_advanced() {
+        let clock = MockClock::new(1_000);
+        let before = clock.now();
+        assert_eq!(clock.now_ms(), 1_000);
+        assert_eq!(clock.now(), before);
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(clock.now_ms(), 1_250);
+        assert_eq!(
+            clock.now().unwrap() - before.unwrap(),
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn test_mock_clock_clones_share_time() {
+        let clock = MockClock::new(0);
+        let shared: Arc<dyn Clock> = Arc::new(clock.clone());
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(shared.now_ms(), 2_000);
+    }
+
+    #[test]
+    fn test_system_clock_moves_forward() {
+        let a = SystemClock.now_ms();
+        let b = SystemClock.now_ms();
+        assert!(b >= a);
+        assert!(SystemClock.now().is_some());
+    }
+}
@@ -15,14 +15,11 @@ pub fn set_color_enabled(enabled: bool) {
 /// `--no-color`, `--color`, and terminal detection.
 pub fn color_enabled() -> bool {
     *COLOR_ENABLED.get_or_init(|| {
-        let no_color = std::env::var("NO_COLOR").is_ok();
-        let force_color = std::env::var("FORCE_COLOR").is_ok();
-        let is_disabled = no_color || std::env::args().any(|a| a == "--no-color");
-        let is_forced = force_color || std::env::args().any(|a| a == "--color");
-        if is_disabled {
+        let env = crate::types::format::env_color_override(&|key: &str| std::env::var(key).ok());
+        if env == Some(false) || std::env::args().any(|a| a == "--no-color") {
             return false;
         }
-        if is_forced {
+        if env == Some(true) || std::env::args().any(|a| a == "--color") {
             return true;
         }
         use std::io::IsTerminal;
@@ -30,6 +27,230 @@ pub fn color_enabled() -> bool {
     })
 }
 
+/// How many colors the terminal can display.
+///
+/// Ordered from least to most capable, so `depth >= ColorDepth::Ansi256`
+/// asks whether 256-color output is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// No color support.
+    None,
+    /// The 16 basic ANSI colors.
+    Ansi16,
+    /// The 256-color xterm palette.
+    Ansi256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detect the color depth from the process environment.
+    ///
+    /// See [`ColorDepth::from_env_vars`] for the variables consulted.
+    pub fn detect() -> Self {
+        Self::from_env_vars(|key| std::env::var(key).ok())
+    }
+
+    /// Detect the color depth from an environment lookup function.
+    ///
+    /// - `NO_COLOR` or `FORCE_COLOR=0` → `None`
+    /// - `FORCE_COLOR=2` / `FORCE_COLOR=3` → `Ansi256` / `TrueColor`
+    /// - `COLORTERM=truecolor` / `24bit`, or `WT_SESSION` (Windows Terminal) → `TrueColor`
+    /// - `TERM` containing `256color` → `Ansi256`
+    /// - `TERM=dumb` → `None`, unless `FORCE_COLOR` is set
+    /// - otherwise `Ansi16`
+    pub fn from_env_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        if lookup("NO_COLOR").is_some() {
+            return Self::None;
+        }
+        let force = lookup("FORCE_COLOR");
+        match force.as_deref().map(str::trim) {
+            Some("0" | "false") => return Self::None,
+            Some("2") => return Self::Ansi256,
+            Some("3") => return Self::TrueColor,
+            _ => {}
+        }
+        let colorterm = lookup("COLORTERM").unwrap_or_default();
+        if matches!(colorterm.as_str(), "truecolor" | "24bit") || lookup("WT_SESSION").is_some() {
+            return Self::TrueColor;
+        }
+        let term = lookup("TERM").unwrap_or_default();
+        if term.contains("256color") {
+            Self::Ansi256
+        } else if term == "dumb" && force.is_none() {
+            Self::None
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// The closest representation of `color` this depth can display, or
+    /// `None` when it cannot display color at all.
+    pub fn downgrade(self, color: anstyle::Color) -> Option<anstyle::Color> {
+        use anstyle::Color;
+        Some(match (self, color) {
+            (Self::None, _) => return None,
+            (Self::Ansi256, Color::Rgb(rgb)) => Color::Ansi256(rgb_to_ansi256(rgb)),
+            (Self::Ansi16, Color::Rgb(rgb)) => Color::Ansi(rgb_to_ansi16(rgb)),
+            (Self::Ansi16, Color::Ansi256(c)) => Color::Ansi(
+                c.into_ansi()
+                    .unwrap_or_else(|| rgb_to_ansi16(ansi256_to_rgb(c.0))),
+            ),
+            (_, c) => c,
+        })
+    }
+}
+
+/// Approximate RGB values of the 16 basic ANSI colors (xterm defaults).
+const ANSI16_RGB: [(anstyle::AnsiColor, (u8, u8, u8)); 16] = {
+    use anstyle::AnsiColor::*;
+    [
+        (Black, (0, 0, 0)),
+        (Red, (205, 0, 0)),
+        (Green, (0, 205, 0)),
+        (Yellow, (205, 205, 0)),
+        (Blue, (0, 0, 238)),
+        (Magenta, (205, 0, 205)),
+        (Cyan, (0, 205, 205)),
+        (White, (229, 229, 229)),
+        (BrightBlack, (127, 127, 127)),
+        (BrightRed, (255, 0, 0)),
+        (BrightGreen, (0, 255, 0)),
+        (BrightYellow, (255, 255, 0)),
+        (BrightBlue, (92, 92, 255)),
+        (BrightMagenta, (255, 0, 255)),
+        (BrightCyan, (0, 255, 255)),
+        (BrightWhite, (255, 255, 255)),
+    ]
+};
+
+/// Channel values of the 6x6x6 color cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn rgb_to_ansi256(rgb: anstyle::RgbColor) -> anstyle::Ansi256Color {
+    let anstyle::RgbColor(r, g, b) = rgb;
+    if r == g && g == b {
+        // Use the finer grayscale ramp (232-255, 8..=238 in steps of 10).
+        return anstyle::Ansi256Color(match r {
+            0..8 => 16,
+            249.. => 231,
+            v => 232 + ((v - 8) as u16 * 24 / 247) as u8,
+        });
+    }
+    // Index of the nearest cube level.
+    let level = |v: u8| match v {
+        0..48 => 0,
+        48..115 => 1,
+        v => (v - 35) / 40,
+    };
+    anstyle::Ansi256Color(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
+
+fn ansi256_to_rgb(index: u8) -> anstyle::RgbColor {
+    let (r, g, b) = match index {
+        0..16 => ANSI16_RGB[index as usize].1,
+        16..232 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    };
+    anstyle::RgbColor(r, g, b)
+}
+
+fn rgb_to_ansi16(rgb: anstyle::RgbColor) -> anstyle::AnsiColor {
+    let anstyle::RgbColor(r, g, b) = rgb;
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, cr) + d(g, cg) + d(b, cb)
+    };
+    ANSI16_RGB
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(anstyle::AnsiColor::White)
+}
+
+/// Parse a `#rrggbb` or `#rgb` hex color.
+pub fn parse_hex_color(hex: &str) -> Option<anstyle::Color> {
+    let digits = hex.strip_prefix('#')?;
+    if !digits.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    let (r, g, b) = match digits.len() {
+        6 => (
+            channel(&digits[0..2])?,
+            channel(&digits[2..4])?,
+            channel(&digits[4..6])?,
+        ),
+        3 => (
+            channel(&digits[0..1])? * 17,
+            channel(&digits[1..2])? * 17,
+            channel(&digits[2..3])? * 17,
+        ),
+        _ => return None,
+    };
+    Some(anstyle::Color::Rgb(anstyle::RgbColor(r, g, b)))
+}
+
+/// Color `text` with `fg`, downgraded to what `depth` can display.
+///
+/// Returns `text` unchanged when colors are disabled or `depth` is
+/// [`ColorDepth::None`].
+pub fn paint(text: &str, fg: anstyle::Color, depth: ColorDepth) -> String {
+    paint_style(
+        text,
+        depth
+            .downgrade(fg)
+            .map(|c| anstyle::Style::new().fg_color(Some(c))),
+    )
+}
+
+/// Give `text` the background `bg`, downgraded to what `depth` can display.
+///
+/// Like [`paint`], returns `text` unchanged when color is unavailable.
+pub fn paint_bg(text: &str, bg: anstyle::Color, depth: ColorDepth) -> String {
+    paint_style(
+        text,
+        depth
+            .downgrade(bg)
+            .map(|c| anstyle::Style::new().bg_color(Some(c))),
+    )
+}
+
+/// Color `text` with a color name or `#rrggbb` hex color at `depth`,
+/// whether or not colors are globally enabled.
+pub(crate) fn paint_always(name: &str, text: &str, depth: ColorDepth) -> String {
+    let fg = match parse_hex_color(name) {
+        Some(c) => depth.downgrade(c),
+        None if depth == ColorDepth::None => None,
+        None => style(name).get_fg_color(),
+    };
+    match fg {
+        Some(c) => format!(
+            "{}{text}{}",
+            anstyle::Style::new().fg_color(Some(c)),
+            anstyle::Reset
+        ),
+        None => text.to_string(),
+    }
+}
+
+fn paint_style(text: &str, style: Option<anstyle::Style>) -> String {
+    match style {
+        Some(s) if color_enabled() => format!("{s}{text}{}", anstyle::Reset),
+        _ => text.to_string(),
+    }
+}
+
 fn style(name: &str) -> anstyle::Style {
     let color = match name {
         "black" => Some(anstyle::AnsiColor::Black),
@@ -768,4 +989,99 @@ mod tests {
             assert_eq!(result, "x");
         }
     }
+
+    fn depth_for(vars: &[(&str, &str)]) -> ColorDepth {
+        ColorDepth::from_env_vars(|key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn test_color_depth_detection() {
+        assert_eq!(depth_for(&[]), ColorDepth::Ansi16);
+        assert_eq!(depth_for(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(
+            depth_for(&[("TERM", "xterm-256color")]),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            depth_for(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(depth_for(&[("COLORTERM", "24bit")]), ColorDepth::TrueColor);
+        assert_eq!(depth_for(&[("WT_SESSION", "1")]), ColorDepth::TrueColor);
+        assert_eq!(depth_for(&[("TERM", "dumb")]), ColorDepth::None);
+    }
+
+    #[test]
+    fn test_color_depth_env_overrides() {
+        assert_eq!(depth_for(&[("FORCE_COLOR", "3")]), ColorDepth::TrueColor);
+        assert_eq!(depth_for(&[("FORCE_COLOR", "2")]), ColorDepth::Ansi256);
+        assert_eq!(
+            depth_for(&[("FORCE_COLOR", "1"), ("TERM", "dumb")]),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            depth_for(&[("FORCE_COLOR", "0"), ("COLORTERM", "truecolor")]),
+            ColorDepth::None
+        );
+        assert_eq!(
+            depth_for(&[("NO_COLOR", "1"), ("FORCE_COLOR", "3")]),
+            ColorDepth::None
+        );
+    }
+
+    #[test]
+    fn test_color_depth_downgrade() {
+        use anstyle::{Ansi256Color, AnsiColor, Color, RgbColor};
+        let orange = Color::Rgb(RgbColor(255, 135, 0));
+        assert_eq!(ColorDepth::TrueColor.downgrade(orange), Some(orange));
+        assert_eq!(
+            ColorDepth::Ansi256.downgrade(orange),
+            Some(Color::Ansi256(Ansi256Color(208)))
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.downgrade(orange),
+            Some(Color::Ansi(AnsiColor::Yellow))
+        );
+        assert_eq!(ColorDepth::None.downgrade(orange), None);
+
+        let gray = Color::Rgb(RgbColor(128, 128, 128));
+        assert_eq!(
+            ColorDepth::Ansi256.downgrade(gray),
+            Some(Color::Ansi256(Ansi256Color(243)))
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.downgrade(Color::Ansi256(Ansi256Color(196))),
+            Some(Color::Ansi(AnsiColor::BrightRed))
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.downgrade(Color::Ansi256(Ansi256Color(4))),
+            Some(Color::Ansi(AnsiColor::Blue))
+        );
+        let red = Color::Ansi(AnsiColor::Red);
+        assert_eq!(ColorDepth::Ansi16.downgrade(red), Some(red));
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        use anstyle::{Color, RgbColor};
+        assert_eq!(
+            parse_hex_color("#ff8700"),
+            Some(Color::Rgb(RgbColor(255, 135, 0)))
+        );
+        assert_eq!(
+            parse_hex_color("#0f8"),
+            Some(Color::Rgb(RgbColor(0, 255, 136)))
+        );
+        assert_eq!(parse_hex_color("ff8700"), None);
+        assert_eq!(parse_hex_color("#ff87"), None);
+        assert_eq!(parse_hex_color("#gggggg"), None);
+        assert_eq!(
+            paint("x", Color::Rgb(RgbColor(1, 2, 3)), ColorDepth::None),
+            "x"
+        );
+    }
 }
@@ -0,0 +1,33 @@
+//! Console encoding helpers for legacy Windows consoles.
+
+/// Windows code page identifier for UTF-8.
+pub const CP_UTF8: u32 = 65001;
+
+/// The console's output code page, or `None` when there is no console or
+/// the platform has no code pages (anything but Windows).
+#[cfg_attr(windows, allow(unsafe_code))]
+pub fn console_code_page() -> Option<u32> {
+    #[cfg(windows)]
+    // SAFETY: `GetConsoleOutputCP` has no preconditions and returns 0 on failure.
+    return match unsafe { windows_sys::Win32::System::Console::GetConsoleOutputCP() } {
+        0 => None,
+        cp => Some(cp),
+    };
+    #[cfg(not(windows))]
+    return None;
+}
+
+/// Switch the console output code page to UTF-8 so Unicode icons render
+/// correctly on legacy Windows consoles.
+///
+/// Returns whether the switch succeeded; always `true` on other platforms,
+/// where this does nothing. [`FancyReporter`](crate::reporters::FancyReporter)
+/// detects Unicode support once, so call this before creating one.
+#[cfg_attr(windows, allow(unsafe_code))]
+pub fn enable_windows_utf8() -> bool {
+    #[cfg(windows)]
+    // SAFETY: `SetConsoleOutputCP` has no preconditions and returns 0 on failure.
+    return unsafe { windows_sys::Win32::System::Console::SetConsoleOutputCP(CP_UTF8) } != 0;
+    #[cfg(not(windows))]
+    return true;
+}
@@ -0,0 +1,176 @@
+//! Helpers for rendering errors and turning stack traces into displayable
+//! frame lines.
+
+use crate::reporters::{BasicReporter, FancyReporter};
+use crate::types::{ErrorInfo, FormatOptions};
+
+/// Render `err` and its [`source`](std::error::Error::source) chain the way
+/// the text reporters render an attached error, for use outside of logging
+/// (e.g. returning a formatted error to a caller).
+///
+/// Each cause is on its own `[cause]: ` line, indented one level deeper,
+/// or on one `message: cause: cause` line when `opts` requests inline
+/// chains (`inline_error_chain` or `compact`). As in the reporters, a
+/// non-zero `error_level` caps the number of causes of an inline chain and
+/// the multi-line form always shows the full chain. With `colors` set the
+/// output uses the fancy reporter's styling.
+///
+/// ```
+/// use consola::{FormatOptions, format_error};
+///
+/// let err = std::io::Error::other("disk full");
+/// assert_eq!(format_error(&err, &FormatOptions::default()), "disk full");
+/// ```
+pub fn format_error(err: &(dyn std::error::Error + 'static), opts: &FormatOptions) -> String {
+    let info = ErrorInfo::from_error(err);
+    let opts = opts.effective();
+    if opts.inline_error_chain {
+        info.inline_chain(opts.error_level)
+    } else if opts.colors {
+        FancyReporter::format_error(&info, &opts, 0)
+    } else {
+        BasicReporter::format_error(&info, &opts, 0)
+    }
+}
+
+/// Split a stack trace or backtrace into trimmed, non-empty frame lines.
+///
+/// `file://` prefixes and the current working directory are stripped so
+/// paths are shown relative to the project, matching consola-js.
+pub fn parse_error_stack(stack: &str) -> Vec<String> {
+    let cwd = std::env::current_dir()
+        .ok()
+        .map(|dir| dir.display().to_string());
+    parse_error_stack_in(stack, cwd.as_deref())
+}
+
+fn parse_error_stack_in(stack: &str, cwd: Option<&str>) -> Vec<String> {
+    stack
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let line = line.replace("file://", "");
+            match cwd {
+                Some(cwd) if !cwd.is_empty() => line
+                    .replace(&format!("{}/", cwd), "")
+                    .replace(&format!("{}\\", cwd), ""),
+                _ => line,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_stack_trims_and_skips_blank_lines() {
+        let stack = "   0: app::main\n\n             at src/main.rs:3:5\n";
+        assert_eq!(
+            parse_error_stack_in(stack, None),
+            vec!["0: app::main", "at src/main.rs:3:5"]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_stack_strips_file_scheme_and_cwd() {
+        let stack = "at file:///home/me/app/src/lib.rs:1:1\nat /home/me/app/src/main.rs:2:2\nat /usr/lib/x.rs:3:3";
+        assert_eq!(
+            parse_error_stack_in(stack, Some("/home/me/app")),
+            vec![
+                "at src/lib.rs:1:1",
+                "at src/main.rs:2:2",
+                "at /usr/lib/x.rs:3:3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_stack_uses_current_dir() {
+        let cwd = std::env::current_dir().unwrap();
+        let stack = format!("at {}/src/lib.rs:1:1", cwd.display());
+        assert_eq!(parse_error_stack(&stack), vec!["at src/lib.rs:1:1"]);
+    }
+
+    #[derive(Debug)]
+    struct Chained(&'static str, Option<Box<Chained>>);
+
+    impl std::fmt::Display for Chained {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Chained {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|e| e as _)
+        }
+    }
+
+    fn three_deep() -> Chained {
+        Chained(
+            "request failed",
+            Some(Box::new(Chained(
+                "connection reset",
+                Some(Box::new(Chained("broken pipe", None))),
+            ))),
+        )
+    }
+
+    #[test]
+    fn test_format_error_renders_cause_lines() {
+        assert_eq!(
+            format_error(&three_deep(), &FormatOptions::default()),
+            "request failed\n\n  [cause]: connection reset\n\n    [cause]: broken pipe"
+        );
+    }
+
+    #[test]
+    fn test_format_error_matches_basic_reporter() {
+        use crate::constants::LogType;
+        use crate::types::LogObject;
+
+        let mut obj = LogObject::new(LogType::Error);
+        obj.args = vec!["failed".into()];
+        obj.error = Some(ErrorInfo::from_error(&three_deep()));
+        for opts in [
+            FormatOptions::default(),
+            FormatOptions {
+                error_level: 1,
+                ..FormatOptions::default()
+            },
+        ] {
+            assert_eq!(
+                BasicReporter::new().format_log_obj(&obj, &opts),
+                format!("[error] failed\n{}", format_error(&three_deep(), &opts))
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_error_caps_inline_chain_at_error_level() {
+        let opts = FormatOptions {
+            inline_error_chain: true,
+            error_level: 1,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            format_error(&three_deep(), &opts),
+            "request failed: connection reset"
+        );
+    }
+
+    #[test]
+    fn test_format_error_inline() {
+        let opts = FormatOptions {
+            compact: true,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            format_error(&three_deep(), &opts),
+            "request failed: connection reset: broken pipe"
+        );
+    }
+}
@@ -1,18 +1,45 @@
 //! Utility modules providing string formatting, ANSI color support, box drawing,
-//! tree display formatting, and log object detection helpers.
+//! table and tree display formatting, output sinks, and log object detection helpers.
 
 /// Box drawing utilities for creating styled text boxes.
 pub mod boxes;
+/// Time sources (system and mock) for timestamps and throttling.
+pub mod clock;
 /// ANSI color and styling functions.
 pub mod color;
+/// Console encoding helpers for legacy Windows consoles.
+pub mod console;
+/// Stack trace parsing helpers.
+pub mod error;
 /// Log object detection helpers.
 pub mod log;
+/// Redaction hooks for masking secrets before entries are emitted.
+pub mod redact;
+/// Output destinations (stdout, stderr, in-memory) for formatted log lines.
+pub mod sink;
 /// Unicode-aware string utilities (alignment, ANSI stripping).
 pub mod string;
+/// Aligned plain-text table rendering.
+pub mod table;
 /// Tree structure display formatting.
 pub mod tree;
 
 pub use boxes::{BoxOpts, BoxStyle, box_text};
-pub use color::{color_enabled, colorize, get_color, set_color_enabled};
-pub use string::{align, center_align, left_align, right_align, string_width, strip_ansi};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use color::{ColorDepth, color_enabled, colorize, get_color, set_color_enabled};
+pub use console::enable_windows_utf8;
+pub use error::{format_error, parse_error_stack};
+pub use redact::Redactor;
+#[cfg(feature = "regex")]
+pub use redact::RegexRedactor;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sink::{AsyncSink, Backpressure, Overflow};
+#[cfg(feature = "testing")]
+pub use sink::{CAPTURE_LIMIT, ThreadLocalSink, captured_output, take_captured_output};
+pub use sink::{Sink, StderrSink, StdoutSink, TestSink, WriterSink};
+pub use string::{
+    align, center_align, coalesce_styles, left_align, right_align, string_width, strip_ansi,
+    truncate_display, wrap_text,
+};
+pub use table::TableBuilder;
 pub use tree::{TreeItem, TreeOptions, format_tree};
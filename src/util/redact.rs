@@ -0,0 +1,134 @@
+//! Hooks for masking secrets in log entries before they are emitted.
+
+use crate::types::LogObject;
+
+/// Rewrites a log entry before it reaches throttling and the reporters.
+///
+/// Redactors listed in [`ConsolaOptions::redactors`](crate::ConsolaOptions)
+/// run in order on every entry that passes the level filter, including
+/// group labels and records from the `log` and `tracing` bridges. They run
+/// before the entry is fingerprinted, so messages that only differ in a
+/// masked secret are throttled as repeats of each other.
+pub trait Redactor: std::fmt::Debug + Send + Sync {
+    /// Mask sensitive data in `log_obj` in place.
+    fn redact(&self, log_obj: &mut LogObject);
+}
+
+/// Replaces every match of a regular expression with a mask (`***` by default).
+///
+/// Applies to the message arguments, the tag, title, additional text, field
+/// values and the whole error chain (messages, stacks and backtraces).
+/// Requires the `regex` Cargo feature.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct RegexRedactor {
+    pattern: regex::Regex,
+    mask: String,
+}
+
+#[cfg(feature = "regex")]
+impl RegexRedactor {
+    /// Create a redactor masking matches of `pattern` with `***`.
+    pub fn new(pattern: regex::Regex) -> Self {
+        Self {
+            pattern,
+            mask: "***".to_string(),
+        }
+    }
+
+    /// Set the text that replaces each match, returning the builder for chaining.
+    pub fn mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    fn replace(&self, text: &mut String) {
+        if let std::borrow::Cow::Owned(replaced) =
+            self.pattern.replace_all(text, regex::NoExpand(&self.mask))
+        {
+            *text = replaced;
+        }
+    }
+
+    fn replace_opt(&self, text: &mut Option<String>) {
+        if let Some(text) = text {
+            self.replace(text);
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Redactor for RegexRedactor {
+    fn redact(&self, log_obj: &mut LogObject) {
+        log_obj.args.iter_mut().for_each(|arg| self.replace(arg));
+        self.replace(&mut log_obj.tag);
+        self.replace_opt(&mut log_obj.message);
+        self.replace_opt(&mut log_obj.additional);
+        self.replace_opt(&mut log_obj.title);
+        log_obj
+            .fields
+            .iter_mut()
+            .for_each(|(_, value)| self.replace(value));
+        let mut error = log_obj.error.as_mut();
+        while let Some(err) = error {
+            self.replace(&mut err.message);
+            self.replace_opt(&mut err.stack);
+            self.replace_opt(&mut err.backtrace);
+            error = err.cause.as_deref_mut();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "regex"))]
+mod tests {
+    use super::*;
+    use crate::constants::LogType;
+    use crate::types::ErrorInfo;
+
+    #[test]
+    fn test_regex_redactor_masks_args_title_and_fields() {
+        let redactor = RegexRedactor::new(regex::Regex::new(r"tok_\w+").unwrap());
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["using tok_abc123 and tok_def".into(), "plain".into()];
+        obj.title = Some("tok_x".into());
+        obj.fields = vec![("auth".into(), "Bearer tok_zz".into())];
+        redactor.redact(&mut obj);
+        assert_eq!(obj.args, vec!["using *** and ***", "plain"]);
+        assert_eq!(obj.title.as_deref(), Some("***"));
+        assert_eq!(obj.fields[0].1, "Bearer ***");
+    }
+
+    #[test]
+    fn test_regex_redactor_masks_tag_additional_and_error_chain() {
+        let redactor = RegexRedactor::new(regex::Regex::new(r"tok_\w+").unwrap());
+        let mut obj = LogObject::new(LogType::Error);
+        obj.tag = "tok_tag".into();
+        obj.additional = Some("retry with tok_a".into());
+        obj.error = Some(ErrorInfo {
+            message: "bad tok_b".into(),
+            stack: Some("at login(tok_c)".into()),
+            backtrace: Some("0: tok_d".into()),
+            cause: Some(Box::new(ErrorInfo {
+                message: "denied tok_e".into(),
+                ..ErrorInfo::default()
+            })),
+        });
+        redactor.redact(&mut obj);
+        assert_eq!(obj.tag, "***");
+        assert_eq!(obj.additional.as_deref(), Some("retry with ***"));
+        let err = obj.error.unwrap();
+        assert_eq!(err.message, "bad ***");
+        assert_eq!(err.stack.as_deref(), Some("at login(***)"));
+        assert_eq!(err.backtrace.as_deref(), Some("0: ***"));
+        assert_eq!(err.cause.unwrap().message, "denied ***");
+    }
+
+    #[test]
+    fn test_mask_is_literal() {
+        let redactor = RegexRedactor::new(regex::Regex::new(r"\d{4}").unwrap()).mask("$0-[card]");
+        let mut obj = LogObject::new(LogType::Info);
+        obj.args = vec!["card 1234".into()];
+        redactor.redact(&mut obj);
+        assert_eq!(obj.args, vec!["card $0-[card]"]);
+    }
+}
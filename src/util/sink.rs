@@ -0,0 +1,1030 @@
+//! Output destinations for formatted log lines.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::sync::{Condvar, Mutex};
+
+/// Destination that formatted log lines are written to.
+///
+/// `Consola` holds one sink for regular output and one for errors and
+/// warnings (see [`ConsolaOptions`](crate::ConsolaOptions)). Both may point at
+/// the same sink to merge the streams.
+pub trait Sink: std::fmt::Debug + Send + Sync {
+    /// Write a single formatted entry. `line` has no trailing newline.
+    fn write_line(&self, line: &str) -> std::io::Result<()>;
+
+    /// Write one already framed binary record, e.g. from the `proto` or
+    /// `syslog` reporters, as a single unit with no newline added.
+    ///
+    /// Sinks that only hold text fail with
+    /// [`Unsupported`](std::io::ErrorKind::Unsupported), the default.
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        let _ = bytes;
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    /// Flush buffered output, if the sink buffers.
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write `line` and its newline with a single `write_all`, so that an entry
+/// is never split across writes (stderr is unbuffered, and `writeln!` may
+/// issue the line and the newline separately).
+fn write_whole_line(w: &mut dyn Write, line: &str) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(line.len() + 1);
+    buf.extend_from_slice(line.as_bytes());
+    buf.push(b'\n');
+    w.write_all(&buf)
+}
+
+/// Sink writing to the process's standard output.
+///
+/// The stdout lock is held for each entry, so lines written concurrently
+/// from several threads never interleave.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl Sink for StdoutSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        write_whole_line(&mut std::io::stdout().lock(), line)
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        std::io::stdout().lock().write_all(bytes)
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        std::io::stdout().flush()
+    }
+}
+
+/// Sink writing to the process's standard error.
+///
+/// Like [`StdoutSink`], each entry is written atomically under the stderr lock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl Sink for StderrSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        write_whole_line(&mut std::io::stderr().lock(), line)
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        std::io::stderr().lock().write_all(bytes)
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
+
+/// Sink writing to any [`Write`] implementation, such as a file.
+///
+/// The writer is locked for each entry, so lines written concurrently from
+/// several threads never interleave.
+pub struct WriterSink<W> {
+    writer: Mutex<W>,
+    auto_flush: bool,
+}
+
+impl<W: Write> WriterSink<W> {
+    /// Wrap `writer` in a sink.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            auto_flush: false,
+        }
+    }
+
+    /// Flush the writer after every line or record, returning the builder for chaining.
+    ///
+    /// For buffered writers whose output must leave the process entry by
+    /// entry, such as a socket behind a `BufWriter`.
+    pub fn auto_flush(mut self) -> Self {
+        self.auto_flush = true;
+        self
+    }
+
+    fn write_with(&self, write: impl FnOnce(&mut W) -> std::io::Result<()>) -> std::io::Result<()> {
+        let mut writer = self.writer.lock();
+        write(&mut writer)?;
+        if self.auto_flush {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Unwrap the sink, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+}
+
+impl<W> std::fmt::Debug for WriterSink<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriterSink").finish_non_exhaustive()
+    }
+}
+
+impl<W: Write + Send> Sink for WriterSink<W> {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        self.write_with(|w| write_whole_line(w, line))
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        self.write_with(|w| w.write_all(bytes))
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.writer.lock().flush()
+    }
+}
+
+/// Sink owned by a reporter that writes its own records, such as
+/// `ProtoReporter` or `SyslogReporter`, with the flushing and write-failure
+/// latching `Consola` applies to its own sinks.
+#[cfg(any(feature = "proto", feature = "syslog"))]
+#[derive(Debug, Clone)]
+pub(crate) struct RecordSink {
+    sink: Arc<dyn Sink>,
+    /// Consecutive failed writes, and whether writing has stopped.
+    health: Arc<Mutex<(u32, bool)>>,
+}
+
+#[cfg(any(feature = "proto", feature = "syslog"))]
+impl RecordSink {
+    pub(crate) fn new(sink: Arc<dyn Sink>) -> Self {
+        Self {
+            sink,
+            health: Arc::new(Mutex::new((0, false))),
+        }
+    }
+
+    /// Write `record` for an entry at `level`, flushing the sink when the
+    /// level reaches `flush_level`.
+    ///
+    /// After `write_failure_limit` consecutive failures the sink latches:
+    /// the failing write reports the last error, and later writes are
+    /// skipped with [`ConsolaError::SinkLatched`].
+    pub(crate) fn write(
+        &self,
+        record: &[u8],
+        level: crate::constants::LogLevel,
+        opts: &crate::types::ConsolaOptions,
+    ) -> Result<(), crate::error::ConsolaError> {
+        use crate::error::ConsolaError;
+
+        let mut health = self.health.lock();
+        if health.1 {
+            return Err(ConsolaError::SinkLatched);
+        }
+        let result = self.sink.write_bytes(record).and_then(|()| {
+            if crate::constants::passes_level(level, opts.flush_level) {
+                self.sink.flush()
+            } else {
+                Ok(())
+            }
+        });
+        let Err(err) = result else {
+            health.0 = 0;
+            return Ok(());
+        };
+        health.0 += 1;
+        if opts.write_failure_limit == 0 || health.0 < opts.write_failure_limit {
+            return Err(ConsolaError::Reporter(err.to_string()));
+        }
+        health.1 = true;
+        Err(ConsolaError::Reporter(format!(
+            "stopped writing after {} failed writes: {}",
+            health.0, err
+        )))
+    }
+
+    /// Flush the underlying sink, unless writing has stopped.
+    pub(crate) fn flush(&self) -> std::io::Result<()> {
+        if self.health.lock().1 {
+            return Ok(());
+        }
+        self.sink.flush()
+    }
+}
+
+/// What an [`AsyncSink`] producer does once its worker falls behind by more
+/// than the configured maximum latency.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Wait until the worker catches up before queueing the line. A worker
+    /// that has stopped (e.g. after a panic in the inner sink) is not
+    /// waited for.
+    Block,
+    /// Queue the line anyway, preceded by a one-time "worker lagging" notice
+    /// per episode of lag.
+    Notice,
+}
+
+/// What an [`AsyncSink`] with a [`capacity`](AsyncSink::capacity) does with
+/// a new line while its queue is full.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wait until the worker frees a slot, or has stopped.
+    Block,
+    /// Discard the oldest queued line to make room; see
+    /// [`dropped_count`](AsyncSink::dropped_count).
+    DropOldest,
+}
+
+/// What an [`AsyncSink`] hands to its inner sink.
+#[cfg(not(target_arch = "wasm32"))]
+enum Record {
+    Line(String),
+    Bytes(Vec<u8>),
+}
+
+/// Lines shared between an [`AsyncSink`] and its worker.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct AsyncQueue {
+    /// Queued lines and their enqueue times, oldest first.
+    lines: std::collections::VecDeque<(std::time::Instant, Record)>,
+    /// Enqueue time of the line the worker is writing.
+    in_flight: Option<std::time::Instant>,
+    /// Set once no more lines are accepted; the worker exits when drained.
+    closed: bool,
+    /// Set once the worker has stopped, including by a panic in the inner
+    /// sink, so nothing waits on it any more.
+    exited: bool,
+    dropped: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AsyncQueue {
+    /// Number of lines queued but not yet written by the worker.
+    fn pending(&self) -> usize {
+        self.lines.len() + usize::from(self.in_flight.is_some())
+    }
+
+    /// How long the oldest pending line has been waiting, if any is.
+    fn oldest_wait(&self) -> Option<std::time::Duration> {
+        self.in_flight
+            .or_else(|| self.lines.front().map(|(t, _)| *t))
+            .map(|t| t.elapsed())
+    }
+}
+
+/// State shared between an [`AsyncSink`] and its worker.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct AsyncShared {
+    queue: Mutex<AsyncQueue>,
+    /// Notified whenever the worker finishes a line or exits.
+    progress: Condvar,
+}
+
+/// Marks the worker as exited when dropped, even while unwinding.
+#[cfg(not(target_arch = "wasm32"))]
+struct WorkerExit(Arc<AsyncShared>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for WorkerExit {
+    fn drop(&mut self) {
+        self.0.queue.lock().exited = true;
+        self.0.progress.notify_all();
+    }
+}
+
+/// Sink that hands lines to a background thread writing to an inner sink,
+/// so logging never waits on slow I/O.
+///
+/// Lines are written in order. [`flush`](Sink::flush) waits until every
+/// queued line has been written, and [`shutdown`](Self::shutdown) (or
+/// dropping the sink) drains the queue and joins the worker. Use
+/// [`pending_count`](Self::pending_count) to watch backpressure,
+/// [`max_latency`](Self::max_latency) to bound how long a line may wait, and
+/// [`capacity`](Self::capacity) to bound how many lines may queue.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct AsyncSink {
+    inner: Arc<dyn Sink>,
+    shared: Arc<AsyncShared>,
+    thread: std::thread::Thread,
+    worker: Mutex<Option<std::thread::JoinHandle<()>>>,
+    max_latency: Option<(std::time::Duration, Backpressure)>,
+    capacity: Option<(usize, Overflow)>,
+    lagging: std::sync::atomic::AtomicBool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AsyncSink {
+    /// Start a worker thread writing to `inner`.
+    pub fn new(inner: Arc<dyn Sink>) -> Self {
+        let shared = Arc::new(AsyncShared::default());
+        let worker_shared = shared.clone();
+        let worker_inner = inner.clone();
+        let worker = std::thread::spawn(move || {
+            let _exit = WorkerExit(worker_shared.clone());
+            loop {
+                let line = {
+                    let mut queue = worker_shared.queue.lock();
+                    match queue.lines.pop_front() {
+                        Some((queued_at, line)) => {
+                            queue.in_flight = Some(queued_at);
+                            line
+                        }
+                        None if queue.closed => break,
+                        None => {
+                            drop(queue);
+                            std::thread::park();
+                            continue;
+                        }
+                    }
+                };
+                let _ = match line {
+                    Record::Line(line) => worker_inner.write_line(&line),
+                    Record::Bytes(bytes) => worker_inner.write_bytes(&bytes),
+                };
+                worker_shared.queue.lock().in_flight = None;
+                worker_shared.progress.notify_all();
+            }
+            let _ = worker_inner.flush();
+        });
+        Self {
+            inner,
+            shared,
+            thread: worker.thread().clone(),
+            worker: Mutex::new(Some(worker)),
+            max_latency: None,
+            capacity: None,
+            lagging: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    /// Bound how long a line may wait for the worker, returning the builder for chaining.
+    ///
+    /// Once the oldest pending line has waited longer than `latency`, new
+    /// lines are handled according to `backpressure`.
+    pub fn max_latency(mut self, latency: std::time::Duration, backpressure: Backpressure) -> Self {
+        self.max_latency = Some((latency, backpressure));
+        self
+    }
+
+    /// Bound the queue to `capacity` lines waiting behind the one being
+    /// written, returning the builder for chaining.
+    ///
+    /// A line arriving at a full queue is handled according to `overflow`.
+    pub fn capacity(mut self, capacity: usize, overflow: Overflow) -> Self {
+        self.capacity = Some((capacity.max(1), overflow));
+        self
+    }
+
+    /// Number of lines queued but not yet written by the worker.
+    pub fn pending_count(&self) -> usize {
+        self.shared.queue.lock().pending()
+    }
+
+    /// Number of lines discarded by [`Overflow::DropOldest`].
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.queue.lock().dropped
+    }
+
+    /// Stop accepting lines, write everything still queued and join the worker.
+    ///
+    /// Later writes fail with [`BrokenPipe`](std::io::ErrorKind::BrokenPipe).
+    /// Dropping the sink shuts it down as well.
+    pub fn shutdown(&self) {
+        self.shared.queue.lock().closed = true;
+        self.thread.unpark();
+        if let Some(worker) = self.worker.lock().take() {
+            let _ = worker.join();
+        }
+    }
+
+    /// Wait for the worker until `done` holds or the worker has exited.
+    fn wait_for_worker(&self, mut done: impl FnMut(&AsyncQueue) -> bool) {
+        let mut queue = self.shared.queue.lock();
+        while !done(&queue) && !queue.exited {
+            queue = self.shared.progress.wait(queue);
+        }
+    }
+
+    /// Queue `record`, applying `max_latency` backpressure first.
+    fn submit(&self, record: Record) -> std::io::Result<()> {
+        use std::sync::atomic::Ordering;
+
+        if let Some((latency, backpressure)) = self.max_latency {
+            let lagging =
+                |queue: &AsyncQueue| queue.oldest_wait().is_some_and(|wait| wait > latency);
+            match backpressure {
+                // A line only stops lagging once the worker moves on, so
+                // waiting for its progress is enough.
+                Backpressure::Block => self.wait_for_worker(|queue| !lagging(queue)),
+                Backpressure::Notice if lagging(&self.shared.queue.lock()) => {
+                    if !self.lagging.swap(true, Ordering::Relaxed) {
+                        self.enqueue(Record::Line(format!(
+                            "consola: log worker lagging ({} pending)",
+                            self.pending_count()
+                        )))?;
+                    }
+                }
+                Backpressure::Notice => self.lagging.store(false, Ordering::Relaxed),
+            }
+        }
+        self.enqueue(record)
+    }
+
+    fn enqueue(&self, line: Record) -> std::io::Result<()> {
+        let mut queue = self.shared.queue.lock();
+        if let Some((capacity, overflow)) = self.capacity {
+            match overflow {
+                Overflow::Block => {
+                    while queue.lines.len() >= capacity && !queue.closed && !queue.exited {
+                        queue = self.shared.progress.wait(queue);
+                    }
+                }
+                Overflow::DropOldest => {
+                    while queue.lines.len() >= capacity {
+                        queue.lines.pop_front();
+                        queue.dropped += 1;
+                    }
+                }
+            }
+        }
+        if queue.closed {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        }
+        queue.lines.push_back((std::time::Instant::now(), line));
+        drop(queue);
+        self.thread.unpark();
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Debug for AsyncSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncSink")
+            .field("pending", &self.pending_count())
+            .field("max_latency", &self.max_latency)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Sink for AsyncSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        self.submit(Record::Line(line.to_string()))
+    }
+
+    /// Queues the record for the inner sink, which must support
+    /// [`write_bytes`](Sink::write_bytes) itself.
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        self.submit(Record::Bytes(bytes.to_vec()))
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.wait_for_worker(|queue| queue.pending() == 0);
+        self.inner.flush()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for AsyncSink {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// In-memory sink that records everything written to it.
+///
+/// Clones share the same buffer, so a clone can be handed to `Consola` while
+/// the original is kept for assertions.
+#[derive(Debug, Clone)]
+pub struct TestSink {
+    buffer: Arc<Mutex<String>>,
+}
+
+impl Default for TestSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestSink {
+    /// Create an empty `TestSink`.
+    pub fn new() -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(String::new())),
+        }
+    }
+
+    /// Everything written so far, one entry per line.
+    pub fn contents(&self) -> String {
+        self.buffer.lock().clone()
+    }
+
+    /// Everything written so far, split into lines.
+    pub fn lines(&self) -> Vec<String> {
+        self.buffer.lock().lines().map(str::to_string).collect()
+    }
+
+    /// Everything written so far, with ANSI escape codes removed.
+    pub fn plain_contents(&self) -> String {
+        crate::util::string::strip_ansi(&self.buffer.lock())
+    }
+
+    /// Whether any line, with ANSI escape codes removed, equals `line`.
+    pub fn contains_line(&self, line: &str) -> bool {
+        self.plain_contents().lines().any(|l| l == line)
+    }
+
+    /// Discard everything written so far.
+    pub fn clear(&self) {
+        self.buffer.lock().clear();
+    }
+}
+
+/// Most bytes [`ThreadLocalSink`] keeps per thread; older lines are
+/// dropped beyond it.
+#[cfg(feature = "testing")]
+pub const CAPTURE_LIMIT: usize = 1 << 20;
+
+#[cfg(feature = "testing")]
+thread_local! {
+    static CAPTURED: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+}
+
+/// Sink recording lines in a buffer private to the writing thread.
+///
+/// Install it as `out_sink` / `err_sink`, or call
+/// [`Consola::capture_output`](crate::Consola::capture_output) (e.g. on
+/// [`CONSOLA`](crate::CONSOLA)), to keep a logger quiet under `cargo test`.
+/// Each test runs on its own thread and sees only its own output through
+/// [`captured_output`]. Each thread keeps at most [`CAPTURE_LIMIT`] bytes,
+/// dropping its oldest lines first.
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadLocalSink;
+
+#[cfg(feature = "testing")]
+impl Sink for ThreadLocalSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        CAPTURED.with_borrow_mut(|buffer| {
+            buffer.push_str(line);
+            buffer.push('\n');
+            if buffer.len() > CAPTURE_LIMIT {
+                // Cut at a line start so the buffer keeps whole lines.
+                let excess = buffer.len() - CAPTURE_LIMIT;
+                let cut = buffer[excess..]
+                    .find('\n')
+                    .map_or(buffer.len(), |i| excess + i + 1);
+                buffer.drain(..cut);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Everything [`ThreadLocalSink`] has captured on the current thread.
+#[cfg(feature = "testing")]
+pub fn captured_output() -> String {
+    CAPTURED.with_borrow(String::clone)
+}
+
+/// Everything [`ThreadLocalSink`] has captured on the current thread,
+/// clearing the buffer.
+#[cfg(feature = "testing")]
+pub fn take_captured_output() -> String {
+    CAPTURED.with_borrow_mut(std::mem::take)
+}
+
+impl Sink for TestSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        let mut buffer = self.buffer.lock();
+        buffer.push_str(line);
+        buffer.push('\n');
+        Ok(())
+    }
+
+    /// Appends the record as text, replacing invalid UTF-8.
+    fn write_bytes(&self, bytes: &[u8]) -> std::io::Result<()> {
+        self.buffer.lock().push_str(&String::from_utf8_lossy(bytes));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_sink_records_lines() {
+        let sink = TestSink::new();
+        sink.write_line("one").unwrap();
+        sink.write_line("two").unwrap();
+        assert_eq!(sink.contents(), "one\ntwo\n");
+    }
+
+    #[test]
+    fn test_test_sink_clones_share_buffer() {
+        let sink = TestSink::new();
+        let shared: Arc<dyn Sink> = Arc::new(sink.clone());
+        shared.write_line("x").unwrap();
+        assert_eq!(sink.contents(), "x\n");
+        sink.clear();
+        assert_eq!(sink.contents(), "");
+    }
+
+    #[test]
+    fn test_test_sink_line_helpers() {
+        let sink = TestSink::new();
+        sink.write_line("\x1b[31mred\x1b[39m first\nsecond")
+            .unwrap();
+        sink.write_line("third").unwrap();
+        assert_eq!(
+            sink.lines(),
+            vec!["\x1b[31mred\x1b[39m first", "second", "third"]
+        );
+        assert_eq!(sink.plain_contents(), "red first\nsecond\nthird\n");
+        assert!(sink.contains_line("red first"));
+        assert!(sink.contains_line("third"));
+        assert!(!sink.contains_line("red"));
+    }
+
+    /// Writer that accepts at most a few bytes per call, so any entry written
+    /// with more than one call could be interleaved with another thread's.
+    struct TrickleWriter(Vec<u8>);
+
+    impl Write for TrickleWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(7);
+            self.0.extend_from_slice(&buf[..n]);
+            std::thread::yield_now();
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_writer_sink_concurrent_lines_do_not_interleave() {
+        const THREADS: usize = 8;
+        const LINES: usize = 20;
+        const WIDTH: usize = 500;
+        let sink = Arc::new(WriterSink::new(TrickleWriter(Vec::new())));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let sink = sink.clone();
+                std::thread::spawn(move || {
+                    let line = char::from(b'a' + t as u8).to_string().repeat(WIDTH);
+                    for _ in 0..LINES {
+                        sink.write_line(&line).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let sink = Arc::try_unwrap(sink).unwrap();
+        let out = String::from_utf8(sink.into_inner().0).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), THREADS * LINES);
+        for line in lines {
+            assert_eq!(line.len(), WIDTH);
+            let first = line.chars().next().unwrap();
+            assert!(line.chars().all(|c| c == first), "interleaved line: {line}");
+        }
+    }
+
+    #[test]
+    fn test_write_whole_line_appends_newline() {
+        let mut out = Vec::new();
+        write_whole_line(&mut out, "entry").unwrap();
+        assert_eq!(out, b"entry\n");
+    }
+
+    #[test]
+    fn test_write_bytes_through_async_sink() {
+        let inner = Arc::new(WriterSink::new(Vec::new()));
+        let sink = AsyncSink::new(inner.clone());
+        sink.write_bytes(b"\x00\xffrecord").unwrap();
+        sink.write_line("text").unwrap();
+        sink.flush().unwrap();
+        assert_eq!(*inner.writer.lock(), b"\x00\xffrecordtext\n");
+        assert_eq!(
+            DefaultSink.write_bytes(b"x").unwrap_err().kind(),
+            std::io::ErrorKind::Unsupported
+        );
+    }
+
+    #[derive(Debug)]
+    struct DefaultSink;
+
+    impl Sink for DefaultSink {
+        fn write_line(&self, _line: &str) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_std_sinks_write() {
+        assert!(StdoutSink.write_line("").is_ok());
+        assert!(StderrSink.write_line("").is_ok());
+        assert!(StdoutSink.flush().is_ok());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_capture_output_is_per_thread() {
+        use crate::reporters::BasicReporter;
+        use crate::{Consola, ConsolaOptions, Reporter};
+
+        let make = || {
+            let c = Consola::new(ConsolaOptions {
+                reporters: vec![Box::new(BasicReporter::new()) as Box<dyn Reporter>],
+                ..ConsolaOptions::default()
+            });
+            c.capture_output();
+            c
+        };
+        take_captured_output();
+        make().info("main");
+        make().warn("careful");
+
+        let other = std::thread::spawn(move || {
+            make().info("worker");
+            take_captured_output()
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(other, "[info] worker\n");
+        assert_eq!(captured_output(), "[info] main\n[warn] careful\n");
+        assert_eq!(take_captured_output(), "[info] main\n[warn] careful\n");
+        assert_eq!(captured_output(), "");
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_thread_local_sink_keeps_newest_lines_within_limit() {
+        take_captured_output();
+        let line = "x".repeat(1023);
+        for _ in 0..(CAPTURE_LIMIT / 1024 + 8) {
+            ThreadLocalSink.write_line(&line).unwrap();
+        }
+        ThreadLocalSink.write_line("last").unwrap();
+
+        let captured = take_captured_output();
+        assert!(captured.len() <= CAPTURE_LIMIT);
+        assert!(captured.ends_with("last\n"));
+        assert!(captured.lines().all(|l| l == line || l == "last"));
+    }
+
+    /// Sink that holds each write until the test releases `gate`, reporting
+    /// each line on `entered` as the write starts.
+    #[derive(Debug, Clone)]
+    struct GatedSink {
+        gate: Arc<Mutex<()>>,
+        entered: std::sync::mpsc::Sender<String>,
+        out: TestSink,
+    }
+
+    impl Sink for GatedSink {
+        fn write_line(&self, line: &str) -> std::io::Result<()> {
+            let _ = self.entered.send(line.to_string());
+            let _open = self.gate.lock();
+            self.out.write_line(line)
+        }
+    }
+
+    struct Gated {
+        gate: Arc<Mutex<()>>,
+        entered: std::sync::mpsc::Receiver<String>,
+        out: TestSink,
+        sink: Arc<dyn Sink>,
+    }
+
+    fn gated() -> Gated {
+        let gate = Arc::new(Mutex::new(()));
+        let (entered_tx, entered) = std::sync::mpsc::channel();
+        let out = TestSink::new();
+        let sink = GatedSink {
+            gate: gate.clone(),
+            entered: entered_tx,
+            out: out.clone(),
+        };
+        Gated {
+            gate,
+            entered,
+            out,
+            sink: Arc::new(sink),
+        }
+    }
+
+    #[test]
+    fn test_async_sink_writes_in_order() {
+        let out = TestSink::new();
+        let sink = AsyncSink::new(Arc::new(out.clone()));
+        for i in 0..50 {
+            sink.write_line(&i.to_string()).unwrap();
+        }
+        sink.flush().unwrap();
+        assert_eq!(sink.pending_count(), 0);
+        let expected: Vec<String> = (0..50).map(|i| i.to_string()).collect();
+        assert_eq!(out.lines(), expected);
+    }
+
+    #[test]
+    fn test_async_sink_drains_on_drop() {
+        let Gated {
+            gate,
+            entered: _,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = AsyncSink::new(inner);
+        sink.write_line("a").unwrap();
+        sink.write_line("b").unwrap();
+        assert!(sink.pending_count() > 0);
+        drop(held);
+        drop(sink);
+        assert_eq!(out.contents(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_async_sink_lagging_notice() {
+        let Gated {
+            gate,
+            entered,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = AsyncSink::new(inner)
+            .max_latency(std::time::Duration::from_millis(5), Backpressure::Notice);
+        sink.write_line("first").unwrap();
+        assert_eq!(entered.recv().unwrap(), "first");
+        // Stay on "first" past `max_latency`.
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        sink.write_line("second").unwrap();
+        sink.write_line("third").unwrap();
+        assert_eq!(sink.pending_count(), 4);
+        drop(held);
+        sink.flush().unwrap();
+        assert_eq!(
+            out.lines(),
+            vec![
+                "first",
+                "consola: log worker lagging (1 pending)",
+                "second",
+                "third",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_async_sink_blocks_while_lagging() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let Gated {
+            gate,
+            entered,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = Arc::new(
+            AsyncSink::new(inner)
+                .max_latency(std::time::Duration::from_millis(5), Backpressure::Block),
+        );
+        sink.write_line("first").unwrap();
+        assert_eq!(entered.recv().unwrap(), "first");
+        // Stay on "first" past `max_latency`.
+        std::thread::sleep(std::time::Duration::from_millis(20));
+
+        let done = Arc::new(AtomicBool::new(false));
+        let producer = {
+            let (sink, done) = (sink.clone(), done.clone());
+            std::thread::spawn(move || {
+                sink.write_line("second").unwrap();
+                done.store(true, Ordering::SeqCst);
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!done.load(Ordering::SeqCst));
+        assert_eq!(sink.pending_count(), 1);
+
+        drop(held);
+        producer.join().unwrap();
+        sink.flush().unwrap();
+        assert_eq!(out.lines(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_async_sink_drop_oldest_when_full() {
+        let Gated {
+            gate,
+            entered,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = AsyncSink::new(inner).capacity(2, Overflow::DropOldest);
+        sink.write_line("first").unwrap();
+        // Wait for the worker to pick up "first" and block on the gate.
+        assert_eq!(entered.recv().unwrap(), "first");
+        for line in ["a", "b", "c", "d"] {
+            sink.write_line(line).unwrap();
+        }
+        assert_eq!(sink.pending_count(), 3);
+        assert_eq!(sink.dropped_count(), 2);
+        drop(held);
+        sink.flush().unwrap();
+        assert_eq!(out.lines(), vec!["first", "c", "d"]);
+    }
+
+    #[test]
+    fn test_async_sink_blocks_when_full() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let Gated {
+            gate,
+            entered,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = Arc::new(AsyncSink::new(inner).capacity(1, Overflow::Block));
+        sink.write_line("first").unwrap();
+        assert_eq!(entered.recv().unwrap(), "first");
+        sink.write_line("a").unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let producer = {
+            let (sink, done) = (sink.clone(), done.clone());
+            std::thread::spawn(move || {
+                sink.write_line("b").unwrap();
+                done.store(true, Ordering::SeqCst);
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!done.load(Ordering::SeqCst));
+
+        drop(held);
+        producer.join().unwrap();
+        sink.flush().unwrap();
+        assert_eq!(sink.dropped_count(), 0);
+        assert_eq!(out.lines(), vec!["first", "a", "b"]);
+    }
+
+    #[derive(Debug)]
+    struct PanickingSink;
+
+    impl Sink for PanickingSink {
+        fn write_line(&self, _line: &str) -> std::io::Result<()> {
+            panic!("inner sink failed");
+        }
+    }
+
+    #[test]
+    fn test_async_sink_stops_blocking_once_worker_is_gone() {
+        let sink = AsyncSink::new(Arc::new(PanickingSink))
+            .max_latency(std::time::Duration::ZERO, Backpressure::Block);
+        sink.write_line("first").unwrap();
+        // "first" never finishes, so only the worker's exit ends the wait.
+        sink.write_line("second").unwrap();
+        sink.flush().unwrap();
+    }
+
+    #[test]
+    fn test_async_sink_shutdown_drains_and_closes() {
+        let Gated {
+            gate,
+            entered: _,
+            out,
+            sink: inner,
+        } = gated();
+        let held = gate.lock();
+        let sink = AsyncSink::new(inner);
+        for i in 0..10 {
+            sink.write_line(&i.to_string()).unwrap();
+        }
+        drop(held);
+        sink.shutdown();
+        assert_eq!(sink.pending_count(), 0);
+        let expected: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        assert_eq!(out.lines(), expected);
+        assert_eq!(
+            sink.write_line("late").unwrap_err().kind(),
+            std::io::ErrorKind::BrokenPipe
+        );
+    }
+}
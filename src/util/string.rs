@@ -48,6 +48,50 @@ pub fn strip_ansi(text: &str) -> String {
     out
 }
 
+/// Length of the SGR sequence (`ESC [ params m`) at the start of `text`.
+fn sgr_len(text: &str) -> Option<usize> {
+    let params = text.strip_prefix("\x1b[")?;
+    let end = params.find(|c: char| !(c.is_ascii_digit() || c == ';'))?;
+    (params.as_bytes()[end] == b'm').then_some(end + 3)
+}
+
+/// Drop resets that are immediately followed by the style they just ended,
+/// so adjacent segments sharing a style are emitted as one styled run.
+///
+/// Only a `ESC[0m` directly followed by the exact sequences that opened the
+/// current style is removed, so the rendered output (and
+/// [`strip_ansi`] of it) is unchanged. Resets before a line break are kept.
+pub fn coalesce_styles(text: &str) -> String {
+    const RESET: &str = "\x1b[0m";
+    let mut out = String::with_capacity(text.len());
+    // SGR sequences written since the last reset, i.e. the style in force.
+    let mut open = String::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find("\x1b[") {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let Some(len) = sgr_len(rest) else {
+            out.push_str("\x1b[");
+            rest = &rest[2..];
+            continue;
+        };
+        let (seq, after) = rest.split_at(len);
+        rest = after;
+        if seq == RESET {
+            if !open.is_empty() && rest.starts_with(open.as_str()) {
+                rest = &rest[open.len()..];
+                continue;
+            }
+            open.clear();
+        } else {
+            open.push_str(seq);
+        }
+        out.push_str(seq);
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Visible width of a string, accounting for wide Unicode characters
 /// (e.g. CJK, emoji, em dashes) and zero-width characters.
 pub fn string_width(text: &str) -> usize {
@@ -119,10 +163,281 @@ pub fn align(alignment: &str, str: &str, len: usize, space: &str) -> String {
     }
 }
 
+/// Split `text` into display units: each ANSI escape sequence is one unit of
+/// width 0, and every other char is one unit of its display width. Zero-width
+/// chars (combining marks, joiners) stay attached to the preceding unit so a
+/// break is never placed inside a grapheme-like cluster.
+fn display_units(text: &str) -> Vec<(&str, usize, bool)> {
+    use unicode_width::UnicodeWidthChar;
+    let bytes = text.as_bytes();
+    // (start, end, width, is_ansi)
+    let mut spans: Vec<(usize, usize, usize, bool)> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i] == 0x1B && i + 1 < bytes.len() && bytes[i + 1] == 0x5B {
+            i += 2;
+            while i < bytes.len() && (0x20..=0x3F).contains(&bytes[i]) {
+                i += 1;
+            }
+            if i < bytes.len() && (0x40..=0x7E).contains(&bytes[i]) {
+                i += 1;
+            }
+            spans.push((start, i, 0, true));
+            continue;
+        }
+        let c = text[i..].chars().next().unwrap_or(' ');
+        i += c.len_utf8();
+        let w = c.width().unwrap_or(0);
+        match spans.last_mut() {
+            Some((_, end, _, false)) if w == 0 => *end = i,
+            _ => spans.push((start, i, w, false)),
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(start, end, w, is_ansi)| (&text[start..end], w, is_ansi))
+        .collect()
+}
+
+/// Word-wrap `text` so that no line exceeds `width` display columns.
+///
+/// Existing newlines are kept. Lines break at spaces where possible; words
+/// longer than `width` are hard-broken. ANSI escape sequences count as zero
+/// width and are never split. If a style is still active at a break, the
+/// line is closed with a reset and the style is re-opened on the next line.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut out = Vec::new();
+    for line in text.split('\n') {
+        wrap_line(line, width, &mut out);
+    }
+    out
+}
+
+fn wrap_line(line: &str, width: usize, out: &mut Vec<String>) {
+    const RESET: &str = "\x1b[0m";
+    let mut active: Vec<&str> = Vec::new();
+    let mut cur = String::new();
+    let mut cur_w = 0;
+    // Byte offset and width in `cur` just after the last space, plus the
+    // styles active at that point; used to move the trailing word down.
+    let mut last_space: Option<(usize, usize, Vec<&str>)> = None;
+
+    let flush = |cur: &mut String, styles: &[&str], out: &mut Vec<String>| {
+        let mut done = std::mem::take(cur);
+        if !styles.is_empty() {
+            done.push_str(RESET);
+            cur.push_str(&styles.concat());
+        }
+        out.push(done.trim_end_matches(' ').to_string());
+    };
+
+    for (unit, w, is_ansi) in display_units(line) {
+        if is_ansi {
+            if unit == RESET || unit == "\x1b[m" {
+                active.clear();
+            } else if unit.ends_with('m') {
+                active.push(unit);
+            }
+            cur.push_str(unit);
+            continue;
+        }
+        if unit == " " {
+            if cur_w + 1 > width {
+                flush(&mut cur, &active, out);
+                cur_w = 0;
+                last_space = None;
+                continue;
+            }
+            cur.push(' ');
+            cur_w += 1;
+            last_space = Some((cur.len(), cur_w, active.clone()));
+            continue;
+        }
+        if cur_w + w > width {
+            match last_space.take() {
+                Some((at, at_w, styles)) => {
+                    let tail = cur.split_off(at);
+                    flush(&mut cur, &styles, out);
+                    cur.push_str(&tail);
+                    cur_w -= at_w;
+                }
+                None => {
+                    flush(&mut cur, &active, out);
+                    cur_w = 0;
+                }
+            }
+            if cur_w + w > width && cur_w > 0 {
+                flush(&mut cur, &active, out);
+                cur_w = 0;
+            }
+        }
+        cur.push_str(unit);
+        cur_w += w;
+    }
+    out.push(cur);
+}
+
+/// Wrap `prefix` followed by `separator` and `message` to `width` columns.
+///
+/// With `indent`, only the message is wrapped and continuation lines are
+/// padded to start under the message; when the prefix leaves too little room
+/// this falls back to wrapping the whole line.
+pub(crate) fn wrap_prefixed(
+    prefix: &str,
+    message: &str,
+    separator: &str,
+    width: usize,
+    indent: bool,
+) -> Vec<String> {
+    let head_width = string_width(prefix) + string_width(separator);
+    let joined = || match (prefix.is_empty(), message.is_empty()) {
+        (true, _) => message.to_string(),
+        (_, true) => prefix.to_string(),
+        _ => format!("{}{}{}", prefix, separator, message),
+    };
+    if !indent || prefix.is_empty() || head_width + 8 > width {
+        return wrap_text(&joined(), width);
+    }
+    let pad = " ".repeat(head_width);
+    wrap_text(message, width - head_width)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                format!("{}{}{}", prefix, separator, line)
+            } else {
+                format!("{}{}", pad, line)
+            }
+        })
+        .collect()
+}
+
+/// Prefix every non-empty line after the first non-empty one with `marker`.
+pub(crate) fn mark_continuation_lines(text: &str, marker: &str) -> String {
+    let mut seen_first = false;
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else if !seen_first {
+                seen_first = true;
+                line.to_string()
+            } else {
+                format!("{}{}", marker, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace every run of whitespace (including newlines) in `text` with a
+/// single space and trim both ends.
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Insert `prefix` at the start of the first non-empty line of `text`.
+pub(crate) fn prefix_first_line(text: &str, prefix: &str) -> String {
+    let start = text.len() - text.trim_start_matches('\n').len();
+    format!("{}{}{}", &text[..start], prefix, &text[start..])
+}
+
+/// Prefix every non-empty line of `text` with `indent`.
+pub(crate) fn indent_lines(text: &str, indent: &str) -> String {
+    if indent.is_empty() {
+        return text.to_string();
+    }
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Truncate `text` to at most `max_width` display columns, ending with
+/// `ellipsis` when anything was cut.
+///
+/// Width is measured like [`string_width`], so wide (e.g. CJK) characters
+/// count as two columns and are never cut in half. ANSI sequences are never
+/// split and count as zero width; if a style is still open at the cut, a
+/// reset is written before the ellipsis.
+///
+/// ```
+/// use consola::util::truncate_display;
+///
+/// assert_eq!(truncate_display("hello world", 8, "…"), "hello w…");
+/// assert_eq!(truncate_display("short", 8, "…"), "short");
+/// ```
+pub fn truncate_display(text: &str, max_width: usize, ellipsis: &str) -> String {
+    if string_width(text) <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(string_width(ellipsis));
+    let mut out = String::with_capacity(text.len());
+    let mut used = 0;
+    let mut styled = false;
+    for (unit, w, is_ansi) in display_units(text) {
+        if is_ansi {
+            styled = unit != "\x1b[0m" && unit != "\x1b[m";
+            out.push_str(unit);
+            continue;
+        }
+        if used + w > budget {
+            break;
+        }
+        out.push_str(unit);
+        used += w;
+    }
+    if styled {
+        out.push_str("\x1b[0m");
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+/// Apply [`truncate_display`] with a `…` ellipsis to every line of `text`.
+pub(crate) fn truncate_lines(text: &str, max_width: usize) -> String {
+    text.split('\n')
+        .map(|line| truncate_display(line, max_width, "…"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_coalesce_styles_merges_same_style_runs() {
+        let gray = "\x1b[90m";
+        let text = format!("{gray}a\x1b[0m{gray}b\x1b[0m{gray}c\x1b[0m d");
+        let merged = coalesce_styles(&text);
+        assert_eq!(merged, format!("{gray}abc\x1b[0m d"));
+        assert_eq!(merged.matches("\x1b[0m").count(), 1);
+        assert_eq!(strip_ansi(&merged), strip_ansi(&text));
+    }
+
+    #[test]
+    fn test_coalesce_styles_keeps_style_changes_and_line_ends() {
+        let bold_red = "\x1b[1m\x1b[31m";
+        let text = format!("{bold_red}a\x1b[0m\x1b[31mb\x1b[0m{bold_red}c\x1b[0m");
+        assert_eq!(coalesce_styles(&text), text);
+
+        let text = "\x1b[90ma\x1b[0m\n\x1b[90mb\x1b[0m";
+        assert_eq!(coalesce_styles(text), text);
+
+        let text = format!("{bold_red}a\x1b[0m{bold_red}b\x1b[0m");
+        assert_eq!(coalesce_styles(&text), format!("{bold_red}ab\x1b[0m"));
+        assert_eq!(coalesce_styles("plain \x1b[2J text"), "plain \x1b[2J text");
+    }
+
     #[test]
     fn test_strip_ansi_sgr() {
         assert_eq!(strip_ansi("\x1b[31mred\x1b[0m"), "red");
@@ -239,4 +554,126 @@ mod tests {
     fn test_align_unknown() {
         assert_eq!(align("unknown", "hi", 5, " "), "hi");
     }
+
+    #[test]
+    fn test_wrap_text_fits() {
+        assert_eq!(wrap_text("hello world", 20), vec!["hello world"]);
+    }
+
+    #[test]
+    fn test_wrap_text_word_boundaries() {
+        assert_eq!(
+            wrap_text("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_text_hard_break() {
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_newlines() {
+        assert_eq!(wrap_text("a\nb", 10), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_wrap_text_cjk_width() {
+        let lines = wrap_text("你好世界", 5);
+        assert_eq!(lines, vec!["你好", "世界"]);
+        assert!(lines.iter().all(|l| string_width(l) <= 5));
+    }
+
+    #[test]
+    fn test_wrap_text_never_splits_ansi() {
+        let text = "\x1b[31mred red red\x1b[0m plain";
+        let lines = wrap_text(text, 7);
+        assert_eq!(
+            lines,
+            vec!["\x1b[31mred red\x1b[0m", "\x1b[31mred\x1b[0m", "plain"]
+        );
+        let joined = lines.iter().map(|l| strip_ansi(l)).collect::<Vec<_>>();
+        assert_eq!(joined, vec!["red red", "red", "plain"]);
+    }
+
+    #[test]
+    fn test_wrap_text_combining_chars_stay_together() {
+        // "e" + combining acute accent must not be split across lines.
+        let lines = wrap_text("abe\u{301}", 2);
+        assert_eq!(lines, vec!["ab", "e\u{301}"]);
+    }
+
+    #[test]
+    fn test_wrap_prefixed_without_indent() {
+        let lines = wrap_prefixed("[info]", "one two three four", " ", 12, false);
+        assert_eq!(lines, vec!["[info] one", "two three", "four"]);
+    }
+
+    #[test]
+    fn test_wrap_prefixed_with_indent() {
+        let lines = wrap_prefixed("[info]", "one two three four five", " ", 17, true);
+        assert_eq!(
+            lines,
+            vec!["[info] one two", "       three four", "       five"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_prefixed_indent_falls_back_when_narrow() {
+        let lines = wrap_prefixed("[info]", "one two", " ", 10, true);
+        assert_eq!(lines, vec!["[info] one", "two"]);
+    }
+
+    #[test]
+    fn test_wrap_prefixed_empty_prefix() {
+        assert_eq!(wrap_prefixed("", "a b", " ", 1, true), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_indent_lines_skips_empty_lines() {
+        assert_eq!(indent_lines("\na\n\nb\n", "  "), "\n  a\n\n  b\n");
+        assert_eq!(indent_lines("a", ""), "a");
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_eq!(collapse_whitespace("  a \n\t b  \nc "), "a b c");
+        assert_eq!(collapse_whitespace(""), "");
+    }
+
+    #[test]
+    fn test_prefix_first_line_skips_leading_newlines() {
+        assert_eq!(prefix_first_line("a\nb", "> "), "> a\nb");
+        assert_eq!(prefix_first_line("\na\n", "> "), "\n> a\n");
+    }
+
+    #[test]
+    fn test_mark_continuation_lines() {
+        assert_eq!(
+            mark_continuation_lines("\nfirst\nsecond\n\nthird\n", "> "),
+            "\nfirst\n> second\n\n> third\n"
+        );
+        assert_eq!(mark_continuation_lines("single", "> "), "single");
+    }
+
+    #[test]
+    fn test_truncate_display_fits() {
+        assert_eq!(truncate_display("hello", 5, "…"), "hello");
+        assert_eq!(truncate_display("", 0, "…"), "");
+    }
+
+    #[test]
+    fn test_truncate_display_cjk_width() {
+        // Each CJK char is two columns; the third would overflow with the ellipsis.
+        assert_eq!(truncate_display("日本語テキスト", 7, "…"), "日本語…");
+        assert_eq!(truncate_display("日本語テキスト", 6, "..."), "日...");
+    }
+
+    #[test]
+    fn test_truncate_display_keeps_ansi_intact() {
+        let out = truncate_display("\x1b[31mred text here\x1b[0m", 6, "…");
+        assert_eq!(out, "\x1b[31mred t\x1b[0m…");
+        assert_eq!(string_width(&out), 6);
+    }
 }
@@ -0,0 +1,185 @@
+//! Utility for rendering rows of text as an aligned table.
+
+use crate::util::string::{align, string_width};
+
+/// Builder for a plain-text table with aligned columns.
+///
+/// Column widths are computed from the visible width of every cell (ANSI codes
+/// are ignored), so colored content lines up with plain content.
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    alignments: Vec<String>,
+    separator: String,
+}
+
+impl Default for TableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableBuilder {
+    /// Create an empty table with two spaces between columns.
+    pub fn new() -> Self {
+        Self {
+            headers: Vec::new(),
+            rows: Vec::new(),
+            alignments: Vec::new(),
+            separator: "  ".into(),
+        }
+    }
+
+    /// Set the header row, returning the builder for chaining.
+    pub fn headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Append a data row, returning the builder for chaining.
+    pub fn row<I, S>(mut self, row: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the alignment ("left", "right", "center") of column `col`.
+    ///
+    /// Columns without an explicit alignment are left-aligned.
+    pub fn align(mut self, col: usize, alignment: &str) -> Self {
+        if self.alignments.len() <= col {
+            self.alignments.resize(col + 1, "left".into());
+        }
+        self.alignments[col] = alignment.to_string();
+        self
+    }
+
+    /// Set the string placed between columns, returning the builder for chaining.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Render the table. A rule line of `─` is drawn under the header row when
+    /// headers are present. Trailing whitespace is trimmed from every line.
+    pub fn build(&self) -> String {
+        let cols = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain(std::iter::once(self.headers.len()))
+            .max()
+            .unwrap_or(0);
+        if cols == 0 {
+            return String::new();
+        }
+
+        let mut widths = vec![0usize; cols];
+        for row in std::iter::once(&self.headers).chain(self.rows.iter()) {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(string_width(cell));
+            }
+        }
+
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+        if !self.headers.is_empty() {
+            lines.push(self.render_row(&self.headers, &widths));
+            let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+            lines.push(rule.join(&self.separator));
+        }
+        for row in &self.rows {
+            lines.push(self.render_row(row, &widths));
+        }
+        lines.join("\n")
+    }
+
+    fn render_row(&self, row: &[String], widths: &[usize]) -> String {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, &width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or("");
+                let alignment = self.alignments.get(i).map(String::as_str).unwrap_or("left");
+                let pad = width.saturating_sub(string_width(cell));
+                match alignment {
+                    "right" | "center" => align(alignment, cell, width, " "),
+                    _ => format!("{}{}", cell, " ".repeat(pad)),
+                }
+            })
+            .collect();
+        cells.join(&self.separator).trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_empty() {
+        assert_eq!(TableBuilder::new().build(), "");
+    }
+
+    #[test]
+    fn test_table_headers_and_rows() {
+        let table = TableBuilder::new()
+            .headers(["name", "count"])
+            .row(["info", "12"])
+            .row(["error", "3"])
+            .build();
+        assert_eq!(table, "name   count\n─────  ─────\ninfo   12\nerror  3");
+    }
+
+    #[test]
+    fn test_table_right_align() {
+        let table = TableBuilder::new()
+            .headers(["name", "count"])
+            .row(["info", "12"])
+            .row(["error", "3"])
+            .align(1, "right")
+            .build();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[2], "info      12");
+        assert_eq!(lines[3], "error      3");
+    }
+
+    #[test]
+    fn test_table_without_headers() {
+        let table = TableBuilder::new()
+            .row(["a", "b"])
+            .row(["ccc", "d"])
+            .build();
+        assert_eq!(table, "a    b\nccc  d");
+    }
+
+    #[test]
+    fn test_table_ragged_rows() {
+        let table = TableBuilder::new().row(["a"]).row(["b", "c"]).build();
+        assert_eq!(table, "a\nb  c");
+    }
+
+    #[test]
+    fn test_table_custom_separator() {
+        let table = TableBuilder::new().separator(" | ").row(["a", "b"]).build();
+        assert_eq!(table, "a | b");
+    }
+
+    #[test]
+    fn test_table_ignores_ansi_in_width() {
+        let table = TableBuilder::new()
+            .row(["\x1b[31mred\x1b[0m", "x"])
+            .row(["plain", "y"])
+            .build();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "\x1b[31mred\x1b[0m    x");
+        assert_eq!(lines[1], "plain  y");
+    }
+}
@@ -41,6 +41,9 @@ pub struct TreeOptions {
     pub max_depth: Option<usize>,
     /// Text shown when a subtree is truncated by `max_depth`.
     pub ellipsis: String,
+    /// Whether to draw connectors with box-drawing characters (`├─`, `└─`,
+    /// `│`) rather than their ASCII fallbacks (`|-`, `` `- ``, `|`).
+    pub unicode: bool,
 }
 
 impl Default for TreeOptions {
@@ -50,11 +53,17 @@ impl Default for TreeOptions {
             prefix: "  ".into(),
             max_depth: None,
             ellipsis: "...".into(),
+            unicode: true,
         }
     }
 }
 
 /// Format items into a tree string.
+///
+/// Each item is drawn on its own line behind a branch connector, with
+/// children nested one level deeper under a vertical bar that continues
+/// while later siblings follow. Levels beyond `max_depth` collapse into a
+/// single `ellipsis` entry.
 pub fn format_tree(items: &[TreeItem], options: &TreeOptions) -> String {
     let tree = build_tree(items, options);
     if let Some(color) = &options.color {
@@ -64,13 +73,23 @@ pub fn format_tree(items: &[TreeItem], options: &TreeOptions) -> String {
     }
 }
 
+/// Branch, last-branch and continuation-bar connectors.
+fn connectors(unicode: bool) -> (&'static str, &'static str, &'static str) {
+    if unicode {
+        ("├─", "└─", "│ ")
+    } else {
+        ("|-", "`-", "| ")
+    }
+}
+
 fn build_tree(items: &[TreeItem], options: &TreeOptions) -> String {
+    let (branch, last_branch, bar) = connectors(options.unicode);
     let mut out = String::new();
     let total = items.len().saturating_sub(1);
     for (i, item) in items.iter().enumerate() {
         let is_limit = options.max_depth == Some(0);
         if is_limit {
-            let ellipsis = format!("{}{}\n", options.prefix, options.ellipsis);
+            let ellipsis = format!("{}{}{}\n", options.prefix, last_branch, options.ellipsis);
             let line = match item {
                 TreeItem::Text(_) => ellipsis,
                 TreeItem::Node { color: Some(c), .. } => colorize(c, &ellipsis),
@@ -82,9 +101,9 @@ fn build_tree(items: &[TreeItem], options: &TreeOptions) -> String {
 
         let is_last = i == total;
         let prefix = if is_last {
-            format!("{}└─", options.prefix)
+            format!("{}{}", options.prefix, last_branch)
         } else {
-            format!("{}├─", options.prefix)
+            format!("{}{}", options.prefix, branch)
         };
 
         match item {
@@ -107,7 +126,7 @@ fn build_tree(items: &[TreeItem], options: &TreeOptions) -> String {
                     let child_prefix = if is_last {
                         format!("{}  ", options.prefix)
                     } else {
-                        format!("{}│ ", options.prefix)
+                        format!("{}{}", options.prefix, bar)
                     };
                     let child_opts = TreeOptions {
                         prefix: child_prefix,
@@ -133,6 +152,7 @@ mod tests {
         assert_eq!(opts.prefix, "  ");
         assert_eq!(opts.max_depth, None);
         assert_eq!(opts.ellipsis, "...");
+        assert!(opts.unicode);
     }
 
     #[test]
@@ -289,4 +309,57 @@ mod tests {
         let result = format_tree(&[], &TreeOptions::default());
         assert_eq!(result, "");
     }
+
+    fn two_level_tree() -> Vec<TreeItem> {
+        vec![
+            TreeItem::Node {
+                text: "src".into(),
+                children: vec![
+                    TreeItem::Node {
+                        text: "util".into(),
+                        children: vec!["tree.rs".into()],
+                        color: None,
+                    },
+                    "lib.rs".into(),
+                ],
+                color: None,
+            },
+            "README.md".into(),
+        ]
+    }
+
+    #[test]
+    fn test_format_tree_two_levels_draws_continuation_bars() {
+        let result = format_tree(&two_level_tree(), &TreeOptions::default());
+        assert_eq!(
+            result,
+            "  ├─src\n  │ ├─util\n  │ │ └─tree.rs\n  │ └─lib.rs\n  └─README.md\n"
+        );
+    }
+
+    #[test]
+    fn test_format_tree_ascii_fallback() {
+        let opts = TreeOptions {
+            unicode: false,
+            ..Default::default()
+        };
+        let result = format_tree(&two_level_tree(), &opts);
+        assert_eq!(
+            result,
+            "  |-src\n  | |-util\n  | | `-tree.rs\n  | `-lib.rs\n  `-README.md\n"
+        );
+    }
+
+    #[test]
+    fn test_format_tree_max_depth_ellipsis_node() {
+        let opts = TreeOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let result = format_tree(&two_level_tree(), &opts);
+        assert_eq!(
+            result,
+            "  ├─src\n  │ ├─util\n  │ │ └─...\n  │ └─lib.rs\n  └─README.md\n"
+        );
+    }
 }
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use consola::log_levels;
 use consola::{
-    Consola, ConsolaOptions, FormatOptions, LogContext, LogLevel, LogObject, LogObjectInput,
-    LogType, Reporter,
+    ConsolaOptions, FormatOptions, LogContext, LogLevel, LogObject, LogObjectInput, LogType,
+    Reporter, TreeItem, TreeOptions,
 };
 use parking_lot::Mutex;
 
@@ -117,6 +117,24 @@ fn test_level_get_set() {
     assert_eq!(c.level(), log_levels::DEBUG);
 }
 
+#[test]
+fn test_is_enabled_follows_set_level() {
+    let c = make_consola_level(log_levels::INFO);
+    assert!(c.is_enabled(LogType::Info));
+    assert!(!c.is_enabled(LogType::Debug));
+    c.set_level(log_levels::SILENT);
+    assert!(!c.is_enabled(LogType::Fatal));
+}
+
+#[test]
+fn test_is_enabled_false_while_silenced() {
+    let c = make_consola_level(log_levels::INFO);
+    c.silence();
+    assert!(!c.is_enabled(LogType::Error));
+    c.unsilence();
+    assert!(c.is_enabled(LogType::Error));
+}
+
 #[test]
 fn test_level_clamped() {
     let c = make_consola_level(log_levels::INFO);
@@ -329,6 +347,165 @@ fn test_pause_multiple_queue() {
     assert_eq!(all[2], "[error]: c");
 }
 
+#[test]
+fn test_discard_queued_keeps_paused() {
+    let (c, cr) = make_consola();
+    c.pause_logs();
+    c.info("a");
+    c.warn("b");
+    assert_eq!(c.queued_len(), 2);
+    assert_eq!(c.discard_queued(), 2);
+    assert_eq!(c.queued_len(), 0);
+
+    c.info("after discard");
+    assert_eq!(c.queued_len(), 1);
+    c.discard_queued();
+    c.resume_logs();
+    assert_eq!(cr.count(), 0);
+    let warn = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Warn);
+    assert_eq!(warn.unwrap().1.dropped, 1);
+}
+
+#[test]
+fn test_discard_queued_keeps_group_nesting() {
+    let (c, cr) = make_consola();
+    c.group("outer");
+    c.group("inner");
+    c.pause_logs();
+    c.info("dropped");
+    c.group_end();
+    c.group("sibling");
+    c.group_end();
+    c.group_end();
+    assert_eq!(c.queued_len(), 5);
+    assert_eq!(c.discard_queued(), 1);
+    assert_eq!(c.queued_len(), 0);
+    assert_eq!(c.group_depth(), 0);
+
+    c.resume_logs();
+    c.info("top level");
+    assert_eq!(
+        cr.all(),
+        vec!["[log]: outer", "[log]: inner", "[info]: top level"]
+    );
+}
+
+fn make_bounded_pause_consola(
+    overflow: consola::types::QueueOverflow,
+) -> (Arc<consola::Consola>, CaptureReporter) {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        pause_queue_capacity: 2,
+        pause_queue_overflow: overflow,
+        ..ConsolaOptions::default()
+    });
+    (Arc::new(c), cr)
+}
+
+#[test]
+fn test_pause_queue_drop_oldest() {
+    let (c, cr) = make_bounded_pause_consola(consola::types::QueueOverflow::DropOldest);
+    c.pause_logs();
+    c.info("a");
+    c.warn("b");
+    assert!(c.error("c"));
+    assert_eq!(c.queue_dropped_count(), 1);
+    c.resume_logs();
+    assert_eq!(cr.all(), vec!["[warn]: b", "[error]: c"]);
+    let info = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Info);
+    assert_eq!(info.unwrap().1.dropped, 1);
+}
+
+#[test]
+fn test_pause_queue_drop_newest() {
+    let (c, cr) = make_bounded_pause_consola(consola::types::QueueOverflow::DropNewest);
+    c.pause_logs();
+    assert!(c.info("a"));
+    assert!(c.warn("b"));
+    assert!(!c.error("c"));
+    assert!(!c.error("d"));
+    assert_eq!(c.queue_dropped_count(), 2);
+    c.resume_logs();
+    assert_eq!(cr.all(), vec!["[info]: a", "[warn]: b"]);
+}
+
+#[test]
+fn test_pause_queue_block_waits_for_resume() {
+    let (c, cr) = make_bounded_pause_consola(consola::types::QueueOverflow::Block);
+    c.pause_logs();
+    c.info("a");
+    c.info("b");
+    let logger = c.clone();
+    let blocked = std::thread::spawn(move || logger.info("c"));
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(!blocked.is_finished());
+    assert_eq!(cr.count(), 0);
+
+    c.resume_logs();
+    assert!(blocked.join().unwrap());
+    // "c" is logged by its own thread, possibly while the queue replays.
+    let mut all = cr.all();
+    all.sort();
+    assert_eq!(all, vec!["[info]: a", "[info]: b", "[info]: c"]);
+    assert_eq!(c.queue_dropped_count(), 0);
+}
+
+#[test]
+fn test_pause_queue_never_drops_group_events() {
+    for overflow in [
+        consola::types::QueueOverflow::DropOldest,
+        consola::types::QueueOverflow::DropNewest,
+    ] {
+        let (c, cr) = make_bounded_pause_consola(overflow);
+        c.pause_logs();
+        c.group("g");
+        c.info("a");
+        c.info("b");
+        c.info("c");
+        c.group_end();
+        assert_eq!(c.queue_dropped_count(), 1);
+        assert_eq!(c.queued_len(), 4);
+        c.resume_logs();
+        assert_eq!(c.group_depth(), 0);
+        let all = cr.all();
+        assert_eq!(all.first().unwrap(), "[log]: g");
+        assert_eq!(all.last().unwrap(), "[log]: g");
+    }
+}
+
+#[test]
+fn test_pause_queue_block_drops_calls_from_pausing_thread() {
+    let (c, cr) = make_bounded_pause_consola(consola::types::QueueOverflow::Block);
+    c.pause_logs();
+    c.info("a");
+    c.info("b");
+    c.group("g");
+    // Waiting here would wait for this very thread to resume.
+    c.info("c");
+    assert_eq!(c.queue_dropped_count(), 1);
+    c.resume_logs();
+    assert_eq!(cr.all(), vec!["[info]: a", "[info]: b", "[log]: g"]);
+}
+
+#[test]
+fn test_pause_queue_block_waits_for_discard() {
+    let (c, cr) = make_bounded_pause_consola(consola::types::QueueOverflow::Block);
+    c.pause_logs();
+    c.info("a");
+    c.info("b");
+    let logger = c.clone();
+    let blocked = std::thread::spawn(move || logger.info("c"));
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(!blocked.is_finished());
+
+    assert_eq!(c.discard_queued(), 2);
+    assert!(blocked.join().unwrap());
+    assert_eq!(c.queued_len(), 1);
+    c.resume_logs();
+    assert_eq!(cr.all(), vec!["[info]: c"]);
+}
+
 #[test]
 fn test_pause_resume_empty() {
     let (c, cr) = make_consola();
@@ -641,6 +818,57 @@ fn test_throttle_repeats() {
     );
 }
 
+#[test]
+fn test_throttle_keeps_raw_and_formatted_apart() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    c.info("dup");
+    c.info_raw("dup");
+    assert_eq!(cr.count(), 2, "got: {:?}", cr.all());
+}
+
+#[test]
+fn test_log_once_emits_a_single_time() {
+    let (c, cr) = make_consola();
+    assert!(c.log_once(LogType::Warn, "x"));
+    assert!(!c.log_once(LogType::Warn, "x"));
+    assert!(!c.log_once(LogType::Warn, "x"));
+    assert_eq!(cr.all(), vec!["[warn]: x"]);
+    let warn = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Warn);
+    assert_eq!(warn.unwrap().1.suppressed, 2);
+}
+
+#[test]
+fn test_log_once_ignores_time_window_and_keys_on_entry() {
+    let clock = consola::MockClock::new(0);
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    assert!(c.info_once("deprecated"));
+    clock.advance(std::time::Duration::from_secs(3600));
+    assert!(!c.info_once("deprecated"));
+    assert!(c.warn_once("deprecated"));
+    assert!(c.info("deprecated"));
+    assert_eq!(cr.count(), 3);
+}
+
+#[test]
+fn test_clear_once_cache() {
+    let (c, cr) = make_consola();
+    c.info_once("x");
+    c.clear_once_cache();
+    c.info_once("x");
+    assert_eq!(cr.count(), 2);
+}
+
 #[test]
 fn test_throttle_min_threshold() {
     let cr = CaptureReporter::new();
@@ -668,177 +896,1386 @@ fn test_throttle_min_threshold() {
 }
 
 #[test]
-fn test_format_options_default() {
-    let opts = FormatOptions::default();
-    let _c = consola::Consola::new(ConsolaOptions {
-        format_options: opts,
+fn test_suppressed_count() {
+    let cr = CaptureReporter::new();
+    let opts = ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        throttle: 60_000,
+        throttle_min: 1,
         ..ConsolaOptions::default()
-    });
-    // smoketest: no crash
-}
-
-// ===================================================================
-// Reporter trait smoke — clone_box
-// ===================================================================
+    };
+    let c = consola::Consola::new(opts);
+    for _ in 0..10 {
+        c.info("dup");
+    }
+    c.info_once("once");
+    c.info_once("once");
+    assert_eq!(c.suppressed_count(), 9);
 
-#[test]
-fn test_reporter_clone_box() {
-    let cr = CaptureReporter::new();
-    let cloned: Box<dyn Reporter> = cr.clone_box();
-    let _ = cloned.format(
-        &LogObject::new(LogType::Info),
-        &LogContext {
-            options: Arc::new(ConsolaOptions::default()),
-        },
+    c.reset_session(true);
+    assert_eq!(c.suppressed_count(), 9);
+    assert!(
+        cr.all()
+            .contains(&"[info]: dup (repeated 9 times)".to_string())
     );
 }
 
 #[test]
-fn test_info_empty_string() {
-    let (c, _cr) = make_consola();
-    assert!(c.info(""));
-}
-
-#[test]
-fn test_info_long_message() {
-    let (c, _cr) = make_consola();
-    let long = "a".repeat(10_000);
-    assert!(c.info(&long));
+fn test_disabled_throttle_emits_every_entry() {
+    let (c, cr) = make_consola();
+    c.set_throttle(
+        consola::types::ThrottleConfig::new()
+            .window(60_000)
+            .min_count(1),
+    );
+    c.info("dup");
+    c.info("dup");
+    c.set_throttle(consola::types::ThrottleConfig::disabled());
+    assert!(c.throttle().is_disabled());
+
+    let repeated = Arc::new(Mutex::new(Vec::new()));
+    let seen = repeated.clone();
+    c.on_record(move |obj| seen.lock().push(obj.repeated));
+    for _ in 0..5 {
+        c.info("dup");
+    }
+    c.flush();
+    assert_eq!(*repeated.lock(), vec![0; 5]);
+    assert_eq!(c.suppressed_count(), 1);
+    assert_eq!(
+        cr.all(),
+        vec!["[info]: dup".to_string(), "[info]: dup".to_string()]
+            .into_iter()
+            .chain(std::iter::repeat_n("[info]: dup".to_string(), 5))
+            .collect::<Vec<_>>()
+    );
 }
 
-// ===================================================================
-// remove_reporter edge cases
-// ===================================================================
-
 #[test]
-fn test_remove_reporter_out_of_bounds() {
+fn test_throttle_by_type_overrides_global_throttle() {
     let cr = CaptureReporter::new();
     let opts = ConsolaOptions {
         reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
         level: log_levels::VERBOSE,
+        throttle: 60_000,
+        throttle_min: 1,
+        throttle_by_type: [(
+            "error".to_string(),
+            consola::types::ThrottleConfig::disabled(),
+        )]
+        .into_iter()
+        .collect(),
         ..ConsolaOptions::default()
     };
     let c = consola::Consola::new(opts);
-    // removing index 5 from a list with 1 element should error
-    assert!(
-        c.remove_reporter(5).is_err(),
-        "expected error on OOB remove"
+    for _ in 0..3 {
+        c.error("boom");
+    }
+    for _ in 0..3 {
+        c.debug("tick");
+    }
+    c.flush();
+    assert_eq!(
+        cr.all(),
+        vec![
+            "[error]: boom",
+            "[error]: boom",
+            "[error]: boom",
+            "[debug]: tick",
+            "[debug]: tick (repeated 2 times)",
+        ]
     );
+    assert_eq!(c.suppressed_count(), 2);
 }
 
 #[test]
-fn test_remove_reporter_empty() {
-    let c = consola::Consola::new(ConsolaOptions::default());
-    // removing index 0 from an empty list should error
-    assert!(
-        c.remove_reporter(0).is_err(),
-        "expected error on remove from empty"
-    );
-}
+fn test_stats_table() {
+    let cr = CaptureReporter::new();
+    let opts = ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::INFO,
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    };
+    let c = consola::Consola::new(opts);
+    c.info("a");
+    c.info("b");
+    c.warn("dup");
+    c.warn("dup");
+    c.warn("dup");
+    c.debug("hidden");
 
-#[test]
-fn test_resume_without_pause() {
-    let (c, cr) = make_consola();
-    c.info("before");
-    let count_before = cr.count();
-    // resume when not paused should be a no-op
-    c.resume_logs();
-    // no crash and no extra emission
-    assert_eq!(cr.count(), count_before);
-}
+    let stats = c.stats();
+    let warn = stats.iter().find(|(t, _)| *t == LogType::Warn).unwrap().1;
+    assert_eq!(warn.emitted, 1);
+    assert_eq!(warn.suppressed, 2);
 
-#[test]
-fn test_level_clamping_negative() {
-    let c = make_consola_level(log_levels::INFO);
-    c.set_level(-5);
-    assert_eq!(c.level(), 0, "-5 should clamp to 0");
+    let table = c.stats_table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "type   emitted  suppressed  filtered  dropped");
+    assert_eq!(lines[2], "warn         1           2         0        0");
+    assert_eq!(lines[3], "info         2           0         0        0");
+    assert_eq!(lines[4], "debug        0           0         1        0");
 }
 
 #[test]
-fn test_level_clamping_max() {
-    let c = make_consola_level(log_levels::INFO);
-    c.set_level(255);
-    let clamped = c.level();
-    assert!(clamped <= 6, "255 should clamp to <=6, got {}", clamped);
-    assert_eq!(clamped, log_levels::TRACE, "255 should clamp to TRACE (5)");
-}
-
-// ===================================================================
-// log_obj_raw
-// ===================================================================
+fn test_reset_session() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    c.info("a");
+    c.warn("dup");
+    c.warn("dup");
+    c.debug("hidden");
+    assert_eq!(c.stats().len(), 3);
 
-#[test]
-fn test_log_obj_raw() {
-    let (c, cr) = make_consola();
-    assert!(c.log_obj_raw(&LogObjectInput {
-        r#type: Some(LogType::Info),
-        message: Some("raw log_obj".into()),
-        ..LogObjectInput::default()
-    }));
-    let last = cr.last().unwrap();
-    assert!(last.contains("raw log_obj"), "got: {last}");
-}
+    c.reset_session(false);
+    assert!(c.stats().is_empty());
+    assert_eq!(c.stats_table().lines().count(), 2);
+    c.warn("dup");
+    let warn = c.stats()[0].1;
+    assert_eq!((warn.emitted, warn.suppressed), (0, 1));
 
-#[test]
-fn test_log_all_types_at_verbose_raw() {
-    let (c, _cr) = make_consola();
-    assert!(c.fatal_raw("x"));
-    assert!(c.error_raw("x"));
-    assert!(c.warn_raw("x"));
-    assert!(c.info_raw("x"));
-    assert!(c.success_raw("x"));
-    assert!(c.fail_raw("x"));
-    assert!(c.ready_raw("x"));
-    assert!(c.start_raw("x"));
-    assert!(c.box_raw("x"));
-    assert!(c.debug_raw("x"));
-    assert!(c.trace_raw("x"));
-    assert!(c.verbose_raw("x"));
-    assert!(c.log_raw("x"));
+    c.reset_session(true);
+    // The pending summary is flushed before the throttle state is dropped.
+    assert_eq!(cr.all().last().unwrap(), "[warn]: dup (repeated 2 times)");
+    assert!(c.stats().is_empty());
+    c.warn("dup");
+    assert_eq!(c.stats()[0].1.emitted, 1);
+    assert_eq!(cr.all().last().unwrap(), "[warn]: dup");
 }
 
-// ===================================================================
-// create with reporters
-// ===================================================================
-
 #[test]
-fn test_create_without_reporters() {
-    let cr = CaptureReporter::new();
+fn test_stats_counts_reporter_errors_as_dropped() {
     let opts = ConsolaOptions {
-        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        reporters: vec![Box::new(ErrReporter) as Box<dyn Reporter>],
         level: log_levels::VERBOSE,
         ..ConsolaOptions::default()
     };
     let c = consola::Consola::new(opts);
-    // child with empty reporters should inherit parent's reporters
-    let child = c.create(ConsolaOptions {
-        reporters: vec![],
-        level: log_levels::VERBOSE,
-        ..ConsolaOptions::default()
-    });
-    child.info("from child");
-    assert_eq!(cr.count(), 1, "child should inherit parent reporters");
+    c.error("nope");
+    assert_eq!(c.stats()[0].1.dropped, 1);
+    assert_eq!(c.stats()[0].1.emitted, 0);
 }
 
 #[test]
-fn test_create_with_new_reporters() {
-    let cr_parent = CaptureReporter::new();
-    let opts = ConsolaOptions {
-        reporters: vec![Box::new(cr_parent.clone()) as Box<dyn Reporter>],
+fn test_multi_reporter_fans_out_to_every_child() {
+    let first = CaptureReporter::new();
+    let second = CaptureReporter::new();
+    let multi = consola::reporters::MultiReporter::new()
+        .add(first.clone())
+        .add(second.clone());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(multi) as Box<dyn Reporter>],
         level: log_levels::VERBOSE,
         ..ConsolaOptions::default()
-    };
-    let c = consola::Consola::new(opts);
+    });
+    c.info("one");
+    c.warn("two");
+    assert_eq!(first.all(), vec!["[info]: one", "[warn]: two"]);
+    assert_eq!(first.all(), second.all());
+}
 
-    let cr_child = CaptureReporter::new();
-    let child = c.create(ConsolaOptions {
-        reporters: vec![Box::new(cr_child.clone()) as Box<dyn Reporter>],
+#[test]
+fn test_multi_reporter_error_does_not_skip_other_children() {
+    let cr = CaptureReporter::new();
+    let multi = consola::reporters::MultiReporter::new()
+        .add(ErrReporter)
+        .add(cr.clone());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(multi) as Box<dyn Reporter>],
         level: log_levels::VERBOSE,
         ..ConsolaOptions::default()
     });
-    child.info("from child");
-    assert_eq!(cr_parent.count(), 0, "parent reporter should not fire");
-    assert_eq!(cr_child.count(), 1, "child reporter should fire");
+    c.info("still captured");
+    assert_eq!(cr.count(), 1);
+    assert_eq!(c.stats()[0].1.emitted, 1);
+    assert_eq!(c.stats()[0].1.dropped, 0);
+}
+
+fn make_throttled_consola(cr: &CaptureReporter) -> consola::Consola {
+    consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    })
+}
+
+#[test]
+fn test_flush_emits_pending_repeat_summary() {
+    let cr = CaptureReporter::new();
+    let c = make_throttled_consola(&cr);
+    c.info("same");
+    c.info("same");
+    c.info("same");
+    assert_eq!(cr.count(), 1);
+    c.flush();
+    assert_eq!(cr.last().unwrap(), "[info]: same (repeated 2 times)");
+    c.flush();
+    assert_eq!(cr.count(), 2);
+}
+
+#[test]
+fn test_flush_shutdown_then_drop_emits_once() {
+    let cr = CaptureReporter::new();
+    let c = make_throttled_consola(&cr);
+    c.info("same");
+    c.info("same");
+    c.info("same");
+    c.flush();
+    c.shutdown();
+    assert!(c.is_shut_down());
+    c.shutdown();
+    drop(c);
+    assert_eq!(
+        cr.all(),
+        vec!["[info]: same", "[info]: same (repeated 2 times)"]
+    );
+}
+
+#[test]
+fn test_resume_throttles_by_original_time() {
+    let cr = CaptureReporter::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        throttle: 50,
+        throttle_min: 1,
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.pause_logs();
+    for _ in 0..3 {
+        c.info("same");
+    }
+    clock.advance(std::time::Duration::from_millis(120));
+    for _ in 0..3 {
+        c.info("same");
+    }
+    c.resume_logs();
+    c.flush();
+    assert_eq!(
+        cr.all(),
+        vec![
+            "[info]: same",
+            "[info]: same (repeated 2 times)",
+            "[info]: same",
+            "[info]: same (repeated 2 times)",
+        ]
+    );
+}
+
+#[test]
+fn test_poll_flushes_expired_throttle_window() {
+    let cr = CaptureReporter::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        throttle: 1000,
+        throttle_min: 1,
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    for _ in 0..100 {
+        c.info("burst");
+    }
+    assert!(!c.poll());
+    clock.advance(std::time::Duration::from_millis(999));
+    assert!(!c.poll());
+    assert_eq!(cr.count(), 1);
+
+    clock.advance(std::time::Duration::from_millis(1));
+    assert!(c.poll());
+    assert!(!c.poll());
+    assert_eq!(
+        cr.all(),
+        vec!["[info]: burst", "[info]: burst (repeated 99 times)"]
+    );
+}
+
+#[test]
+fn test_poll_ignores_lone_entries_with_zero_throttle_min() {
+    for throttle in [
+        consola::types::ThrottleConfig::new()
+            .window(1000)
+            .min_count(0),
+        consola::types::ThrottleConfig::disabled(),
+    ] {
+        let cr = CaptureReporter::new();
+        let clock = consola::MockClock::new(0);
+        let c = consola::Consola::new(ConsolaOptions {
+            reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+            clock: Arc::new(clock.clone()),
+            ..ConsolaOptions::default()
+        });
+        c.set_throttle(throttle);
+        c.info("once");
+        clock.advance(std::time::Duration::from_millis(1000));
+        assert!(!c.poll());
+        c.info("twice");
+        clock.advance(std::time::Duration::from_millis(1000));
+        assert!(!c.poll());
+        assert_eq!(cr.all(), vec!["[info]: once", "[info]: twice"]);
+    }
+}
+
+#[test]
+fn test_rate_limit_drops_excess_and_refills() {
+    let cr = CaptureReporter::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        rate_limit: Some(consola::types::RateLimit::new(2)),
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    let burst = |n: usize| (0..n).filter(|i| c.info(&format!("msg {}", i))).count();
+    assert_eq!(burst(5), 2);
+
+    clock.advance(std::time::Duration::from_millis(499));
+    assert_eq!(burst(1), 0);
+    clock.advance(std::time::Duration::from_millis(1));
+    assert_eq!(burst(2), 1);
+
+    // The bucket holds no more than `burst` entries however long it idles.
+    clock.advance(std::time::Duration::from_secs(10));
+    assert_eq!(burst(3), 2);
+
+    assert_eq!(cr.count(), 5);
+    assert_eq!(c.rate_limited_count(), 6);
+    let info = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Info);
+    assert_eq!(info.unwrap().1.dropped, 6);
+}
+
+#[test]
+fn test_mock_clock_advances_past_throttle_window() {
+    let cr = CaptureReporter::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        throttle: 1000,
+        throttle_min: 1,
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("same");
+    clock.advance(std::time::Duration::from_millis(500));
+    c.info("same");
+    c.info("same");
+    assert_eq!(cr.count(), 1);
+
+    clock.advance(std::time::Duration::from_millis(600));
+    c.info("same");
+    assert_eq!(
+        cr.all(),
+        vec![
+            "[info]: same",
+            "[info]: same (repeated 2 times)",
+            "[info]: same",
+        ]
+    );
+}
+
+#[test]
+fn test_mock_clock_sets_timestamps() {
+    let clock = consola::MockClock::new(1_700_000_000_000);
+    let rec = TimestampReporter::default();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(rec.clone()) as Box<dyn Reporter>],
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("a");
+    clock.advance(std::time::Duration::from_secs(1));
+    c.info("b");
+    assert_eq!(
+        *rec.timestamps.lock(),
+        vec![1_700_000_000_000, 1_700_000_001_000]
+    );
+}
+
+/// Records the timestamp of every entry it formats.
+#[derive(Debug, Clone, Default)]
+struct TimestampReporter {
+    timestamps: Arc<Mutex<Vec<i64>>>,
+}
+
+impl Reporter for TimestampReporter {
+    fn format(
+        &self,
+        log_obj: &LogObject,
+        _ctx: &LogContext,
+    ) -> Result<String, consola::error::ConsolaError> {
+        self.timestamps.lock().push(log_obj.timestamp_ms);
+        Ok(String::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_define_type_logs_with_level_and_icon() {
+    let ty = consola::define_type("release", log_levels::INFO, Some("🚀"), Some("blue")).unwrap();
+    let sink = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::FancyReporter::new()) as Box<dyn Reporter>],
+        format_options: FormatOptions {
+            date: false,
+            colors: false,
+            ..FormatOptions::default()
+        },
+        out_sink: Arc::new(sink.clone()),
+        ..ConsolaOptions::default()
+    });
+    assert!(c.log_obj(&LogObjectInput::new().type_(ty).message("v1.2.0 shipped")));
+    assert!(
+        sink.plain_contents().starts_with("🚀 v1.2.0 shipped"),
+        "{:?}",
+        sink.contents()
+    );
+    assert_eq!(c.stats()[0].0, ty);
+    assert_eq!(c.stats()[0].1.emitted, 1);
+
+    c.set_level(log_levels::LOG);
+    assert!(!c.log_obj(&LogObjectInput::new().type_(ty).message("hidden")));
+}
+
+#[test]
+fn test_drop_flushes_pending_repeats() {
+    let cr = CaptureReporter::new();
+    let c = make_throttled_consola(&cr);
+    c.warn("again");
+    c.warn("again");
+    drop(c);
+    assert_eq!(cr.all(), vec!["[warn]: again", "[warn]: again"]);
+}
+
+/// Sink that takes a while per line, so an `AsyncSink` in front of it
+/// falls behind.
+#[derive(Debug)]
+struct SlowSink(consola::TestSink);
+
+impl consola::Sink for SlowSink {
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        self.0.write_line(line)
+    }
+}
+
+#[test]
+fn test_shutdown_writes_everything_before_returning() {
+    let out = consola::TestSink::new();
+    let sink: Arc<dyn consola::Sink> =
+        Arc::new(consola::AsyncSink::new(Arc::new(SlowSink(out.clone()))));
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: sink.clone(),
+        err_sink: sink,
+        format_options: FormatOptions {
+            date: false,
+            ..FormatOptions::default()
+        },
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    for i in 0..5 {
+        c.info(&format!("line {}", i));
+    }
+    c.warn("dup");
+    c.warn("dup");
+    c.warn("dup");
+    c.pause_logs();
+    c.info("queued");
+
+    c.shutdown();
+    assert_eq!(
+        out.lines(),
+        vec![
+            "[info] line 0",
+            "[info] line 1",
+            "[info] line 2",
+            "[info] line 3",
+            "[info] line 4",
+            "[warn] dup",
+            "[warn] dup (repeated 2 times)",
+            "[info] queued",
+        ]
+    );
+    assert!(!c.info("late"));
+}
+
+#[test]
+fn test_logs_after_shutdown_are_dropped() {
+    let (c, cr) = make_consola();
+    c.shutdown();
+    c.info("late");
+    assert_eq!(cr.count(), 0);
+    assert_eq!(c.stats()[0].1.dropped, 1);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_bridged_records_after_shutdown_are_dropped() {
+    let (c, cr) = make_consola();
+    c.shutdown();
+    log::Log::log(
+        &c,
+        &log::Record::builder()
+            .args(format_args!("bridged after shutdown"))
+            .level(log::Level::Warn)
+            .target("app")
+            .build(),
+    );
+    assert_eq!(cr.count(), 0);
+    assert_eq!(c.stats()[0].1.dropped, 1);
+}
+
+/// Reporter that buffers its records and only hands them over on `flush`.
+#[derive(Debug, Clone, Default)]
+struct BufferingReporter {
+    pending: Arc<Mutex<Vec<String>>>,
+    flushed: Arc<Mutex<Vec<String>>>,
+}
+
+impl Reporter for BufferingReporter {
+    fn format(
+        &self,
+        log_obj: &LogObject,
+        _ctx: &LogContext,
+    ) -> Result<String, consola::error::ConsolaError> {
+        self.pending.lock().push(log_obj.args.join(" "));
+        Ok(String::new())
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(self.clone())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        self.flushed.lock().extend(pending);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_shutdown_flushes_reporters() {
+    let reporter = BufferingReporter::default();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![
+            Box::new(consola::reporters::MultiReporter::new().add(reporter.clone()))
+                as Box<dyn Reporter>,
+        ],
+        ..ConsolaOptions::default()
+    });
+    c.info("one");
+    c.info("two");
+    assert!(reporter.flushed.lock().is_empty());
+    c.shutdown();
+    assert_eq!(*reporter.flushed.lock(), vec!["one", "two"]);
+}
+
+/// Sink whose `flush` blocks until the test releases it.
+#[derive(Debug)]
+struct GatedFlushSink {
+    entered: std::sync::mpsc::SyncSender<()>,
+    release: std::sync::Mutex<std::sync::mpsc::Receiver<()>>,
+    flushed: Arc<Mutex<bool>>,
+}
+
+impl consola::Sink for GatedFlushSink {
+    fn write_line(&self, _line: &str) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        let _ = self.entered.send(());
+        let _ = self.release.lock().unwrap().recv();
+        *self.flushed.lock() = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_concurrent_shutdown_waits_for_first_call() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let (entered_tx, entered_rx) = mpsc::sync_channel(2);
+    let (release_tx, release_rx) = mpsc::channel();
+    let flushed = Arc::new(Mutex::new(false));
+    let c = Arc::new(consola::Consola::new(ConsolaOptions {
+        out_sink: Arc::new(GatedFlushSink {
+            entered: entered_tx,
+            release: std::sync::Mutex::new(release_rx),
+            flushed: flushed.clone(),
+        }),
+        err_sink: Arc::new(consola::TestSink::new()),
+        ..ConsolaOptions::default()
+    }));
+
+    let first = std::thread::spawn({
+        let c = c.clone();
+        move || c.shutdown()
+    });
+    entered_rx.recv().unwrap();
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let second = std::thread::spawn({
+        let c = c.clone();
+        let flushed = flushed.clone();
+        move || {
+            c.shutdown();
+            done_tx.send(*flushed.lock()).unwrap();
+        }
+    });
+    assert_eq!(
+        done_rx.recv_timeout(Duration::from_millis(50)),
+        Err(mpsc::RecvTimeoutError::Timeout)
+    );
+
+    release_tx.send(()).unwrap();
+    assert!(done_rx.recv().unwrap());
+    first.join().unwrap();
+    second.join().unwrap();
+}
+
+/// Wraps a real reporter and records every non-empty string it produces.
+#[derive(Debug)]
+struct RecordingReporter {
+    inner: Box<dyn Reporter>,
+    captured: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingReporter {
+    fn new(inner: impl Reporter + 'static) -> (Self, Arc<Mutex<Vec<String>>>) {
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let r = Self {
+            inner: Box::new(inner),
+            captured: captured.clone(),
+        };
+        (r, captured)
+    }
+}
+
+impl Reporter for RecordingReporter {
+    fn format(
+        &self,
+        log_obj: &LogObject,
+        ctx: &LogContext,
+    ) -> Result<String, consola::error::ConsolaError> {
+        let out = self.inner.format(log_obj, ctx)?;
+        if !out.is_empty() {
+            self.captured.lock().push(out.clone());
+        }
+        Ok(out)
+    }
+
+    fn clone_box(&self) -> Box<dyn Reporter> {
+        Box::new(Self {
+            inner: self.inner.clone_box(),
+            captured: self.captured.clone(),
+        })
+    }
+}
+
+fn log_nested_groups(c: &consola::Consola) {
+    c.info("top");
+    c.group("outer");
+    c.info("in outer");
+    c.group("inner");
+    c.info("in inner");
+    c.group_end();
+    c.info("back in outer");
+    c.group_end();
+    c.group_end(); // unbalanced: no-op
+    c.info("top again");
+}
+
+#[test]
+fn test_group_json_emits_boundary_events() {
+    let (rec, captured) = RecordingReporter::new(consola::reporters::JsonReporter::new());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(rec) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    });
+    log_nested_groups(&c);
+    let values: Vec<serde_json::Value> = captured
+        .lock()
+        .iter()
+        .map(|s| serde_json::from_str(s).unwrap())
+        .collect();
+    let summary: Vec<String> = values
+        .iter()
+        .map(|v| match v["event"].as_str() {
+            Some(ev) => format!("{}:{}:{}", ev, v["label"].as_str().unwrap(), v["depth"]),
+            None => v["args"][0].as_str().unwrap().to_string(),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "top",
+            "group_start:outer:0",
+            "in outer",
+            "group_start:inner:1",
+            "in inner",
+            "group_end:inner:1",
+            "back in outer",
+            "group_end:outer:0",
+            "top again",
+        ]
+    );
+    assert_eq!(c.group_depth(), 0);
+}
+
+#[test]
+fn test_group_fancy_indents_nested_output() {
+    let (rec, captured) = RecordingReporter::new(consola::reporters::FancyReporter::new());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(rec) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        format_options: FormatOptions {
+            date: false,
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    log_nested_groups(&c);
+    let lines: Vec<String> = captured
+        .lock()
+        .iter()
+        .map(|s| consola::strip_ansi(s))
+        .collect();
+    let indents: Vec<usize> = lines
+        .iter()
+        .map(|l| l.len() - l.trim_start().len())
+        .collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(indents, vec![0, 0, 2, 2, 4, 2, 0]);
+    assert!(lines[1].ends_with("outer"));
+    assert!(lines[3].ends_with("inner"));
+}
+
+#[test]
+fn test_group_indent_is_configurable() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        format_options: FormatOptions {
+            group_indent: 4,
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    log_nested_groups(&c);
+    assert_eq!(
+        out.lines(),
+        vec![
+            "[info] top",
+            "[log] outer",
+            "    [info] in outer",
+            "    [log] inner",
+            "        [info] in inner",
+            "    [info] back in outer",
+            "[info] top again",
+        ]
+    );
+}
+
+#[test]
+fn test_group_paused_keeps_order() {
+    let (rec, captured) = RecordingReporter::new(consola::reporters::BasicReporter::new());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(rec) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    });
+    c.pause_logs();
+    c.group("g");
+    c.info("nested");
+    c.group_end();
+    assert!(captured.lock().is_empty());
+    c.resume_logs();
+    assert_eq!(*captured.lock(), vec!["[log] g", "  [info] nested"]);
+}
+
+#[test]
+fn test_sinks_capture_info_and_error() {
+    let sink = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(sink.clone()),
+        err_sink: Arc::new(sink.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("hello");
+    c.error("boom");
+    let out = sink.contents();
+    assert!(out.starts_with("[info] hello\n"), "got: {out:?}");
+    assert!(out.contains("[error] boom"), "got: {out:?}");
+}
+
+#[test]
+fn test_sinks_split_by_level() {
+    let out = consola::TestSink::new();
+    let err = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        err_sink: Arc::new(err.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("to out");
+    c.warn("to err");
+    assert_eq!(out.contents(), "[info] to out\n");
+    assert_eq!(err.contents(), "[warn] to err\n");
+}
+
+fn make_basic_consola() -> (consola::Consola, consola::TestSink) {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        err_sink: Arc::new(out.clone()),
+        ..ConsolaOptions::default()
+    });
+    (c, out)
+}
+
+#[test]
+fn test_log_with_fields() {
+    let (c, out) = make_basic_consola();
+    assert!(c.log_with_fields(
+        LogType::Info,
+        "http",
+        "listening",
+        &[("port", &8080), ("host", &"localhost"), ("port", &8443)],
+    ));
+    assert_eq!(
+        out.contents(),
+        "[info] [http] listening port=8443 host=localhost\n"
+    );
+}
+
+#[test]
+fn test_default_fields_merge_with_entry_fields() {
+    let (c, out) = make_basic_consola();
+    let sub = c.with_defaults(
+        LogObjectInput::new()
+            .field("service", "api")
+            .field("env", "dev"),
+    );
+    sub.log_obj(
+        &LogObjectInput::new()
+            .type_(LogType::Info)
+            .arg("ready")
+            .field("env", "prod")
+            .field("pid", 42),
+    );
+    assert_eq!(out.contents(), "[info] ready service=api env=prod pid=42\n");
+}
+
+fn make_aligned_consola(wrap: bool) -> (consola::Consola, consola::TestSink) {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        format_options: FormatOptions {
+            align_multiline: true,
+            wrap,
+            columns: Some(80),
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    (c, out)
+}
+
+#[test]
+fn test_align_multiline_indents_under_message() {
+    for wrap in [false, true] {
+        let (c, out) = make_aligned_consola(wrap);
+        c.info("first line\nsecond line\nthird line");
+        assert_eq!(
+            out.lines(),
+            vec![
+                "[info] first line",
+                "       second line",
+                "       third line"
+            ],
+            "wrap: {wrap}"
+        );
+    }
+}
+
+#[test]
+fn test_align_multiline_accounts_for_logger_name() {
+    let (c, out) = make_aligned_consola(false);
+    let api = c.with_name("api");
+    api.info("first line\nsecond line");
+    assert_eq!(
+        out.lines(),
+        vec!["api [info] first line", "           second line"]
+    );
+}
+
+#[test]
+fn test_truncate_caps_long_lines() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        format_options: FormatOptions {
+            truncate: Some(20),
+            wrap: true,
+            columns: Some(80),
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    c.info("a very long message that keeps going\nshort");
+    c.info("fits");
+    assert_eq!(
+        out.lines(),
+        vec!["[info] a very long …", "short", "[info] fits"]
+    );
+}
+
+#[test]
+fn test_multiline_unaligned_by_default() {
+    let (c, out) = make_basic_consola();
+    c.info("first line\nsecond line");
+    assert_eq!(out.lines(), vec!["[info] first line", "second line"]);
+}
+
+#[test]
+fn test_named_logger_prefixes_output() {
+    let (c, out) = make_basic_consola();
+    let api = c.with_name("api");
+    assert_eq!(api.name().as_deref(), Some("api"));
+    assert_eq!(c.name(), None);
+    api.log_with_fields(LogType::Info, "auth", "ready", &[]);
+    c.info("unnamed");
+    assert_eq!(out.contents(), "api [info] [auth] ready\n[info] unnamed\n");
+}
+
+#[test]
+fn test_named_logger_in_json() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        name: Some("worker".into()),
+        reporters: vec![Box::new(consola::reporters::JsonReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("started");
+    let v: serde_json::Value = serde_json::from_str(out.contents().trim()).unwrap();
+    assert_eq!(v["logger"], "worker");
+    assert_eq!(v["tag"], "");
+}
+
+#[test]
+fn test_trace_id_prefix() {
+    let (c, out) = make_basic_consola();
+    let sub = c.with_trace_id("4bf92f3577b34da6a3ce929d0e0e4736");
+    sub.info("handled");
+    sub.log_obj(
+        &LogObjectInput::new()
+            .type_(LogType::Warn)
+            .trace_id("00f067aa0ba902b7")
+            .arg("retried"),
+    );
+    assert_eq!(
+        out.contents(),
+        "#4bf92f35 [info] handled\n#00f067aa [warn] retried\n"
+    );
+}
+
+#[test]
+fn test_trace_id_in_json_and_children() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::JsonReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        ..ConsolaOptions::default()
+    });
+    let child = c.with_trace_id("req-42").with_tag("db").with_name("worker");
+    child.info("query");
+    c.info("untraced");
+    let lines: Vec<serde_json::Value> = out
+        .contents()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines[0]["trace_id"], "req-42");
+    assert!(lines[1]["trace_id"].is_null());
+}
+
+#[test]
+fn test_large_integers_keep_precision() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::JsonReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    // Adjacent values near i64::MAX are equal as f64, so these would
+    // throttle as duplicates if args went through floats.
+    for n in [i64::MAX, i64::MAX - 1] {
+        c.log_obj(
+            &LogObjectInput::new()
+                .type_(LogType::Info)
+                .arg(n)
+                .field("max", u64::MAX),
+        );
+    }
+    let records: Vec<serde_json::Value> = out
+        .lines()
+        .iter()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["args"][0], "9223372036854775807");
+    assert_eq!(records[1]["args"][0], "9223372036854775806");
+    assert_eq!(records[0]["fields"]["max"], "18446744073709551615");
+}
+
+fn make_split_consola(
+    stderr_level: LogLevel,
+    stdout_only: bool,
+) -> (consola::Consola, consola::TestSink, consola::TestSink) {
+    let out = consola::TestSink::new();
+    let err = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        err_sink: Arc::new(err.clone()),
+        stderr_level,
+        stdout_only,
+        ..ConsolaOptions::default()
+    });
+    (c, out, err)
+}
+
+#[test]
+fn test_stderr_level_default_sends_warn_to_err() {
+    let (c, out, err) = make_split_consola(ConsolaOptions::default().stderr_level, false);
+    c.warn("w");
+    assert_eq!(out.contents(), "");
+    assert_eq!(err.contents(), "[warn] w\n");
+}
+
+#[test]
+fn test_stderr_level_error_keeps_warn_on_out() {
+    let (c, out, err) = make_split_consola(log_levels::ERROR, false);
+    c.warn("w");
+    assert_eq!(out.contents(), "[warn] w\n");
+    assert_eq!(err.contents(), "");
+}
+
+#[test]
+fn test_stderr_level_info_sends_info_to_err() {
+    let (c, out, err) = make_split_consola(log_levels::INFO, false);
+    c.warn("w");
+    c.info("i");
+    c.debug("d");
+    assert_eq!(err.contents(), "[warn] w\n[info] i\n");
+    assert_eq!(out.contents(), "");
+}
+
+#[test]
+fn test_stdout_only_sends_warn_to_out() {
+    let (c, out, err) = make_split_consola(log_levels::WARN, true);
+    c.warn("w");
+    assert_eq!(out.contents(), "[warn] w\n");
+    assert_eq!(err.contents(), "");
+}
+
+#[derive(Debug, Clone, Default)]
+struct FlushCountingSink {
+    writes: Arc<Mutex<usize>>,
+    flushes: Arc<Mutex<usize>>,
+}
+
+impl consola::Sink for FlushCountingSink {
+    fn write_line(&self, _line: &str) -> std::io::Result<()> {
+        *self.writes.lock() += 1;
+        Ok(())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        *self.flushes.lock() += 1;
+        Ok(())
+    }
+}
+
+fn make_flush_consola(flush_level: LogLevel) -> (consola::Consola, FlushCountingSink) {
+    let sink = FlushCountingSink::default();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(sink.clone()),
+        err_sink: Arc::new(sink.clone()),
+        flush_level,
+        ..ConsolaOptions::default()
+    });
+    (c, sink)
+}
+
+#[test]
+fn test_flush_level_default_flushes_errors_only() {
+    let (c, sink) = make_flush_consola(ConsolaOptions::default().flush_level);
+    c.info("batched");
+    c.warn("batched");
+    assert_eq!(*sink.writes.lock(), 2);
+    assert_eq!(*sink.flushes.lock(), 0);
+    c.error("flushed");
+    assert_eq!(*sink.writes.lock(), 3);
+    assert_eq!(*sink.flushes.lock(), 1);
+}
+
+#[test]
+fn test_flush_level_configurable() {
+    let (c, sink) = make_flush_consola(log_levels::INFO);
+    c.info("flushed");
+    c.debug("skipped by level");
+    assert_eq!(*sink.flushes.lock(), 1);
+
+    let (c, sink) = make_flush_consola(log_levels::SILENT);
+    c.error("never flushed");
+    assert_eq!(*sink.writes.lock(), 1);
+    assert_eq!(*sink.flushes.lock(), 0);
+}
+
+#[test]
+fn test_reporter_errors_go_to_err_sink() {
+    let err = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(ErrReporter) as Box<dyn Reporter>],
+        err_sink: Arc::new(err.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.info("x");
+    assert_eq!(
+        err.contents(),
+        "[consola] reporter error: reporter error: intentional test error\n"
+    );
+}
+
+#[test]
+fn test_silent_suppresses_levels_below_silent() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::SILENT,
+        ..ConsolaOptions::default()
+    });
+    let below = LogObjectInput {
+        level: Some(-100),
+        r#type: Some(LogType::Fatal),
+        ..LogObjectInput::default()
+    }
+    .message("very severe");
+    assert!(!c.log_obj(&below));
+    assert!(!c.fatal("also filtered"));
+    c.group("nothing");
+    assert_eq!(cr.count(), 0);
+
+    // At a real level, a custom level below FATAL is still emitted.
+    c.set_level(log_levels::ERROR);
+    assert!(c.log_obj(&below));
+    assert_eq!(cr.count(), 1);
+}
+
+#[test]
+fn test_format_options_default() {
+    let opts = FormatOptions::default();
+    let _c = consola::Consola::new(ConsolaOptions {
+        format_options: opts,
+        ..ConsolaOptions::default()
+    });
+    // smoketest: no crash
+}
+
+// ===================================================================
+// Reporter trait smoke — clone_box
+// ===================================================================
+
+#[test]
+fn test_reporter_clone_box() {
+    let cr = CaptureReporter::new();
+    let cloned: Box<dyn Reporter> = cr.clone_box();
+    let _ = cloned.format(
+        &LogObject::new(LogType::Info),
+        &LogContext {
+            options: Arc::new(ConsolaOptions::default()),
+        },
+    );
+}
+
+#[test]
+fn test_info_empty_string() {
+    let (c, _cr) = make_consola();
+    assert!(c.info(""));
+}
+
+#[test]
+fn test_info_long_message() {
+    let (c, _cr) = make_consola();
+    let long = "a".repeat(10_000);
+    assert!(c.info(&long));
+}
+
+// ===================================================================
+// remove_reporter edge cases
+// ===================================================================
+
+#[test]
+fn test_remove_reporter_out_of_bounds() {
+    let cr = CaptureReporter::new();
+    let opts = ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    };
+    let c = consola::Consola::new(opts);
+    // removing index 5 from a list with 1 element should error
+    assert!(
+        c.remove_reporter(5).is_err(),
+        "expected error on OOB remove"
+    );
+}
+
+#[test]
+fn test_remove_reporter_empty() {
+    let c = consola::Consola::new(ConsolaOptions::default());
+    // removing index 0 from an empty list should error
+    assert!(
+        c.remove_reporter(0).is_err(),
+        "expected error on remove from empty"
+    );
+}
+
+#[test]
+fn test_resume_without_pause() {
+    let (c, cr) = make_consola();
+    c.info("before");
+    let count_before = cr.count();
+    // resume when not paused should be a no-op
+    c.resume_logs();
+    // no crash and no extra emission
+    assert_eq!(cr.count(), count_before);
+}
+
+#[test]
+fn test_level_clamping_negative() {
+    let c = make_consola_level(log_levels::INFO);
+    c.set_level(-5);
+    assert_eq!(c.level(), 0, "-5 should clamp to 0");
+}
+
+#[test]
+fn test_level_clamping_max() {
+    let c = make_consola_level(log_levels::INFO);
+    c.set_level(255);
+    let clamped = c.level();
+    assert!(clamped <= 6, "255 should clamp to <=6, got {}", clamped);
+    assert_eq!(clamped, log_levels::TRACE, "255 should clamp to TRACE (5)");
+}
+
+// ===================================================================
+// log_obj_raw
+// ===================================================================
+
+#[test]
+fn test_log_obj_raw() {
+    let (c, cr) = make_consola();
+    assert!(c.log_obj_raw(&LogObjectInput {
+        r#type: Some(LogType::Info),
+        message: Some("raw log_obj".into()),
+        ..LogObjectInput::default()
+    }));
+    let last = cr.last().unwrap();
+    assert!(last.contains("raw log_obj"), "got: {last}");
+}
+
+#[test]
+fn test_log_all_types_at_verbose_raw() {
+    let (c, _cr) = make_consola();
+    assert!(c.fatal_raw("x"));
+    assert!(c.error_raw("x"));
+    assert!(c.warn_raw("x"));
+    assert!(c.info_raw("x"));
+    assert!(c.success_raw("x"));
+    assert!(c.fail_raw("x"));
+    assert!(c.ready_raw("x"));
+    assert!(c.start_raw("x"));
+    assert!(c.box_raw("x"));
+    assert!(c.debug_raw("x"));
+    assert!(c.trace_raw("x"));
+    assert!(c.verbose_raw("x"));
+    assert!(c.log_raw("x"));
+}
+
+// ===================================================================
+// create with reporters
+// ===================================================================
+
+#[test]
+fn test_create_without_reporters() {
+    let cr = CaptureReporter::new();
+    let opts = ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    };
+    let c = consola::Consola::new(opts);
+    // child with empty reporters should inherit parent's reporters
+    let child = c.create(ConsolaOptions {
+        reporters: vec![],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    });
+    child.info("from child");
+    assert_eq!(cr.count(), 1, "child should inherit parent reporters");
+}
+
+#[test]
+fn test_create_with_new_reporters() {
+    let cr_parent = CaptureReporter::new();
+    let opts = ConsolaOptions {
+        reporters: vec![Box::new(cr_parent.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    };
+    let c = consola::Consola::new(opts);
+
+    let cr_child = CaptureReporter::new();
+    let child = c.create(ConsolaOptions {
+        reporters: vec![Box::new(cr_child.clone()) as Box<dyn Reporter>],
+        level: log_levels::VERBOSE,
+        ..ConsolaOptions::default()
+    });
+    child.info("from child");
+    assert_eq!(cr_parent.count(), 0, "parent reporter should not fire");
+    assert_eq!(cr_child.count(), 1, "child reporter should fire");
 }
 
 #[test]
@@ -924,6 +2361,7 @@ fn test_with_defaults_additional() {
 #[cfg(feature = "log")]
 mod log_trait_tests {
     use super::*;
+    use consola::Consola;
 
     fn make_logger() -> (Consola, CaptureReporter) {
         let cr = CaptureReporter::new();
@@ -978,6 +2416,53 @@ mod log_trait_tests {
         assert!(last.contains("log-test-message"), "got: {}", last);
     }
 
+    #[test]
+    fn test_log_records_are_redacted() {
+        let (c, cr) = make_logger();
+        c.set_redactors(vec![Arc::new(TokenRedactor)]);
+        log::Log::log(
+            &c,
+            &log::Record::builder()
+                .args(format_args!("login tok_abc ok"))
+                .level(log::Level::Info)
+                .target("auth")
+                .build(),
+        );
+        assert_eq!(cr.all(), vec!["[info]<auth>: login *** ok"]);
+    }
+
+    #[test]
+    fn test_log_target_levels_override_global_level() {
+        let (c, cr) = make_logger();
+        c.set_level(log_levels::INFO);
+        c.set_target_levels("mycrate=debug,mycrate::noisy=off".parse().unwrap());
+
+        let debug_meta = |target| {
+            log::Metadata::builder()
+                .level(log::Level::Debug)
+                .target(target)
+                .build()
+        };
+        assert!(log::Log::enabled(&c, &debug_meta("mycrate::db")));
+        assert!(!log::Log::enabled(&c, &debug_meta("mycrate::noisy")));
+        assert!(!log::Log::enabled(&c, &debug_meta("other")));
+
+        for target in ["mycrate::db", "mycrate::noisy", "other"] {
+            log::Log::log(
+                &c,
+                &log::Record::builder()
+                    .args(format_args!("debug from {}", target))
+                    .level(log::Level::Debug)
+                    .target(target)
+                    .build(),
+            );
+        }
+        assert_eq!(
+            cr.all(),
+            vec!["[debug]<mycrate::db>: debug from mycrate::db"]
+        );
+    }
+
     #[test]
     fn test_log_log_level_filtering() {
         let (c, cr) = make_logger();
@@ -1006,6 +2491,20 @@ mod log_trait_tests {
         log::Log::flush(&c);
     }
 
+    #[test]
+    fn test_log_flush_reaches_sinks_and_reporters() {
+        let (c, sink) = make_flush_consola(log_levels::ERROR);
+        let reporter = BufferingReporter::default();
+        c.add_reporter(Box::new(reporter.clone()));
+        c.info("buffered");
+        assert_eq!(*sink.flushes.lock(), 0);
+
+        log::Log::flush(&c);
+        // Both sinks are the same counting sink.
+        assert_eq!(*sink.flushes.lock(), 2);
+        assert_eq!(*reporter.flushed.lock(), vec!["buffered"]);
+    }
+
     #[test]
     fn test_log_enabled_warn() {
         let (c, _cr) = make_logger();
@@ -1127,3 +2626,530 @@ mod subscriber_tests {
         assert!(hint.is_some());
     }
 }
+
+fn retry_error() -> LogObjectInput {
+    LogObjectInput {
+        r#type: Some(LogType::Error),
+        level: Some(log_levels::ERROR),
+        message: Some("retrying".into()),
+        error: Some(consola::types::ErrorInfo {
+            message: "request failed".into(),
+            cause: Some(Box::new(consola::types::ErrorInfo {
+                message: "connection refused".into(),
+                ..consola::types::ErrorInfo::default()
+            })),
+            ..consola::types::ErrorInfo::default()
+        }),
+        ..LogObjectInput::default()
+    }
+}
+
+#[test]
+fn test_error_chain_window_abbreviates_repeats() {
+    let out = consola::TestSink::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        err_sink: Arc::new(out.clone()),
+        error_chain_window: 1000,
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    c.log_obj(&retry_error());
+    clock.advance(std::time::Duration::from_millis(1500));
+    c.log_obj(&retry_error());
+    c.info("backing off");
+    c.log_obj(&retry_error());
+    assert_eq!(
+        out.lines(),
+        vec![
+            "[error] retrying",
+            "request failed",
+            "",
+            "  [cause]: connection refused",
+            "[error] retrying",
+            "request failed",
+            "",
+            "  [cause]: connection refused",
+            "[info] backing off",
+            "[error] retrying",
+            "request failed (same cause as above)",
+        ]
+    );
+}
+
+#[test]
+fn test_cardinality_guard_bounds_unique_floods() {
+    let out = consola::TestSink::new();
+    let clock = consola::MockClock::new(0);
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        err_sink: Arc::new(out.clone()),
+        cardinality_limit: 100,
+        clock: Arc::new(clock.clone()),
+        ..ConsolaOptions::default()
+    });
+    let accepted = (0..5000)
+        .filter(|i| c.info(&format!("request {} done", i)))
+        .count();
+    assert_eq!(accepted, 100);
+    // Entries already admitted in the window still pass.
+    assert!(c.info("request 7 done"));
+
+    let lines = out.lines();
+    assert_eq!(lines.len(), 102);
+    assert_eq!(lines[99], "[info] request 99 done");
+    assert_eq!(
+        lines[100],
+        "[warn] consola: more than 100 distinct messages within 1000ms, suppressing new ones"
+    );
+    assert_eq!(lines[101], "[info] request 7 done");
+
+    clock.advance(std::time::Duration::from_millis(1000));
+    c.info("recovered");
+    assert_eq!(
+        out.lines()[102..],
+        [
+            "[warn] consola: suppressed 4900 distinct messages over the cardinality limit",
+            "[info] recovered",
+        ]
+    );
+    let info = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Info);
+    assert_eq!(info.unwrap().1.suppressed, 4900);
+}
+
+#[test]
+fn test_error_chain_printed_in_full_by_default() {
+    let (c, out) = make_basic_consola();
+    c.log_obj(&retry_error());
+    c.info("backing off");
+    c.log_obj(&retry_error());
+    assert_eq!(
+        out.lines()
+            .iter()
+            .filter(|l| l.contains("connection refused"))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_shared_across_threads_without_mutex() {
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 200;
+
+    let (c, cr) = make_consola();
+    let c = Arc::new(c);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let c = Arc::clone(&c);
+            std::thread::spawn(move || {
+                for n in 0..PER_THREAD {
+                    assert!(c.info(&format!("t{} {}", t, n)));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let all = cr.all();
+    assert_eq!(all.len(), THREADS * PER_THREAD);
+    for t in 0..THREADS {
+        let prefix = format!("[info]: t{} ", t);
+        let seq: Vec<usize> = all
+            .iter()
+            .filter_map(|line| line.strip_prefix(&prefix))
+            .map(|n| n.parse().unwrap())
+            .collect();
+        assert_eq!(seq, (0..PER_THREAD).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_box_message_and_tree_reach_reporters() {
+    let (c, out) = make_basic_consola();
+    assert!(c.box_message("Update", &["v1 -> v2".into(), "run cargo update".into()]));
+    let items = vec![TreeItem::Node {
+        text: "src".into(),
+        children: vec!["lib.rs".into(), "main.rs".into()],
+        color: None,
+    }];
+    let opts = TreeOptions {
+        unicode: false,
+        ..TreeOptions::default()
+    };
+    assert!(c.tree(&items, &opts));
+    assert_eq!(
+        out.contents(),
+        "\n > Update\n > v1 -> v2\n > run cargo update\n\n\
+         [log]   `-src\n    |-lib.rs\n    `-main.rs\n"
+    );
+
+    c.set_level(log_levels::ERROR);
+    assert!(!c.box_message("", &["hidden".into()]));
+    assert!(!c.tree(&items, &opts));
+}
+
+#[derive(Debug, Default)]
+struct ClosedPipeSink {
+    attempts: std::sync::atomic::AtomicU32,
+}
+
+impl consola::Sink for ClosedPipeSink {
+    fn write_line(&self, _line: &str) -> std::io::Result<()> {
+        self.attempts
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+}
+
+#[test]
+fn test_failing_sink_latches_off_after_limit() {
+    let closed = Arc::new(ClosedPipeSink::default());
+    let err = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        out_sink: closed.clone(),
+        err_sink: Arc::new(err.clone()),
+        write_failure_limit: 3,
+        ..ConsolaOptions::default()
+    });
+    for i in 0..10 {
+        c.info(&format!("line {}", i));
+    }
+    assert_eq!(closed.attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+    assert_eq!(
+        err.lines(),
+        vec!["[consola] stopped writing to the output sink after 3 failed writes: broken pipe"]
+    );
+    assert_eq!(c.stats()[0].1.dropped, 7);
+
+    let hooked = Arc::new(std::sync::atomic::AtomicU32::new(0));
+    {
+        let hooked = hooked.clone();
+        c.on_record(move |_| {
+            hooked.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+    }
+    c.info("dropped");
+    assert_eq!(hooked.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    c.error("still reported");
+    assert_eq!(hooked.load(std::sync::atomic::Ordering::SeqCst), 1);
+    assert!(err.contains_line("[error] still reported"));
+}
+
+#[test]
+fn test_set_format_options_reaches_reporters() {
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(
+            consola::reporters::FancyReporter::new()
+                .colors(false)
+                .unicode(false),
+        ) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        format_options: FormatOptions {
+            date: true,
+            columns: Some(40),
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    c.info("with date");
+    c.set_format_options(FormatOptions {
+        date: false,
+        ..c.format_options()
+    });
+    c.info("without date");
+    c.set_columns(None);
+    assert_eq!(c.format_options().columns, None);
+    assert!(!c.format_options().date);
+
+    let lines = out.lines();
+    assert!(lines[0].contains(':'), "got: {:?}", lines[0]);
+    assert_eq!(lines[1], "i without date");
+}
+
+#[test]
+fn test_silence_discards_and_resumes() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    c.info("dup");
+    c.info("dup");
+    c.info("dup");
+
+    c.silence();
+    assert!(c.is_silenced());
+    // The pending repeat summary is written before silencing.
+    assert_eq!(
+        cr.all(),
+        vec!["[info]: dup", "[info]: dup (repeated 2 times)"]
+    );
+    assert!(!c.info("noisy"));
+    assert!(!c.error("noisy"));
+    c.group("hidden");
+    assert_eq!(c.group_depth(), 1);
+    c.group_end();
+    assert_eq!(cr.count(), 2);
+    assert_eq!(c.level(), log_levels::INFO);
+
+    c.unsilence();
+    assert!(!c.is_silenced());
+    assert!(c.info("back"));
+    assert_eq!(cr.all().last().unwrap(), "[info]: back");
+    let error = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Error);
+    assert_eq!(error.unwrap().1.filtered, 1);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_silence_discards_bridged_records() {
+    let (c, cr) = make_consola();
+    c.silence();
+    log::Log::log(
+        &c,
+        &log::Record::builder()
+            .args(format_args!("bridged while silenced"))
+            .level(log::Level::Info)
+            .target("app")
+            .build(),
+    );
+    assert_eq!(cr.count(), 0);
+    let info = c.stats().into_iter().find(|(ty, _)| *ty == LogType::Info);
+    assert_eq!(info.unwrap().1.filtered, 1);
+}
+
+#[derive(Debug)]
+struct TokenRedactor;
+
+impl consola::Redactor for TokenRedactor {
+    fn redact(&self, log_obj: &mut LogObject) {
+        for arg in &mut log_obj.args {
+            if let Some(start) = arg.find("tok_") {
+                let end = arg[start..]
+                    .find(' ')
+                    .map_or(arg.len(), |offset| start + offset);
+                arg.replace_range(start..end, "***");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_redactors_mask_before_throttling() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        redactors: vec![Arc::new(TokenRedactor)],
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    c.info("auth with tok_abc ok");
+    // These differ only in the secret, so they are throttled as repeats.
+    c.info("auth with tok_def ok");
+    c.info("auth with tok_ghi ok");
+    c.flush();
+    assert_eq!(
+        cr.all(),
+        vec![
+            "[info]: auth with *** ok",
+            "[info]: auth with *** ok (repeated 2 times)"
+        ]
+    );
+
+    // Derived instances keep the parent's redactors.
+    let child = c.create(ConsolaOptions::default());
+    child.info("tok_xyz");
+    assert_eq!(cr.all().last().unwrap(), "[info]: ***");
+}
+
+#[test]
+fn test_redactors_mask_group_labels() {
+    let cr = CaptureReporter::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+        redactors: vec![Arc::new(TokenRedactor)],
+        ..ConsolaOptions::default()
+    });
+    c.group("session tok_abc");
+    c.group_end();
+    assert_eq!(cr.all(), vec!["[log]: session ***", "[log]: session ***"]);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex_redactor_masks_emitted_message() {
+    let (c, sink) = make_basic_consola();
+    c.set_redactors(vec![Arc::new(consola::RegexRedactor::new(
+        regex::Regex::new(r"[\w.]+@[\w.]+").unwrap(),
+    ))]);
+    c.info("signup from jane@example.com");
+    assert!(sink.contents().contains("signup from ***"));
+}
+
+#[test]
+fn test_record_hooks_fire_in_order_and_can_be_removed() {
+    let c = make_consola_level(log_levels::INFO);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let first = {
+        let seen = seen.clone();
+        c.on_record(move |obj| seen.lock().push(format!("first:{}", obj.args[0])))
+    };
+    let _second = {
+        let seen = seen.clone();
+        c.on_record(move |obj| seen.lock().push(format!("second:{}", obj.r#type.as_str())))
+    };
+
+    c.warn("disk");
+    c.debug("filtered");
+    assert_eq!(*seen.lock(), vec!["first:disk", "second:warn"]);
+
+    assert!(c.remove_hook(first));
+    assert!(!c.remove_hook(first));
+    c.info("next");
+    assert_eq!(seen.lock().last().unwrap(), "second:info");
+    assert_eq!(seen.lock().len(), 3);
+}
+
+#[test]
+fn test_metrics_reporter_tallies_types_and_repeats() {
+    let metrics = consola::reporters::MetricsReporter::new(CaptureReporter::new());
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(metrics.clone()) as Box<dyn Reporter>],
+        throttle: 60_000,
+        throttle_min: 1,
+        ..ConsolaOptions::default()
+    });
+    c.warn("low disk");
+    c.error("boom");
+    for _ in 0..4 {
+        c.info("tick");
+    }
+    c.debug("filtered");
+    c.flush();
+
+    let counts = metrics.counts();
+    assert_eq!(counts.get("warn"), Some(&1));
+    assert_eq!(counts.get("error"), Some(&1));
+    assert_eq!(counts.get("info"), Some(&4));
+    assert_eq!(counts.get("debug"), None);
+}
+
+#[test]
+fn test_relative_time_mode_uses_logger_start() {
+    let clock = consola::MockClock::new(1_700_000_000_000);
+    let out = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::FancyReporter::new()) as Box<dyn Reporter>],
+        out_sink: Arc::new(out.clone()),
+        clock: Arc::new(clock.clone()),
+        format_options: FormatOptions {
+            time_mode: consola::types::TimeMode::Relative,
+            columns: Some(40),
+            ..FormatOptions::default()
+        },
+        ..ConsolaOptions::default()
+    });
+    c.info("start");
+    clock.advance(std::time::Duration::from_millis(1_250));
+    c.info("later");
+
+    let lines = out.lines();
+    let plain: Vec<String> = lines.iter().map(|l| consola::strip_ansi(l)).collect();
+    assert!(plain[0].ends_with("+0.000s"), "{:?}", plain);
+    assert!(plain[1].ends_with("+1.250s"), "{:?}", plain);
+}
+
+#[test]
+fn test_prefix_renders_between_type_and_message() {
+    let (c, sink) = make_basic_consola();
+    let api = c.with_prefix("api");
+    api.info("ready");
+    assert_eq!(sink.lines().last().unwrap(), "[info] api: ready");
+    api.log_obj(&LogObjectInput::new().tag("db").message("connected"));
+    assert_eq!(sink.lines().last().unwrap(), "[log] [db] api: connected");
+}
+
+#[test]
+fn test_prefix_is_not_fingerprinted_by_default() {
+    let log_with_prefix = |fingerprint_prefix: bool| {
+        let cr = CaptureReporter::new();
+        let c = consola::Consola::new(ConsolaOptions {
+            reporters: vec![Box::new(cr.clone()) as Box<dyn Reporter>],
+            throttle: 60_000,
+            throttle_min: 1,
+            fingerprint_prefix,
+            ..ConsolaOptions::default()
+        });
+        for prefix in ["a", "b", "c"] {
+            c.log_obj(&LogObjectInput {
+                r#type: Some(LogType::Info),
+                prefix: Some(prefix.into()),
+                args: vec!["same".into()],
+                ..LogObjectInput::default()
+            });
+        }
+        c.flush();
+        cr.count()
+    };
+    // One entry plus a "(repeated 2 times)" summary.
+    assert_eq!(log_with_prefix(false), 2);
+    assert_eq!(log_with_prefix(true), 3);
+}
+
+#[test]
+fn test_extensions_reach_custom_reporters() {
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u64);
+
+    #[derive(Debug, Clone)]
+    struct RequestReporter(Arc<Mutex<Vec<Option<u64>>>>);
+
+    impl Reporter for RequestReporter {
+        fn format(
+            &self,
+            log_obj: &LogObject,
+            _ctx: &LogContext,
+        ) -> Result<String, consola::error::ConsolaError> {
+            self.0
+                .lock()
+                .push(log_obj.get_ext::<RequestId>().map(|id| id.0));
+            Ok(String::new())
+        }
+
+        fn clone_box(&self) -> Box<dyn Reporter> {
+            Box::new(self.clone())
+        }
+    }
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(RequestReporter(seen.clone())) as Box<dyn Reporter>],
+        throttle: 0,
+        ..ConsolaOptions::default()
+    });
+    c.log_obj(&LogObjectInput::new().message("plain"));
+    c.log_obj(
+        &LogObjectInput::new()
+            .message("tagged")
+            .extension(RequestId(42)),
+    );
+
+    let scoped = c.with_defaults(LogObjectInput::new().extension(RequestId(7)));
+    scoped.info("inherited");
+    scoped.log_obj(&LogObjectInput::new().message("own").extension(RequestId(8)));
+    c.flush();
+
+    assert_eq!(*seen.lock(), vec![None, Some(42), Some(7), Some(8)]);
+}
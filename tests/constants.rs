@@ -1,4 +1,8 @@
-use consola::constants::{LOG_TYPES, log_type_defaults, log_type_level, normalize_log_level};
+use consola::constants::{
+    LOG_TYPES, all_log_types, custom_type_def, custom_types, define_type, log_type_defaults,
+    log_type_level, normalize_log_level, parse_log_level, passes_level, primary_type_for_level,
+    type_names_for_level,
+};
 use consola::{LogLevel, LogType, log_levels};
 use std::str::FromStr;
 
@@ -172,3 +176,104 @@ fn normalize_log_level_values() {
     assert_eq!(normalize_log_level(Some(i32::MIN), 0), 0);
     assert_eq!(normalize_log_level(Some(i32::MAX), 0), 5);
 }
+
+#[test]
+fn parse_log_level_values() {
+    assert_eq!(parse_log_level("4"), Some(4));
+    assert_eq!(parse_log_level(" 0 "), Some(0));
+    assert_eq!(parse_log_level("debug"), Some(log_levels::DEBUG));
+    assert_eq!(parse_log_level("warn"), Some(log_levels::WARN));
+    assert_eq!(parse_log_level("verbose"), Some(log_levels::VERBOSE));
+    assert_eq!(parse_log_level("loud"), None);
+    assert_eq!(parse_log_level(""), None);
+}
+
+#[test]
+fn parse_log_level_case_and_aliases() {
+    assert_eq!(parse_log_level("INFO"), Some(log_levels::INFO));
+    assert_eq!(parse_log_level(" Debug "), Some(log_levels::DEBUG));
+    assert_eq!(parse_log_level("warning"), Some(log_levels::WARN));
+    assert_eq!(parse_log_level("WARNING"), Some(log_levels::WARN));
+    assert_eq!(parse_log_level("err"), Some(log_levels::ERROR));
+    assert_eq!(parse_log_level("dbg"), Some(log_levels::DEBUG));
+    assert_eq!(parse_log_level("off"), Some(log_levels::SILENT));
+    assert_eq!(parse_log_level("None"), Some(log_levels::SILENT));
+    assert_eq!(parse_log_level("all"), Some(log_levels::VERBOSE));
+}
+
+#[test]
+fn passes_level_semantics() {
+    assert!(passes_level(log_levels::INFO, log_levels::INFO));
+    assert!(!passes_level(log_levels::DEBUG, log_levels::INFO));
+    // Custom levels below FATAL are more severe and pass at any real level.
+    assert!(passes_level(-100, log_levels::ERROR));
+    assert!(passes_level(-100, -100));
+    // SILENT as a config suppresses everything, even levels at or below it.
+    assert!(!passes_level(log_levels::FATAL, log_levels::SILENT));
+    assert!(!passes_level(-100, log_levels::SILENT));
+    assert!(!passes_level(log_levels::SILENT, log_levels::SILENT));
+    assert!(passes_level(log_levels::VERBOSE, log_levels::VERBOSE));
+}
+
+#[test]
+fn define_type_registers_level_and_name() {
+    let ty = define_type("deploy", 3, Some("🚀"), Some("magenta")).unwrap();
+    assert_eq!(ty, LogType::Custom("deploy"));
+    assert_eq!(ty.as_str(), "deploy");
+    assert_eq!(ty.level(), 3);
+    assert_eq!(LogType::from_str("deploy"), Ok(ty));
+    assert_eq!(parse_log_level("deploy"), Some(3));
+
+    let def = custom_type_def("deploy").unwrap();
+    assert_eq!(def.icon, Some("🚀"));
+    assert_eq!(def.color, Some("magenta"));
+}
+
+#[test]
+fn define_type_redefinition_replaces_theme() {
+    define_type("audit", 2, Some("A"), None).unwrap();
+    let ty = define_type("audit", 4, None, Some("blue")).unwrap();
+    assert_eq!(ty.level(), 4);
+    assert_eq!(custom_type_def("audit").unwrap().icon, None);
+}
+
+#[test]
+fn define_type_rejects_builtin_names() {
+    assert_eq!(
+        define_type("info", 0, None, None),
+        Err(consola::error::ConsolaError::BuiltinType("info".into()))
+    );
+}
+
+#[test]
+fn type_names_for_builtin_levels() {
+    assert_eq!(type_names_for_level(0), vec!["fatal", "error"]);
+    assert_eq!(type_names_for_level(1), vec!["warn"]);
+    assert_eq!(type_names_for_level(5), vec!["trace"]);
+    assert!(type_names_for_level(77).is_empty());
+}
+
+#[test]
+fn primary_type_for_builtin_levels() {
+    assert_eq!(primary_type_for_level(0), Some(LogType::Error));
+    assert_eq!(primary_type_for_level(2), Some(LogType::Log));
+    assert_eq!(primary_type_for_level(3), Some(LogType::Info));
+    assert_eq!(primary_type_for_level(-1), Some(LogType::Silent));
+    assert_eq!(primary_type_for_level(77), None);
+}
+
+#[test]
+fn level_lookups_include_custom_types() {
+    let ty = define_type("lookup-metric", 42, None, Some("cyan")).unwrap();
+    assert_eq!(type_names_for_level(42), vec!["lookup-metric"]);
+    assert_eq!(primary_type_for_level(42), Some(ty));
+
+    assert!(all_log_types().starts_with(LOG_TYPES));
+    assert!(all_log_types().contains(&ty));
+    let (_, def) = custom_types()
+        .into_iter()
+        .find(|(name, _)| *name == "lookup-metric")
+        .unwrap();
+    assert_eq!(def.level, 42);
+    assert_eq!(def.color, Some("cyan"));
+}
@@ -0,0 +1,20 @@
+//! Runs in its own test binary: changes the level of the global instance.
+
+use consola::{CONSOLA, LogType, is_log_type_enabled, log_levels};
+
+#[test]
+fn test_is_log_type_enabled_follows_global_level() {
+    CONSOLA.set_level(log_levels::WARN);
+    assert!(!is_log_type_enabled(LogType::Info));
+    assert!(is_log_type_enabled(LogType::Warn));
+    assert!(is_log_type_enabled(LogType::Error));
+
+    CONSOLA.set_level(log_levels::DEBUG);
+    assert!(is_log_type_enabled(LogType::Info));
+    assert!(!is_log_type_enabled(LogType::Trace));
+
+    CONSOLA.silence();
+    assert!(!is_log_type_enabled(LogType::Warn));
+    CONSOLA.unsilence();
+    assert!(is_log_type_enabled(LogType::Warn));
+}
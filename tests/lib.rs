@@ -80,14 +80,19 @@ fn test_can_set_level() {
     // DEBUG (4) <= DEBUG (4) -> passes now
     assert!(c.debug("should pass now"));
 
-    // Set level to 0 (lowest after normalize: SILENT clamps to 0)
-    c.set_level(log_levels::SILENT);
-    // FATAL and ERROR are level 0, so 0 > 0 is false -> passes
+    // Set level to 0: FATAL and ERROR are level 0, so they still pass
+    c.set_level(log_levels::ERROR);
     assert!(c.fatal("fatal passes"));
     assert!(c.error("error passes"));
 
     // WARN (1) > 0 -> filtered
     assert!(!c.warn("warn filtered"));
+
+    // SILENT is kept as-is (not clamped to 0) and suppresses everything
+    c.set_level(log_levels::SILENT);
+    assert_eq!(c.level(), log_levels::SILENT);
+    assert!(!c.fatal("fatal filtered"));
+    assert!(!c.error("error filtered"));
 }
 
 #[test]
@@ -214,7 +219,7 @@ fn test_throttle_basic() {
     let c = Consola::new(ConsolaOptions {
         throttle: 5000,  // 5 second window
         throttle_min: 1, // collapse after first repeat
-        reporters: vec![Box::new(BasicReporter) as Box<dyn Reporter>],
+        reporters: vec![Box::new(BasicReporter::new()) as Box<dyn Reporter>],
         level: log_levels::INFO,
         ..ConsolaOptions::default()
     });
@@ -263,12 +268,19 @@ fn make_log_obj(ty: LogType, args: &[&str], tag: &str) -> LogObject {
         icon: None,
         style: None,
         error: None,
+        group_depth: 0,
+        group_event: None,
+        fields: Vec::new(),
+        repeated: 0,
+        trace_id: None,
+        prefix: None,
+        extensions: Default::default(),
     }
 }
 
 #[test]
 fn test_reporter_format() {
-    let reporter = BasicReporter;
+    let reporter = BasicReporter::new();
     let ctx = make_ctx_no_date();
 
     let obj = make_log_obj(LogType::Info, &["hello", "world"], "");
@@ -299,6 +311,25 @@ fn test_fancy_reporter_format() {
     assert!(result.contains("mytag"));
 }
 
+#[test]
+fn test_render_to_string_matches_format() {
+    let obj = make_log_obj(LogType::Warn, &["disk", "low"], "fs");
+    let ctx = LogContext {
+        options: Arc::new(ConsolaOptions::default()),
+    };
+    let reporter = BasicReporter::new();
+    assert_eq!(
+        reporter.render_to_string(&obj).unwrap(),
+        reporter.format(&obj, &ctx).unwrap()
+    );
+
+    // Fancy output carries the current time, so compare content rather than
+    // the exact line.
+    let plain = FancyReporter::new().render_plain(&obj).unwrap();
+    assert!(plain.contains("fs disk low"), "got: {plain:?}");
+    assert!(!plain.contains('\x1b'));
+}
+
 // ---------------------------------------------------------------------------
 // Prompt stub tests
 // ---------------------------------------------------------------------------
@@ -51,7 +51,7 @@ impl Reporter for CaptureReporter {
             "[{}]{}: {}",
             log_obj.r#type.as_str(),
             tag_part,
-            log_obj.args.join(" ")
+            consola::reporters::BasicReporter::new().format_message(log_obj, &Default::default())
         );
         self.captured.lock().unwrap().push(formatted.clone());
         Ok(formatted)
@@ -198,6 +198,63 @@ fn test_nested_spans_stack() {
     assert!(last.contains("child"), "child name missing: {}", last);
 }
 
+#[tracing::instrument]
+fn handle_request(user_id: u64) {
+    load_profile("main");
+}
+
+#[tracing::instrument]
+fn load_profile(db: &str) {
+    tracing::info!("profile loaded");
+}
+
+#[test]
+fn test_instrumented_spans_keep_outer_context() {
+    let (c, cr) = make_sub(log_levels::TRACE);
+    let _guard = tracing::subscriber::set_default(Box::new(c));
+
+    handle_request(7);
+    assert_eq!(cr.count(), 1);
+    let last = cr.last().unwrap();
+    assert!(
+        last.contains("<handle_request:load_profile::"),
+        "span names missing: {last}"
+    );
+    assert!(
+        last.ends_with("user_id=7 db=main"),
+        "span fields missing: {last}"
+    );
+}
+
+#[derive(Debug)]
+struct TokenRedactor;
+
+impl consola::Redactor for TokenRedactor {
+    fn redact(&self, log_obj: &mut LogObject) {
+        let mask = |text: &mut String| *text = text.replace("tok_abc", "***");
+        log_obj.args.iter_mut().for_each(mask);
+        log_obj.fields.iter_mut().for_each(|(_, v)| mask(v));
+    }
+}
+
+#[test]
+fn test_events_are_redacted() {
+    let (c, cr) = make_sub(log_levels::INFO);
+    c.set_redactors(vec![Arc::new(TokenRedactor)]);
+    let _guard = tracing::subscriber::set_default(Box::new(c));
+
+    let span = tracing::info_span!("auth", token = "tok_abc");
+    let _enter = span.enter();
+    tracing::info!("login tok_abc ok");
+
+    let last = cr.last().unwrap();
+    assert!(
+        last.contains("login *** ok"),
+        "message not redacted: {last}"
+    );
+    assert!(!last.contains("tok_abc"), "span field not redacted: {last}");
+}
+
 #[test]
 fn test_record_dynamic_fields() {
     let (c, cr) = make_sub(log_levels::TRACE);
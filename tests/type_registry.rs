@@ -0,0 +1,30 @@
+//! Runs in its own test binary: resets the process-wide custom type registry.
+#![cfg(feature = "testing")]
+
+use consola::constants::{
+    LOG_TYPES, custom_type, custom_types, define_type, reset_custom_types, scoped_custom_types,
+};
+use consola::{LogType, log_levels};
+
+#[test]
+fn test_reset_and_scoped_custom_types() {
+    define_type("registry-deploy", log_levels::INFO, Some("🚀"), None).unwrap();
+    assert!(custom_type("registry-deploy").is_some());
+
+    reset_custom_types();
+    assert!(custom_type("registry-deploy").is_none());
+    assert!(custom_types().is_empty());
+    assert_eq!("info".parse::<LogType>(), Ok(LogType::Info));
+    assert_eq!(LOG_TYPES.len(), consola::constants::all_log_types().len());
+
+    define_type("registry-kept", log_levels::LOG, None, None).unwrap();
+    {
+        let _guard = scoped_custom_types();
+        define_type("registry-scoped", log_levels::LOG, None, None).unwrap();
+        define_type("registry-kept", log_levels::WARN, None, None).unwrap();
+        assert_eq!(custom_types().len(), 2);
+    }
+    assert!(custom_type("registry-scoped").is_none());
+    let kept = consola::constants::custom_type_def("registry-kept").unwrap();
+    assert_eq!(kept.level, log_levels::LOG);
+}
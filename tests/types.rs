@@ -3,11 +3,13 @@
 use consola::{
     LogType, log_levels,
     types::{
-        ConfirmPromptOptions, ConsolaOptions, ErrorInfo, FormatOptions, LogContext, LogObject,
-        LogObjectInput, MultiSelectOptions, PromptCommonOptions, PromptOptions, Reporter,
-        SelectOption, SelectPromptOptions, TextPromptOptions,
+        AutocompletePromptOptions, ConfirmPromptOptions, ConsolaOptions, ErrorInfo, FormatOptions,
+        LogContext, LogObject, LogObjectInput, MultiSelectOptions, NumberPromptOptions,
+        PasswordPromptOptions, PromptCommonOptions, PromptOptions, Reporter, SelectOption,
+        SelectPromptOptions, TextPromptOptions,
     },
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // ---------------------------------------------------------------------------
@@ -39,8 +41,12 @@ fn format_options_default() {
     let opts = FormatOptions::default();
     assert!(opts.date);
     assert!(!opts.colors);
-    assert!(opts.compact);
+    // Compact mode is opt-in; see the comment in `FormatOptions::default`.
+    assert!(!opts.compact);
     assert_eq!(opts.error_level, 0);
+    assert!(!opts.color_message_by_level);
+    assert!(!opts.inline_error_chain);
+    assert!(!opts.mark_continuations);
 }
 
 // ---------------------------------------------------------------------------
@@ -77,12 +83,72 @@ fn log_object_input_arg() {
     assert_eq!(input.args, vec!["single"]);
 }
 
+#[test]
+fn log_object_input_numeric_args() {
+    let owned = String::from("owned");
+    let input = LogObjectInput::new()
+        .arg(-7i32)
+        .arg(7u32)
+        .arg(usize::MAX)
+        .arg(-3isize)
+        .arg(1.5f32)
+        .arg(u64::MAX)
+        .arg(0.1f64)
+        .arg('x')
+        .arg(&owned);
+    assert_eq!(
+        input.args,
+        vec![
+            "-7",
+            "7",
+            &usize::MAX.to_string(),
+            "-3",
+            "1.5",
+            "18446744073709551615",
+            "0.1",
+            "x",
+            "owned",
+        ]
+    );
+}
+
 #[test]
 fn log_object_input_additional() {
     let input = LogObjectInput::new().additional("extra");
     assert_eq!(input.additional.as_deref(), Some("extra"));
 }
 
+#[test]
+fn log_object_input_fields_accumulate() {
+    let input = LogObjectInput::new()
+        .field("port", 8080)
+        .field("host", "localhost")
+        .field("tls", true);
+    assert_eq!(
+        input.fields,
+        vec![
+            ("port".to_string(), "8080".to_string()),
+            ("host".to_string(), "localhost".to_string()),
+            ("tls".to_string(), "true".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn log_object_input_duplicate_field_last_writer_wins() {
+    let input = LogObjectInput::new()
+        .field("attempt", 1)
+        .field("user", "ann")
+        .field("attempt", 2);
+    assert_eq!(
+        input.fields,
+        vec![
+            ("attempt".to_string(), "2".to_string()),
+            ("user".to_string(), "ann".to_string()),
+        ]
+    );
+}
+
 #[test]
 fn log_object_input_chained() {
     let input = LogObjectInput::new()
@@ -118,6 +184,44 @@ fn log_object_new_all_types() {
     }
 }
 
+#[test]
+fn log_object_fingerprint_bytes() {
+    let mut obj = LogObject::new(LogType::Warn);
+    obj.tag = "db".into();
+    obj.args = vec!["1".into()];
+    obj.fields = vec![("k".into(), "v".into())];
+    #[rustfmt::skip]
+    let expected: Vec<u8> = vec![
+        0x01, 0, 0, 0, 4, b'w', b'a', b'r', b'n',
+        0x02, 0, 0, 0, 4, 0, 0, 0, 1,
+        0x03, 0, 0, 0, 2, b'd', b'b',
+        0x05, 0, 0, 0, 1, b'1',
+        0x06, 0, 0, 0, 1, b'k',
+        0x07, 0, 0, 0, 1, b'v',
+        0x08, 0, 0, 0, 1, 0,
+    ];
+    assert_eq!(obj.fingerprint(false), expected);
+}
+
+#[test]
+fn log_object_fingerprint_ignores_timestamp_and_tracks_raw() {
+    let mut a = LogObject::new(LogType::Info);
+    a.args = vec!["same".into()];
+    let mut b = a.clone();
+    b.timestamp_ms += 5_000;
+    assert_eq!(a.fingerprint(false), b.fingerprint(false));
+    assert_ne!(a.fingerprint(false), a.fingerprint(true));
+}
+
+#[test]
+fn log_object_fingerprint_keeps_arg_boundaries() {
+    let mut a = LogObject::new(LogType::Info);
+    a.args = vec!["a b".into()];
+    let mut b = a.clone();
+    b.args = vec!["a".into(), "b".into()];
+    assert_ne!(a.fingerprint(false), b.fingerprint(false));
+}
+
 #[cfg(feature = "jiff")]
 #[test]
 fn log_object_timestamp_jiff() {
@@ -385,6 +489,43 @@ fn multi_select_options() {
     assert_eq!(opts.required, Some(true));
 }
 
+// ---------------------------------------------------------------------------
+// PasswordPromptOptions / NumberPromptOptions / AutocompletePromptOptions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn password_prompt_options() {
+    let opts = PasswordPromptOptions {
+        common: PromptCommonOptions { cancel: None },
+        placeholder: Some("secret".into()),
+    };
+    assert_eq!(opts.placeholder.as_deref(), Some("secret"));
+}
+
+#[test]
+fn number_prompt_options() {
+    let opts = NumberPromptOptions {
+        common: PromptCommonOptions { cancel: None },
+        default: Some(1.5),
+        min: Some(0.0),
+        max: None,
+    };
+    assert_eq!(opts.default, Some(1.5));
+    assert_eq!(opts.min, Some(0.0));
+    assert!(opts.max.is_none());
+}
+
+#[test]
+fn autocomplete_prompt_options() {
+    let opts = AutocompletePromptOptions {
+        common: PromptCommonOptions { cancel: None },
+        candidates: vec!["one".into(), "two".into()],
+        default: None,
+        placeholder: None,
+    };
+    assert_eq!(opts.candidates.len(), 2);
+}
+
 // ---------------------------------------------------------------------------
 // PromptOptions enum matching
 // ---------------------------------------------------------------------------
@@ -450,6 +591,21 @@ fn prompt_options_match_multiselect() {
     }
 }
 
+#[test]
+fn prompt_options_match_number() {
+    let popts = PromptOptions::Number(NumberPromptOptions {
+        common: PromptCommonOptions { cancel: None },
+        default: None,
+        min: None,
+        max: Some(10.0),
+    });
+
+    match &popts {
+        PromptOptions::Number(n) => assert_eq!(n.max, Some(10.0)),
+        _ => panic!("expected Number variant"),
+    }
+}
+
 #[test]
 fn prompt_options_debug() {
     let popts = PromptOptions::Text(TextPromptOptions {
@@ -638,7 +794,12 @@ fn log_object_input_all_fields() {
             backtrace: None,
             cause: None,
         }),
+        fields: vec![("k".into(), "v".into())],
+        trace_id: Some("t".into()),
+        prefix: Some("p".into()),
+        extensions: Default::default(),
     };
+    assert_eq!(input.trace_id.as_deref(), Some("t"));
     assert_eq!(input.level, Some(log_levels::INFO));
     assert_eq!(input.r#type, Some(LogType::Info));
     assert_eq!(input.tag.as_deref(), Some("tag"));
@@ -694,3 +855,244 @@ fn test_terminal_width_integration() {
         Some(w) => assert!(w > 0, "terminal width should be positive, got {w}"),
     }
 }
+
+fn env_lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let map: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |key| map.get(key).cloned()
+}
+
+#[test]
+fn consola_options_from_env_vars_empty() {
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[]));
+    let defaults = ConsolaOptions::default();
+    assert_eq!(opts.level, defaults.level);
+    assert!(opts.reporters.is_empty());
+    assert_eq!(opts.format_options.colors, defaults.format_options.colors);
+    assert_eq!(opts.format_options.columns, defaults.format_options.columns);
+}
+
+#[test]
+fn consola_options_from_env_vars_level() {
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_LEVEL", "debug")]));
+    assert_eq!(opts.level, log_levels::DEBUG);
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_LEVEL", "1")]));
+    assert_eq!(opts.level, 1);
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_LEVEL", "WARNING")]));
+    assert_eq!(opts.level, log_levels::WARN);
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_LEVEL", "nope")]));
+    assert_eq!(opts.level, ConsolaOptions::default().level);
+}
+
+#[test]
+fn consola_options_from_env_vars_format() {
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_FORMAT", "basic")]));
+    assert_eq!(opts.reporters.len(), 1);
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_FORMAT", "fancy")]));
+    assert_eq!(opts.reporters.len(), 1);
+    let opts = ConsolaOptions::from_env_vars(env_lookup(&[("CONSOLA_FORMAT", "json")]));
+    assert!(opts.reporters.is_empty());
+}
+
+#[test]
+fn consola_options_from_env_vars_for_terminal() {
+    let opts = ConsolaOptions::from_env_vars_for(env_lookup(&[("CONSOLA_LEVEL", "debug")]), true);
+    assert_eq!(opts.level, log_levels::DEBUG);
+    assert!(opts.format_options.colors);
+    let opts = ConsolaOptions::from_env_vars_for(env_lookup(&[("NO_COLOR", "1")]), true);
+    assert!(!opts.format_options.colors);
+    let opts = ConsolaOptions::from_env_vars_for(env_lookup(&[]), false);
+    assert!(!opts.format_options.colors);
+}
+
+#[test]
+fn format_options_from_env_vars_colors() {
+    let opts = FormatOptions::from_env_vars(env_lookup(&[("NO_COLOR", "1")]));
+    assert!(!opts.colors);
+    let opts = FormatOptions::from_env_vars(env_lookup(&[("FORCE_COLOR", "1")]));
+    assert!(opts.colors);
+    let opts = FormatOptions::from_env_vars(env_lookup(&[("FORCE_COLOR", "0")]));
+    assert!(!opts.colors);
+    let opts = FormatOptions::from_env_vars(env_lookup(&[("NO_COLOR", ""), ("FORCE_COLOR", "1")]));
+    assert!(!opts.colors);
+}
+
+#[test]
+fn format_options_colors_follow_terminal_without_override() {
+    let decide =
+        |vars: &[(&str, &str)], tty| FormatOptions::from_env_vars_for(env_lookup(vars), tty).colors;
+    assert!(decide(&[], true));
+    assert!(!decide(&[], false));
+    assert!(decide(&[("FORCE_COLOR", "1")], false));
+    assert!(!decide(&[("FORCE_COLOR", "0")], true));
+    assert!(!decide(&[("NO_COLOR", "1")], true));
+    assert!(!decide(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")], true));
+    // Other variables are still applied.
+    let opts = FormatOptions::from_env_vars_for(env_lookup(&[("CONSOLA_DATE", "0")]), true);
+    assert!(!opts.date);
+}
+
+#[test]
+fn format_options_from_env_vars_bools_and_columns() {
+    let opts = FormatOptions::from_env_vars(env_lookup(&[
+        ("CONSOLA_COMPACT", "yes"),
+        ("CONSOLA_DATE", "off"),
+        ("COLUMNS", "120"),
+    ]));
+    assert!(opts.compact);
+    assert!(!opts.date);
+    assert_eq!(opts.columns, Some(120));
+
+    let opts = FormatOptions::from_env_vars(env_lookup(&[
+        ("CONSOLA_COLUMNS", "60"),
+        ("COLUMNS", "120"),
+        ("CONSOLA_COMPACT", "maybe"),
+    ]));
+    assert_eq!(opts.columns, Some(60));
+    assert_eq!(opts.compact, FormatOptions::default().compact);
+}
+
+#[test]
+fn error_info_inline_chain() {
+    let err = ErrorInfo {
+        message: "a".into(),
+        cause: Some(Box::new(ErrorInfo {
+            message: String::new(),
+            cause: Some(Box::new(ErrorInfo {
+                message: "c".into(),
+                ..ErrorInfo::default()
+            })),
+            ..ErrorInfo::default()
+        })),
+        ..ErrorInfo::default()
+    };
+    assert_eq!(err.inline_chain(0), "a: c");
+    assert_eq!(err.inline_chain(1), "a");
+    assert_eq!(ErrorInfo::default().inline_chain(0), "");
+}
+
+#[test]
+fn error_info_abbreviated() {
+    let err = ErrorInfo {
+        message: "a".into(),
+        stack: Some("at main".into()),
+        cause: Some(Box::new(ErrorInfo {
+            message: "b".into(),
+            ..ErrorInfo::default()
+        })),
+        ..ErrorInfo::default()
+    };
+    let short = err.abbreviated();
+    assert_eq!(short.message, "a (same cause as above)");
+    assert!(short.cause.is_none() && short.stack.is_none());
+
+    let plain = ErrorInfo {
+        message: "a".into(),
+        ..ErrorInfo::default()
+    };
+    assert_eq!(plain.abbreviated(), plain);
+}
+
+#[derive(Debug)]
+struct ChainError {
+    msg: &'static str,
+    source: Option<Box<ChainError>>,
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.msg)
+    }
+}
+
+impl std::error::Error for ChainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+fn chain_error() -> ChainError {
+    ChainError {
+        msg: "read config",
+        source: Some(Box::new(ChainError {
+            msg: "open file",
+            source: None,
+        })),
+    }
+}
+
+#[test]
+fn error_info_from_error_follows_source_chain() {
+    let info = ErrorInfo::from_error(&chain_error());
+    assert_eq!(info.message, "read config");
+    assert_eq!(info.cause.as_ref().unwrap().message, "open file");
+    assert!(info.cause.as_ref().unwrap().cause.is_none());
+    assert!(info.stack.is_none());
+}
+
+#[test]
+fn log_object_input_attach_error() {
+    let input = LogObjectInput::new().attach_error(&chain_error());
+    let err = input.error.unwrap();
+    assert_eq!(err.inline_chain(0), "read config: open file");
+}
+
+#[test]
+fn log_object_input_error_arg_mid_list_renders_chain_below() {
+    let sink = consola::TestSink::new();
+    let c = consola::Consola::new(ConsolaOptions {
+        reporters: vec![Box::new(consola::reporters::BasicReporter::new()) as Box<dyn Reporter>],
+        format_options: FormatOptions {
+            date: false,
+            ..FormatOptions::default()
+        },
+        out_sink: Arc::new(sink.clone()),
+        err_sink: Arc::new(sink.clone()),
+        ..ConsolaOptions::default()
+    });
+    let input = LogObjectInput::new()
+        .type_(LogType::Warn)
+        .arg("context")
+        .error_arg(&chain_error())
+        .arg("retrying");
+    assert_eq!(input.args, vec!["context", "retrying"]);
+    c.log_obj(&input);
+
+    let out = sink.contents();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "[warn] context retrying");
+    let msg = lines
+        .iter()
+        .position(|l| l.contains("read config"))
+        .unwrap();
+    let cause = lines
+        .iter()
+        .position(|l| l.contains("[cause]: open file"))
+        .unwrap();
+    assert!(0 < msg && msg < cause, "{out}");
+}
+
+#[test]
+fn log_object_input_second_error_arg_is_inlined() {
+    let input = LogObjectInput::new()
+        .error_arg(&chain_error())
+        .error_arg(&chain_error());
+    assert_eq!(input.error.unwrap().message, "read config");
+    assert_eq!(input.args, vec!["read config: open file"]);
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn error_info_with_backtrace_populates_stack() {
+    let info = ErrorInfo::from_error(&chain_error()).with_backtrace();
+    let stack = info.stack.expect("stack should be captured");
+    assert!(!stack.is_empty());
+    assert!(!stack.contains("file://"));
+    let cwd = std::env::current_dir().unwrap().display().to_string();
+    assert!(!stack.contains(&format!("{cwd}/")));
+    assert!(info.backtrace.is_some());
+}
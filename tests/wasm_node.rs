@@ -50,6 +50,13 @@ fn browser_format_with_tag_and_type() {
         icon: None,
         style: None,
         error: None,
+        group_depth: 0,
+        group_event: None,
+        fields: Vec::new(),
+        repeated: 0,
+        trace_id: None,
+        prefix: None,
+        extensions: Default::default(),
     };
     let ctx = LogContext {
         options: Arc::new(ConsolaOptions::default()),